@@ -9,6 +9,23 @@ use self::time::{Duration, PreciseTime};
 use points::*;
 use convexhull::*;
 use inputset::*;
+use distribution::Dist;
+use prefilter::Prefilter;
+use perf::{Counters, PerfCounters};
+use kinetic::KineticHull;
+use concave::concave_hull;
+use hull::{hausdorff_distance, twice_signed_area, Line, PointLocator};
+use io::hull_of_file;
+#[cfg(feature = "rational")]
+use hull::ConvexHull;
+#[cfg(feature = "rational")]
+use rational::exact_hull;
+
+use std::fs;
+use std::io::{self, Write};
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::thread;
 
 /// Provides the duration in various
 /// time units
@@ -50,11 +67,10 @@ impl Time {
     }
 }
 
-/// One another convenient function to print the results
-/// The output is printed in console
-pub fn benchmark_algorithm(algorithm: Algorithm, shape: Shape, presort: bool) {
+/// Sample sizes every benchmark is run with
+fn sample_sizes() -> Vec<Number> {
     use self::Number::*;
-    let sample_sizes: Vec<Number> = vec![
+    vec![
         Hundred,
         Thousand,
         TenThousand,
@@ -67,33 +83,771 @@ pub fn benchmark_algorithm(algorithm: Algorithm, shape: Shape, presort: bool) {
         Million.times(5),
         Million.times(7),
         TenMillion,
-    ];
+    ]
+}
+
+/// Settings shared by all the benchmark modes
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// leave the sorting of the input out of the measured time
+    pub presort: bool,
+    /// number of times every input set is timed
+    pub runs: usize,
+    /// drop outlying samples before the statistics are computed
+    pub reject_outliers: bool,
+    /// directory to write every individual timing sample to
+    pub dump_samples: Option<PathBuf>,
+    /// watch for cpu frequency drops and slowdowns across runs
+    pub detect_throttling: bool,
+    /// milli seconds to pause after a throttled measurement
+    pub cooldown_ms: u64,
+    /// filter run on every input set before the algorithm, inside the measured time
+    pub prefilter: Option<Prefilter>,
+    /// count instructions, cache misses and branch mispredictions of
+    /// every run (needs the perf_counters feature on Linux)
+    pub perf_counters: bool,
+}
+
+impl Options {
+    /// A single timed run per input set without outlier rejection
+    pub fn new(presort: bool) -> Options {
+        Options {
+            presort,
+            runs: 1,
+            reject_outliers: false,
+            dump_samples: None,
+            detect_throttling: false,
+            cooldown_ms: 0,
+            prefilter: None,
+            perf_counters: false,
+        }
+    }
+}
+
+/// The timing samples of the repeated runs on one input set
+#[derive(Debug, Clone)]
+pub struct Measurement {
+    raw_samples: Vec<f64>,
+    samples: Vec<f64>,
+    rejected: usize,
+    throttled: bool,
+    counters: Option<Counters>,
+}
+
+impl Measurement {
+    /// Collects samples, optionally rejecting the outliers among them
+    ///
+    /// A sample is an outlier when it is more than 3.5 scaled median
+    /// absolute deviations (MAD) away from the median of the samples.
+    pub fn new(samples: Vec<f64>, reject_outliers: bool) -> Measurement {
+        if !reject_outliers || samples.len() < 3 {
+            return Measurement {
+                raw_samples: samples.clone(),
+                samples,
+                rejected: 0,
+                throttled: false,
+                counters: None,
+            };
+        }
+        let center = median(&samples);
+        let deviations: Vec<f64> = samples.iter().map(|sample| (sample - center).abs()).collect();
+        // 1.4826 scales the MAD to the standard deviation of normal data
+        let mad = 1.4826 * median(&deviations);
+        let total = samples.len();
+        let kept: Vec<f64> = samples
+            .iter()
+            .cloned()
+            .filter(|sample| mad == 0.0 || (sample - center).abs() / mad <= 3.5)
+            .collect();
+        Measurement {
+            raw_samples: samples,
+            rejected: total - kept.len(),
+            samples: kept,
+            throttled: false,
+            counters: None,
+        }
+    }
+
+    /// The samples that were kept, in milli seconds
+    pub fn samples(&self) -> &[f64] {
+        &self.samples
+    }
+
+    /// Every sample in the order they were timed, including outliers
+    pub fn raw_samples(&self) -> &[f64] {
+        &self.raw_samples
+    }
+
+    /// Number of samples dropped as outliers
+    pub fn rejected(&self) -> usize {
+        self.rejected
+    }
+
+    /// True when the cpu looked throttled while the samples were taken
+    pub fn throttled(&self) -> bool {
+        self.throttled
+    }
+
+    /// The hardware counters of a run, averaged over all the runs
+    pub fn counters(&self) -> Option<Counters> {
+        self.counters
+    }
+
+    /// Mean of the kept samples in milli seconds
+    pub fn mean(&self) -> f64 {
+        self.samples.iter().sum::<f64>() / self.samples.len() as f64
+    }
+
+    /// Median of the kept samples in milli seconds
+    pub fn median(&self) -> f64 {
+        median(&self.samples)
+    }
+}
+
+/// Median of a non empty list of values
+fn median(values: &[f64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    }
+}
+
+/// One another convenient function to print the results
+/// The output is printed in console
+pub fn benchmark_algorithm(algorithm: Algorithm, shape: Shape, presort: bool) {
+    benchmark_algorithm_with(algorithm, shape, &Options::new(presort));
+}
+
+/// Same as benchmark_algorithm with all the benchmark options
+///
+/// For the approximate hull the Hausdorff distance to the exact hull
+/// is printed as well, as `<label>_hausdorff`.
+pub fn benchmark_algorithm_with(algorithm: Algorithm, shape: Shape, options: &Options) {
+    let mut output: Vec<(u64, Measurement)> = Vec::new();
+    let mut errors: Vec<(u64, f64)> = Vec::new();
+    for sample_size in sample_sizes() {
+        let input_set: Vec<Point2D> = generate(shape, sample_size);
+        output.push((sample_size.val(), measure(algorithm, &input_set, options)));
+        if let Algorithm::Approximate(_) = algorithm {
+            let approximate = execute(algorithm, &mut input_set.clone());
+            errors.push((sample_size.val(), hausdorff_distance(&approximate, &convex_hull_of(&input_set))));
+        }
+    }
+    let label = format!("{:?}_{:?}", algorithm, shape);
+    report(&label, &output, options);
+    if !errors.is_empty() {
+        println!("{}_hausdorff : {:?}", label, errors);
+    }
+}
+
+/// Prints for every sample size the extent of an input set of the
+/// shape, as the least x, least y, greatest x and greatest y of its
+/// bounding box, the number of distinct points, since copies make an
+/// input set look larger than it is, and
+/// whether it is in convex position, with all of its points on the hull
+pub fn describe_shape(shape: Shape) {
+    let mut extents: Vec<(u64, [f64; 4])> = Vec::new();
+    let mut distinct: Vec<(u64, usize)> = Vec::new();
+    let mut convex_position: Vec<(u64, bool)> = Vec::new();
+    for sample_size in sample_sizes() {
+        let input_set = generate(shape, sample_size);
+        let (low, high) = bounding_box(&input_set);
+        extents.push((sample_size.val(), [low.x, low.y, high.x, high.y]));
+        let mut points = input_set.clone();
+        dedup_points(&mut points, 0.0);
+        distinct.push((sample_size.val(), points.len()));
+        convex_position.push((sample_size.val(), in_convex_position(&input_set)));
+    }
+    println!("{:?}_extent : {:?}", shape, extents);
+    println!("{:?}_distinct : {:?}", shape, distinct);
+    println!("{:?}_convex_position : {:?}", shape, convex_position);
+}
+
+/// Benchmarks an algorithm on the same input sets presented
+/// shuffled, sorted by x-coordinate and reverse sorted.
+///
+/// The algorithm is executed on the arranged input set itself, only
+/// the Graham scans sort it by polar angle first, so the order shows
+/// in their sorting time. The output is printed in console, one line
+/// per order
+pub fn benchmark_input_order(algorithm: Algorithm, shape: Shape, options: &Options) {
+    let orders = [InputOrder::Shuffled, InputOrder::XSorted, InputOrder::ReverseSorted];
+    let mut outputs: Vec<Vec<(u64, Measurement)>> = vec![Vec::new(); orders.len()];
+    for sample_size in sample_sizes() {
+        let input_set: Vec<Point2D> = generate(shape, sample_size);
+        for (order, output) in orders.iter().zip(outputs.iter_mut()) {
+            let mut ordered_input_set = input_set.clone();
+            order.arrange(&mut ordered_input_set);
+            output.push((sample_size.val(), measure(algorithm, &ordered_input_set, options)));
+        }
+    }
+    for (order, output) in orders.iter().zip(outputs.iter()) {
+        report(&format!("{:?}_{:?}_{:?}", algorithm, shape, order), output, options);
+    }
+}
+
+/// Benchmarks an algorithm on input sets sampled from a distribution
+/// The output is printed in console under the given name
+pub fn benchmark_distribution(algorithm: Algorithm, name: &str, dist: &Dist, options: &Options) {
+    benchmark_generator(algorithm, name, |sample_size| dist.take(sample_size.val()), options);
+}
+
+/// Benchmarks an algorithm on the input sets made by any generator,
+/// like random_walk. The output is printed in console under the given name
+pub fn benchmark_generator<F: Fn(Number) -> Vec<Point2D>>(
+    algorithm: Algorithm,
+    name: &str,
+    generator: F,
+    options: &Options,
+) {
+    let mut output: Vec<(u64, Measurement)> = Vec::new();
+    for sample_size in sample_sizes() {
+        let input_set: Vec<Point2D> = generator(sample_size);
+        output.push((sample_size.val(), measure(algorithm, &input_set, options)));
+    }
+    report(&format!("{:?}_{}", algorithm, name), &output, options);
+}
+
+/// Benchmarks an algorithm on input sets of one fixed size whose
+/// hulls have the given numbers of vertices.
+///
+/// Unlike benchmark_algorithm the results are indexed by the
+/// hull size h, which shows how output sensitive an algorithm is.
+/// Only the Graham scans pay for the polar sort of `prepare`, the
+/// O(n log n) of which would hide the O(nh) of Jarvis march and the
+/// O(n log h) of Chan's algorithm. The output is printed in console
+/// and returned for plotting
+pub fn benchmark_hull_size(
+    algorithm: Algorithm,
+    sample_size: Number,
+    hull_sizes: &[u64],
+    options: &Options,
+) -> Vec<(u64, f64)> {
+    let mut output: Vec<(u64, Measurement)> = Vec::new();
+    for &hull_size in hull_sizes {
+        let input_set: Vec<Point2D> = generate(Shape::Polygon(hull_size), sample_size);
+        output.push((hull_size, measure(algorithm, &input_set, options)));
+    }
+    report(&format!("{:?}_n{}", algorithm, sample_size.val()), &output, options);
+    output
+        .iter()
+        .map(|&(hull_size, ref measurement)| (hull_size, measurement.mean()))
+        .collect()
+}
+
+/// Compares keeping the hull of moving points up to date with a
+/// KineticHull to recomputing it with each algorithm at every one of
+/// the given number of time steps.
+///
+/// The points of the shape move with random velocities of at most
+/// 0.01 units per time step, and every algorithm is timed on the same
+/// points. Sizes stop at a hundred thousand points, since every
+/// kinetic event takes linear time. The output is printed in console,
+/// one line per algorithm, one for the kinetic hull and one with the
+/// number of kinetic events per size.
+pub fn benchmark_kinetic(algorithms: &[Algorithm], shape: Shape, steps: usize, options: &Options) {
+    let mut outputs: Vec<Vec<(u64, Measurement)>> = vec![Vec::new(); algorithms.len()];
+    let mut kinetic_output: Vec<(u64, Measurement)> = Vec::new();
+    let mut events: Vec<(u64, usize)> = Vec::new();
+    for sample_size in sample_sizes().into_iter().take(4) {
+        let moving_points = generate_moving(shape, sample_size, 0.01);
+        for (&algorithm, output) in algorithms.iter().zip(outputs.iter_mut()) {
+            let mut samples: Vec<f64> = Vec::new();
+            for _ in 0..options.runs.max(1) {
+                let start = PreciseTime::now();
+                for step in 1..steps + 1 {
+                    let mut input_set: Vec<Point2D> =
+                        moving_points.iter().map(|point| point.at(step as f64)).collect();
+                    execute(algorithm, &mut prepare(algorithm, &mut input_set));
+                }
+                samples.push(Time::new(start.to(PreciseTime::now())).milli_seconds());
+            }
+            output.push((sample_size.val(), Measurement::new(samples, options.reject_outliers)));
+        }
+        let mut samples: Vec<f64> = Vec::new();
+        let mut event_count = 0;
+        for _ in 0..options.runs.max(1) {
+            let points = moving_points.clone();
+            let start = PreciseTime::now();
+            let mut kinetic_hull = KineticHull::new(points, 0.0);
+            for step in 1..steps + 1 {
+                kinetic_hull.advance(step as f64);
+                kinetic_hull.hull();
+            }
+            samples.push(Time::new(start.to(PreciseTime::now())).milli_seconds());
+            event_count = kinetic_hull.events();
+        }
+        kinetic_output.push((sample_size.val(), Measurement::new(samples, options.reject_outliers)));
+        events.push((sample_size.val(), event_count));
+    }
+    for (algorithm, output) in algorithms.iter().zip(outputs.iter()) {
+        report(&format!("{:?}_{:?}_Moving", algorithm, shape), output, options);
+    }
+    report(&format!("Kinetic_{:?}", shape), &kinetic_output, options);
+    println!("Kinetic_{:?}_events : {:?}", shape, events);
+}
+
+/// Compares the concave hull with k neighbours to the convex hull
+/// algorithms on the same input sets of the shape
+///
+/// Sizes stop at a hundred thousand points, since the concave hull is
+/// checked to contain every point. The output is printed in console,
+/// one line per algorithm, one for `Concave(k)` and one with the share
+/// of the convex hull area the concave hull covers per size.
+pub fn benchmark_concave(k: usize, algorithms: &[Algorithm], shape: Shape, options: &Options) {
+    let concave = Algorithm::Concave(k);
+    let mut outputs: Vec<Vec<(u64, Measurement)>> = vec![Vec::new(); algorithms.len() + 1];
+    let mut areas: Vec<(u64, f64)> = Vec::new();
+    for sample_size in sample_sizes().into_iter().take(4) {
+        let input_set: Vec<Point2D> = generate(shape, sample_size);
+        for (&algorithm, output) in algorithms.iter().chain(Some(&concave)).zip(outputs.iter_mut()) {
+            output.push((sample_size.val(), measure(algorithm, &input_set, options)));
+        }
+        let share = twice_signed_area(&concave_hull(&input_set, k)) / twice_signed_area(&convex_hull_of(&input_set));
+        areas.push((sample_size.val(), share));
+    }
+    for (algorithm, output) in algorithms.iter().chain(Some(&concave)).zip(outputs.iter()) {
+        report(&format!("{:?}_{:?}", algorithm, shape), output, options);
+    }
+    println!("{:?}_{:?}_area : {:?}", concave, shape, areas);
+}
 
-    let mut output: Vec<(u64, f64)> = Vec::new();
-    for sample_size in sample_sizes {
-        let mut input_set: Vec<Point2D> = generate(shape, sample_size);
-        let result = (
+/// Compares the hull of every algorithm with the ground truth of
+/// `rational::exact_hull` on the same input sets of the shape
+///
+/// Sizes stop at a hundred thousand points, since the rational hull is
+/// slow. The output is printed in console, one line per algorithm with
+/// the number of its vertices not in the exact hull and of exact
+/// vertices it misses per size, as `<algorithm>_<shape>_wrong`.
+/// `Approximate` hulls are not meant to match. Needs the `rational`
+/// feature.
+#[cfg(feature = "rational")]
+pub fn benchmark_correctness(algorithms: &[Algorithm], shape: Shape) {
+    let mut outputs: Vec<Vec<(u64, usize)>> = vec![Vec::new(); algorithms.len()];
+    for sample_size in sample_sizes().into_iter().take(4) {
+        if sample_size.val() < shape.num_of_vertices() {
+            continue;
+        }
+        let input_set: Vec<Point2D> = generate(shape, sample_size);
+        let exact = ConvexHull::new(exact_hull(&input_set));
+        for (&algorithm, output) in algorithms.iter().zip(outputs.iter_mut()) {
+            let hull = execute(algorithm, &mut prepare(algorithm, &mut input_set.clone()));
+            let diff = ConvexHull::new(hull).diff(&exact, 0.0);
+            output.push((sample_size.val(), diff.only_in_first.len() + diff.only_in_second.len()));
+        }
+    }
+    for (algorithm, output) in algorithms.iter().zip(outputs.iter()) {
+        println!("{:?}_{:?}_wrong : {:?}", algorithm, shape, output);
+    }
+}
+
+/// Fractional bits of the fixed point coordinates of benchmark_precision
+pub const FIXED_POINT_BITS: i32 = 40;
+
+/// The points in i64 fixed point coordinates with FIXED_POINT_BITS
+/// fractional bits, rounded to the nearest
+///
+/// The generated shapes lie within a few units of the origin, so they
+/// keep about as many bits as their f64 coordinates have.
+pub fn to_fixed_point(input_set: &[Point2D]) -> Vec<Point2D<i64>> {
+    let scale = 2f64.powi(FIXED_POINT_BITS);
+    input_set
+        .iter()
+        .map(|point| Point2D::new((point.x * scale).round() as i64, (point.y * scale).round() as i64))
+        .collect()
+}
+
+/// Compares every algorithm on f32, f64 and i64 copies of the same
+/// input sets
+///
+/// Every run times a copy of the input set, prepare and execute,
+/// whatever the presort and prefilter options, with every turn taken in
+/// the coordinate type: rounded in f32 and f64, exact in i64, where the
+/// points are the fixed point ones of `to_fixed_point`. They are
+/// reported as `<algorithm>_<shape>_f32` and `<algorithm>_<shape>_i64`
+/// next to the f64 runs as `<algorithm>_<shape>`. `convex_hull_exact`
+/// is reported as `MonotoneChain_<shape>` with the same suffixes. Sizes
+/// stop at a million points.
+pub fn benchmark_precision(algorithms: &[Algorithm], shape: Shape, options: &Options) {
+    let mut outputs: Vec<Vec<(u64, Measurement)>> = vec![Vec::new(); algorithms.len()];
+    let mut f32_outputs: Vec<Vec<(u64, Measurement)>> = vec![Vec::new(); algorithms.len()];
+    let mut i64_outputs: Vec<Vec<(u64, Measurement)>> = vec![Vec::new(); algorithms.len()];
+    let mut chain: Vec<(u64, Measurement)> = Vec::new();
+    let mut f32_chain: Vec<(u64, Measurement)> = Vec::new();
+    let mut i64_chain: Vec<(u64, Measurement)> = Vec::new();
+    let time = |run: &dyn Fn() -> usize| {
+        let mut samples: Vec<f64> = Vec::new();
+        for _ in 0..options.runs.max(1) {
+            let start = PreciseTime::now();
+            black_box(run());
+            samples.push(Time::new(start.to(PreciseTime::now())).milli_seconds());
+        }
+        Measurement::new(samples, options.reject_outliers)
+    };
+    for sample_size in sample_sizes().into_iter().take(8) {
+        if sample_size.val() < shape.num_of_vertices() {
+            continue;
+        }
+        let input_set: Vec<Point2D> = generate(shape, sample_size);
+        let f32_input_set: Vec<Point2D<f32>> = input_set.iter().map(|point| point.to_f32()).collect();
+        let i64_input_set = to_fixed_point(&input_set);
+        for (i, &algorithm) in algorithms.iter().enumerate() {
+            let run = || execute(algorithm, &mut prepare(algorithm, &mut input_set.clone())).len();
+            outputs[i].push((sample_size.val(), time(&run)));
+            let f32_run = || execute(algorithm, &mut prepare(algorithm, &mut f32_input_set.clone())).len();
+            f32_outputs[i].push((sample_size.val(), time(&f32_run)));
+            let i64_run = || execute(algorithm, &mut prepare(algorithm, &mut i64_input_set.clone())).len();
+            i64_outputs[i].push((sample_size.val(), time(&i64_run)));
+        }
+        chain.push((sample_size.val(), time(&|| convex_hull_exact(&input_set).len())));
+        f32_chain.push((sample_size.val(), time(&|| convex_hull_exact(&f32_input_set).len())));
+        i64_chain.push((sample_size.val(), time(&|| convex_hull_exact(&i64_input_set).len())));
+    }
+    for (i, algorithm) in algorithms.iter().enumerate() {
+        report(&format!("{:?}_{:?}", algorithm, shape), &outputs[i], options);
+        report(&format!("{:?}_{:?}_f32", algorithm, shape), &f32_outputs[i], options);
+        report(&format!("{:?}_{:?}_i64", algorithm, shape), &i64_outputs[i], options);
+    }
+    report(&format!("MonotoneChain_{:?}", shape), &chain, options);
+    report(&format!("MonotoneChain_{:?}_f32", shape), &f32_chain, options);
+    report(&format!("MonotoneChain_{:?}_i64", shape), &i64_chain, options);
+}
+
+/// Times scalar algorithms and their multi-threaded variants on the
+/// same input sets and prints the speedups of the variants in one table
+///
+/// Every entry pairs a scalar algorithm with its variants, like
+/// QuickHull with ParallelQuickHull or DivideAndConquer(1) with more
+/// threads. The sorting of the input runs on one thread, so it is left
+/// out of the measured time as with presort. The measurements are
+/// reported like benchmark_algorithm, followed by the table of
+/// `write_speedup_table` labelled `Speedup_<shape>`.
+pub fn benchmark_speedup(variants: &[(Algorithm, Vec<Algorithm>)], shape: Shape, options: &Options) {
+    let options = Options { presort: true, ..options.clone() };
+    let algorithms: Vec<Algorithm> = variants
+        .iter()
+        .flat_map(|&(scalar, ref parallel)| Some(scalar).into_iter().chain(parallel.iter().cloned()))
+        .collect();
+    let mut outputs: Vec<Vec<(u64, Measurement)>> = vec![Vec::new(); algorithms.len()];
+    for sample_size in sample_sizes() {
+        let input_set: Vec<Point2D> = generate(shape, sample_size);
+        for (&algorithm, output) in algorithms.iter().zip(outputs.iter_mut()) {
+            output.push((sample_size.val(), measure(algorithm, &input_set, &options)));
+        }
+    }
+    for (algorithm, output) in algorithms.iter().zip(outputs.iter()) {
+        report(&format!("{:?}_{:?}", algorithm, shape), output, &options);
+    }
+    let mut columns: Vec<String> = Vec::new();
+    let mut speedups: Vec<Vec<f64>> = Vec::new();
+    let mut index = 0;
+    for &(scalar, ref parallel) in variants {
+        let scalar_output = &outputs[index];
+        for (variant, output) in parallel.iter().zip(&outputs[index + 1..]) {
+            columns.push(format!("{:?}/{:?}", variant, scalar));
+            speedups.push(
+                scalar_output
+                    .iter()
+                    .zip(output)
+                    .map(|((_, scalar), (_, variant))| scalar.mean() / variant.mean())
+                    .collect(),
+            );
+        }
+        index += 1 + parallel.len();
+    }
+    let rows: Vec<(u64, Vec<f64>)> = sample_sizes()
+        .iter()
+        .enumerate()
+        .map(|(row, sample_size)| (sample_size.val(), speedups.iter().map(|column| column[row]).collect()))
+        .collect();
+    write_speedup_table(&mut io::stdout(), &format!("Speedup_{:?}", shape), &columns, &rows)
+        .expect("Could not print the results");
+}
+
+/// Writes a table with a row per size and a column per variant, each
+/// cell how many times faster the variant ran than its scalar algorithm
+pub fn write_speedup_table<W: Write>(out: &mut W, label: &str, columns: &[String], rows: &[(u64, Vec<f64>)]) -> io::Result<()> {
+    let widths: Vec<usize> = columns.iter().map(|column| column.len().max(6)).collect();
+    write!(out, "{:<10}", label)?;
+    for (column, &width) in columns.iter().zip(&widths) {
+        write!(out, " {:>width$}", column, width = width)?;
+    }
+    writeln!(out)?;
+    for &(size, ref speedups) in rows {
+        write!(out, "{:<10}", size)?;
+        for (speedup, &width) in speedups.iter().zip(&widths) {
+            write!(out, " {:>width$.2}", speedup, width = width)?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// Benchmarks containment queries against the hull of a shape
+///
+/// The queries are the points of the shape scaled by 1.5, so that some
+/// are outside, and there are as many as the shape has points, up to a
+/// million. Testing every query against every hull edge is reported as
+/// `Containment_Scan_<shape>`, a `hull::PointLocator` prepared from the
+/// hull and queried with `contains_all` as `Containment_Locator_<shape>`.
+/// The time of the locator includes preparing it.
+pub fn benchmark_containment(shape: Shape, options: &Options) {
+    let mut scan: Vec<(u64, Measurement)> = Vec::new();
+    let mut locator: Vec<(u64, Measurement)> = Vec::new();
+    let time = |query: &dyn Fn() -> usize| {
+        let mut samples: Vec<f64> = Vec::new();
+        for _ in 0..options.runs.max(1) {
+            let start = PreciseTime::now();
+            //the number of points inside keeps the queries from being optimized away
+            black_box(query());
+            samples.push(Time::new(start.to(PreciseTime::now())).milli_seconds());
+        }
+        Measurement::new(samples, options.reject_outliers)
+    };
+    for sample_size in sample_sizes().into_iter().take(8) {
+        if sample_size.val() < shape.num_of_vertices() {
+            continue;
+        }
+        let input_set: Vec<Point2D> = generate(shape, sample_size);
+        let hull = convex_hull_of(&input_set);
+        let queries: Vec<Point2D> = input_set.iter().map(|point| Point2D::new(1.5 * point.x, 1.5 * point.y)).collect();
+        scan.push((
             sample_size.val(),
-            execution_time(algorithm, &mut input_set, presort).milli_seconds(),
-        );
-        output.push(result);
+            time(&|| {
+                queries
+                    .iter()
+                    .filter(|point| (0..hull.len()).all(|i| Line::new(hull[i], hull[(i + 1) % hull.len()]).side(point) >= 0.0))
+                    .count()
+            }),
+        ));
+        locator.push((
+            sample_size.val(),
+            time(&|| PointLocator::new(&hull).contains_all(&queries).into_iter().filter(|&inside| inside).count()),
+        ));
     }
-    println!("{:?}_{:?} : {:?}", algorithm, shape, output);
+    report(&format!("Containment_Scan_{:?}", shape), &scan, options);
+    report(&format!("Containment_Locator_{:?}", shape), &locator, options);
 }
 
-/// Executes an algorithm for given inputset of point and returns the time
-fn execution_time(algorithm: Algorithm, input_set: &mut Vec<Point2D>, presort: bool) -> Time {
-    #[allow(unused_assignments)]
-    let mut start = PreciseTime::now();
-    if presort {
-        let mut sorted_input_set = sort_input(input_set);
-        start = PreciseTime::now();
-        execute(algorithm, &mut sorted_input_set);
+/// Times `io::hull_of_file` on a file with every chunk size
+///
+/// The sizes are reported as `OutOfCore_<file name>`, the number of
+/// hull vertices as `OutOfCore_<file name>_hull`.
+pub fn benchmark_out_of_core(path: &Path, chunk_sizes: &[usize], options: &Options) -> io::Result<()> {
+    let name = path.file_name().map_or(String::new(), |name| name.to_string_lossy().into_owned());
+    let mut output: Vec<(u64, Measurement)> = Vec::new();
+    let mut hull_size = 0;
+    for &chunk_size in chunk_sizes {
+        let mut samples: Vec<f64> = Vec::new();
+        for _ in 0..options.runs.max(1) {
+            let start = PreciseTime::now();
+            hull_size = hull_of_file(path, chunk_size)?.len();
+            samples.push(Time::new(start.to(PreciseTime::now())).milli_seconds());
+        }
+        output.push((chunk_size as u64, Measurement::new(samples, options.reject_outliers)));
+    }
+    report(&format!("OutOfCore_{}", name), &output, options);
+    println!("OutOfCore_{}_hull : {}", name, hull_size);
+    Ok(())
+}
+
+/// Times the configured number of runs of an algorithm on an input set
+///
+/// The hardware counters, when enabled, cover the whole run,
+/// the sorting of a presorted input set included. They only count
+/// the calling thread, so they are left out for ParallelQuickHull
+/// and DivideAndConquer with more than one thread.
+fn measure(algorithm: Algorithm, input_set: &[Point2D], options: &Options) -> Measurement {
+    let mut samples: Vec<f64> = Vec::new();
+    let mut frequencies: Vec<f64> = Vec::new();
+    let multi_threaded = match algorithm {
+        Algorithm::ParallelQuickHull => true,
+        Algorithm::DivideAndConquer(threads) => threads > 1,
+        _ => false,
+    };
+    let mut perf_counters = if options.perf_counters && !multi_threaded {
+        PerfCounters::open().ok()
     } else {
-        start = PreciseTime::now();
-        let mut sorted_input_set = sort_input(input_set);
-        execute(algorithm, &mut sorted_input_set);
+        None
+    };
+    let mut total = Counters::default();
+    let runs = options.runs.max(1);
+    for _ in 0..runs {
+        let mut run_input_set = input_set.to_vec();
+        if options.detect_throttling {
+            frequencies.extend(cpu_frequency());
+        }
+        if let Some(ref mut perf_counters) = perf_counters {
+            perf_counters.start().expect("Could not start the performance counters");
+        }
+        samples.push(execution_time(algorithm, &mut run_input_set, options).milli_seconds());
+        if let Some(ref mut perf_counters) = perf_counters {
+            let counters = perf_counters.stop().expect("Could not read the performance counters");
+            total.instructions += counters.instructions;
+            total.cache_misses += counters.cache_misses;
+            total.branch_misses += counters.branch_misses;
+        }
+    }
+    let mut measurement = Measurement::new(samples, options.reject_outliers);
+    if perf_counters.is_some() {
+        measurement.counters = Some(Counters {
+            instructions: total.instructions / runs as u64,
+            cache_misses: total.cache_misses / runs as u64,
+            branch_misses: total.branch_misses / runs as u64,
+        });
+    }
+    if options.detect_throttling {
+        frequencies.extend(cpu_frequency());
+        measurement.throttled = is_throttled(measurement.raw_samples(), &frequencies);
+        thread::sleep(cooldown(measurement.throttled, options));
+    }
+    measurement
+}
+
+/// Runs needed before a steady slowdown across them counts as throttling
+///
+/// With fewer runs a noisy machine too often times every run slower
+/// than the one before.
+pub const MIN_TREND_RUNS: usize = 5;
+
+/// Reads the current frequency of the first cpu in MHz
+///
+/// Only available on Linux machines exposing cpufreq in sysfs.
+pub fn cpu_frequency() -> Option<f64> {
+    fs::read_to_string("/sys/devices/system/cpu/cpu0/cpufreq/scaling_cur_freq")
+        .ok()
+        .and_then(|khz| parse_frequency(&khz))
+}
+
+/// The frequency in MHz of a cpufreq reading in kHz
+pub fn parse_frequency(khz: &str) -> Option<f64> {
+    khz.trim().parse::<f64>().ok().map(|khz| khz / 1_000.0)
+}
+
+/// A measurement looks throttled when the cpu frequency dropped more
+/// than 10% below its highest reading, or when there were at least
+/// MIN_TREND_RUNS runs, every run was slower than the one before and
+/// the last run took 10% longer than the first
+pub fn is_throttled(samples: &[f64], frequencies: &[f64]) -> bool {
+    let max_frequency = frequencies.iter().cloned().fold(0.0, f64::max);
+    let frequency_dropped = frequencies
+        .iter()
+        .any(|&frequency| frequency < 0.9 * max_frequency);
+    let slowing_down = samples.len() >= MIN_TREND_RUNS
+        && samples.windows(2).all(|pair| pair[1] > pair[0])
+        && samples[samples.len() - 1] > 1.1 * samples[0];
+    frequency_dropped || slowing_down
+}
+
+/// The pause after a measurement: the cooldown of the options after a
+/// throttled one, none otherwise
+pub fn cooldown(throttled: bool, options: &Options) -> ::std::time::Duration {
+    if throttled {
+        ::std::time::Duration::from_millis(options.cooldown_ms)
+    } else {
+        ::std::time::Duration::from_millis(0)
+    }
+}
+
+/// Prints the results of a benchmark mode with `write_report`
+fn report(label: &str, output: &[(u64, Measurement)], options: &Options) {
+    write_report(&mut io::stdout(), label, output, options).expect("Could not print the results");
+}
+
+/// Writes the mean runtime of every measurement under the given label,
+/// followed by the number of rejected samples when outliers are rejected
+///
+/// The name of the prefilter, if any, is appended to the label.
+/// Counted hardware events are written as
+/// `(size, instructions, cache misses, branch misses)`. The samples are
+/// dumped too when the options ask for it.
+pub fn write_report<W: Write>(out: &mut W, label: &str, output: &[(u64, Measurement)], options: &Options) -> io::Result<()> {
+    let label = match options.prefilter {
+        Some(prefilter) => format!("{}_{:?}", label, prefilter),
+        None => label.to_string(),
+    };
+    let label = label.as_str();
+    let means: Vec<(u64, f64)> = output
+        .iter()
+        .map(|&(size, ref measurement)| (size, measurement.mean()))
+        .collect();
+    writeln!(out, "{} : {:?}", label, means)?;
+    if options.reject_outliers {
+        let rejected: Vec<(u64, usize)> = output
+            .iter()
+            .map(|&(size, ref measurement)| (size, measurement.rejected()))
+            .collect();
+        writeln!(out, "{}_rejected : {:?}", label, rejected)?;
+    }
+    if options.detect_throttling {
+        let throttled: Vec<u64> = output
+            .iter()
+            .filter(|(_, measurement)| measurement.throttled())
+            .map(|&(size, _)| size)
+            .collect();
+        if !throttled.is_empty() {
+            writeln!(out, "{}_throttled : {:?}", label, throttled)?;
+        }
+    }
+    if options.perf_counters {
+        let counters: Vec<(u64, u64, u64, u64)> = output
+            .iter()
+            .filter_map(|&(size, ref measurement)| {
+                measurement.counters().map(|counters| {
+                    (size, counters.instructions, counters.cache_misses, counters.branch_misses)
+                })
+            })
+            .collect();
+        writeln!(out, "{}_counters : {:?}", label, counters)?;
+    }
+    if let Some(ref dir) = options.dump_samples {
+        dump_samples(dir, label, output);
+    }
+    Ok(())
+}
+
+/// Writes the raw samples of every measurement to `<dir>/<label>_<size>.txt`,
+/// one sample in milli seconds per line
+fn dump_samples(dir: &PathBuf, label: &str, output: &[(u64, Measurement)]) {
+    fs::create_dir_all(dir).expect("Could not create the samples directory");
+    for &(size, ref measurement) in output {
+        let path = dir.join(format!("{}_{}.txt", label, size));
+        let mut file = fs::File::create(&path).expect("Could not create the samples file");
+        for sample in measurement.raw_samples() {
+            writeln!(file, "{}", sample).expect("Could not write the samples file");
+        }
     }
+}
+
+/// Executes an algorithm for given inputset of point and returns the time
+///
+/// Only the Graham scans run on the input set sorted by `prepare`,
+/// every other algorithm on the input set itself, in its own order.
+/// The prefilter is always timed. With presort the sorting is not.
+fn execution_time(algorithm: Algorithm, input_set: &mut Vec<Point2D>, options: &Options) -> Time {
+    let start = PreciseTime::now();
+    if let Some(prefilter) = options.prefilter {
+        *input_set = prefilter.apply(input_set);
+    }
+    let filtered = PreciseTime::now();
+    let mut sorted_input_set;
+    let prepared: &mut [Point2D] = match algorithm {
+        Algorithm::Graham | Algorithm::GrahamAngleFree => {
+            sorted_input_set = prepare(algorithm, input_set);
+            &mut sorted_input_set
+        }
+        _ => input_set,
+    };
+    let sorted = PreciseTime::now();
+    execute(algorithm, prepared);
     let end = PreciseTime::now();
-    Time::new(start.to(end))
+    if options.presort {
+        Time::new(start.to(filtered) + sorted.to(end))
+    } else {
+        Time::new(start.to(end))
+    }
 }
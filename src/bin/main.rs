@@ -4,31 +4,331 @@
 // distribution of this software for license terms.
 
 extern crate rustalgo;
-use rustalgo::benchmark::benchmark_algorithm;
+use rustalgo::benchmark::*;
 use rustalgo::inputset::Shape::*;
+use rustalgo::inputset::Number::*;
 use rustalgo::convexhull::Algorithm::*;
+use rustalgo::convexhull::GuessSchedule;
+use rustalgo::plots::draw_benchmark_plot;
+use rustalgo::distribution::Dist;
+use rustalgo::inputset::random_walk;
+use rustalgo::prefilter::Prefilter;
+use rustalgo::hull::ConvexHull;
+use rustalgo::io::read_points;
+use rustalgo::perf::PerfCounters;
+
+fn usage() {
+    println!("You can use argument 'presort' to benchmark presorted input, \
+              'order' to benchmark shuffled, sorted and reverse sorted input \
+              'hullsize' to benchmark a fixed input size with growing hulls, \
+              'kinetic' to compare a kinetic hull of moving points with recomputing it \
+              and 'containment' to benchmark containment queries against a hull.");
+    println!("Use 'correctness' to count the wrong hull vertices of every algorithm against \
+              exact rational hulls (rational feature).");
+    println!("Use 'precision' to compare every algorithm on f32, f64 and i64 points.");
+    println!("Use 'speedup' to compare the multi-threaded algorithms with their scalar versions \
+              on the same input sets.");
+    println!("Use '--runs N' to time every input N times and \
+              '--reject-outliers' to drop outlying samples of those runs.");
+    println!("Use '--dump-samples DIR' to write every timing sample to files in DIR.");
+    println!("Use '--detect-throttling' to flag results taken while the cpu slowed down \
+              and '--cooldown MS' to also pause MS milli seconds after such a result.");
+    println!("Use '--prefilter grid' to drop interior points with a uniform grid before every run,");
+    println!("or '--prefilter akl-toussaint' to drop the points inside the quadrilateral of the extreme points.");
+    println!("Use '--perf-counters' to also count instructions, cache misses and branch \
+              mispredictions of every run (Linux, perf_counters feature).");
+    println!("Use '--approximate STRIPS' to also benchmark the approximate hull with STRIPS strips.");
+    println!("Use '--delaunay' to also benchmark the hull as the boundary of the Delaunay triangulation.");
+    println!("Use '--concave K' to compare the concave hull with K neighbours to the convex hulls instead.");
+    println!("Use 'hull-diff A B [--tolerance T]' to compare the hulls stored in two files.");
+    println!("Use 'out-of-core FILE [--chunk N]' to time the hull of a file read N points at a time.");
+}
+
+/// Compares two hull files and exits with 1 when they differ
+fn hull_diff(mut args: std::iter::Skip<std::env::Args>) {
+    let mut files: Vec<String> = Vec::new();
+    let mut tolerance = 1e-9;
+    while let Some(arg) = args.next() {
+        if arg == "--tolerance" {
+            match args.next().and_then(|tolerance| tolerance.parse().ok()) {
+                Some(value) => tolerance = value,
+                None => return usage(),
+            }
+        } else {
+            files.push(arg);
+        }
+    }
+    if files.len() != 2 {
+        return usage();
+    }
+    let mut hulls: Vec<ConvexHull> = Vec::new();
+    for file in &files {
+        match read_points(file) {
+            Ok(vertices) => hulls.push(ConvexHull::new(vertices)),
+            Err(error) => {
+                println!("Could not read {}: {}", file, error);
+                std::process::exit(2);
+            }
+        }
+    }
+    let diff = hulls[0].diff(&hulls[1], tolerance);
+    for vertex in &diff.only_in_first {
+        println!("only in {} : ({}, {})", files[0], vertex.x, vertex.y);
+    }
+    for vertex in &diff.only_in_second {
+        println!("only in {} : ({}, {})", files[1], vertex.x, vertex.y);
+    }
+    println!("symmetric difference area : {}", diff.symmetric_difference_area);
+    if !diff.vertices_match() {
+        std::process::exit(1);
+    }
+}
+
+/// Times the chunked hull of a file too large to read at once
+fn out_of_core(mut args: std::iter::Skip<std::env::Args>) {
+    let file = match args.next() {
+        Some(file) => file,
+        None => return usage(),
+    };
+    let mut chunk_sizes = vec![1_000, 10_000, 100_000, 1_000_000];
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), args.next().and_then(|size| size.parse().ok())) {
+            ("--chunk", Some(size)) => chunk_sizes = vec![size],
+            _ => return usage(),
+        }
+    }
+    println!("Out Of Core Benchmark Results");
+    if let Err(error) = benchmark_out_of_core(std::path::Path::new(&file), &chunk_sizes, &Options::new(false)) {
+        println!("Could not read {}: {}", file, error);
+        std::process::exit(2);
+    }
+}
 
 fn main() {
 
-    let args: Vec<String> = std::env::args().collect();
-    let mut presort = false;
-    if args.len() > 1 {
-        match args[1].as_str() {
+    let mut args = std::env::args().skip(1);
+    if std::env::args().nth(1).is_some_and(|arg| arg == "hull-diff") {
+        args.next();
+        return hull_diff(args);
+    }
+    if std::env::args().nth(1).is_some_and(|arg| arg == "out-of-core") {
+        args.next();
+        return out_of_core(args);
+    }
+    let mut options = Options::new(false);
+    let mut input_order = false;
+    let mut hull_size = false;
+    let mut kinetic = false;
+    let mut containment = false;
+    let mut correctness = false;
+    let mut precision = false;
+    let mut speedup = false;
+    let mut approximate = None;
+    let mut delaunay = false;
+    let mut concave = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
             "presort" => {
-                presort = true
+                options.presort = true
+            },
+            "order" => {
+                input_order = true
+            },
+            "hullsize" => {
+                hull_size = true
+            },
+            "kinetic" => {
+                kinetic = true
+            },
+            "containment" => {
+                containment = true
+            },
+            "correctness" => {
+                correctness = true
+            },
+            "precision" => {
+                precision = true
+            },
+            "speedup" => {
+                speedup = true
+            },
+            "--runs" => {
+                match args.next().and_then(|runs| runs.parse().ok()) {
+                    Some(runs) => options.runs = runs,
+                    None => usage(),
+                }
+            },
+            "--reject-outliers" => {
+                options.reject_outliers = true
+            },
+            "--detect-throttling" => {
+                options.detect_throttling = true
+            },
+            "--cooldown" => {
+                match args.next().and_then(|cooldown| cooldown.parse().ok()) {
+                    Some(cooldown) => {
+                        options.detect_throttling = true;
+                        options.cooldown_ms = cooldown
+                    },
+                    None => usage(),
+                }
+            },
+            "--prefilter" => {
+                match args.next().as_deref() {
+                    Some("grid") => options.prefilter = Some(Prefilter::Grid),
+                    Some("akl-toussaint") => options.prefilter = Some(Prefilter::AklToussaint),
+                    _ => usage(),
+                }
+            },
+            "--perf-counters" => {
+                if let Err(error) = PerfCounters::open() {
+                    println!("Could not open the performance counters: {}", error);
+                    return;
+                }
+                options.perf_counters = true
+            },
+            "--approximate" => {
+                match args.next().and_then(|strips| strips.parse().ok()) {
+                    Some(strips) => approximate = Some(strips),
+                    None => usage(),
+                }
+            },
+            "--delaunay" => {
+                delaunay = true
             },
-            _ => println!("You can use argument 'presort' to benchmark presorted input"),
+            "--concave" => {
+                match args.next().and_then(|k| k.parse().ok()) {
+                    Some(k) => concave = Some(k),
+                    None => usage(),
+                }
+            },
+            "--dump-samples" => {
+                match args.next() {
+                    Some(dir) => options.dump_samples = Some(dir.into()),
+                    None => usage(),
+                }
+            },
+            _ => usage(),
+        }
+    }
+
+    let mut algorithms = vec![Graham, GrahamAngleFree, Jarvis, Chan, ChanWith(GuessSchedule::Doubling), QuickHull, KirkpatrickSeidel, Incremental, RandomizedIncremental, Auto];
+    #[cfg(feature = "parallel")]
+    algorithms.push(ParallelQuickHull);
+    // one thread, two threads and so on up to all the cores, for the speedup
+    let cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    let mut threads = 1;
+    while threads < cores {
+        algorithms.push(DivideAndConquer(threads));
+        threads *= 2;
+    }
+    algorithms.push(DivideAndConquer(cores));
+    if let Some(strips) = approximate {
+        algorithms.push(Approximate(strips));
+    }
+    if delaunay {
+        algorithms.push(Delaunay);
+    }
+
+    if input_order {
+        println!("Input Order Benchmark Results");
+        for shape in [Triangle, Rectangle, Circle] {
+            for &algorithm in &algorithms {
+                benchmark_input_order(algorithm, shape, &options);
+            }
+        }
+        return;
+    }
+
+    if kinetic {
+        println!("Kinetic Benchmark Results");
+        for shape in [Triangle, Rectangle, Circle] {
+            benchmark_kinetic(&algorithms, shape, 100, &options);
+        }
+        return;
+    }
+
+    if containment {
+        println!("Containment Benchmark Results");
+        for shape in [Triangle, Rectangle, Circle, Polygon(1000)] {
+            benchmark_containment(shape, &options);
+        }
+        return;
+    }
+
+    if correctness {
+        println!("Correctness Benchmark Results");
+        #[cfg(feature = "rational")]
+        for shape in [Triangle, Rectangle, Circle, Polygon(1000)] {
+            benchmark_correctness(&algorithms, shape);
         }
+        #[cfg(not(feature = "rational"))]
+        println!("The correctness benchmark needs the rational feature.");
+        return;
+    }
+
+    if precision {
+        println!("Precision Benchmark Results");
+        for shape in [Triangle, Rectangle, Circle] {
+            benchmark_precision(&algorithms, shape, &options);
+        }
+        return;
+    }
+
+    if speedup {
+        println!("Speedup Benchmark Results");
+        let threads: Vec<_> = algorithms
+            .iter()
+            .cloned()
+            .filter(|algorithm| match *algorithm {
+                DivideAndConquer(threads) => threads > 1,
+                _ => false,
+            })
+            .collect();
+        let variants = vec![
+            (DivideAndConquer(1), threads),
+            #[cfg(feature = "parallel")]
+            (QuickHull, vec![ParallelQuickHull]),
+        ];
+        for shape in [Triangle, Rectangle, Circle] {
+            benchmark_speedup(&variants, shape, &options);
+        }
+        return;
+    }
+
+    if let Some(k) = concave {
+        println!("Concave Hull Benchmark Results");
+        for shape in [Triangle, Rectangle, Circle] {
+            benchmark_concave(k, &algorithms, shape, &options);
+        }
+        return;
+    }
+
+    if hull_size {
+        println!("Hull Size Benchmark Results");
+        let hull_sizes = vec![3, 4, 8, 16, 32, 64, 128, 256, 512, 1024];
+        for &algorithm in &algorithms {
+            let output = benchmark_hull_size(algorithm, HundredThousand, &hull_sizes, &options);
+            draw_benchmark_plot(&output, "Hull size (h)", &format!("{:?}_hull_size.svg", algorithm));
+        }
+        return;
     }
 
     println!("Benchmark Results");
-    benchmark_algorithm(Graham, Triangle, presort);
-    benchmark_algorithm(Jarvis, Triangle, presort);
-    benchmark_algorithm(Chan, Triangle, presort);
-    benchmark_algorithm(Graham, Rectangle, presort);
-    benchmark_algorithm(Jarvis, Rectangle, presort);
-    benchmark_algorithm(Chan, Rectangle, presort);
-    benchmark_algorithm(Graham, Circle, presort);
-    benchmark_algorithm(Jarvis, Circle, presort);
-    benchmark_algorithm(Chan, Circle, presort);
+    for shape in [Triangle, Rectangle, Circle] {
+        describe_shape(shape);
+        for &algorithm in &algorithms {
+            benchmark_algorithm_with(algorithm, shape, &options);
+        }
+    }
+    // multi-modal data: 90% gaussian blob and 10% uniform ring
+    let mixture = Dist::mixture(vec![(Dist::gaussian(0.5), 9.0), (Dist::ring(1.8, 2.0), 1.0)]);
+    for &algorithm in &algorithms {
+        benchmark_distribution(algorithm, "Mixture", &mixture, &options);
+    }
+    // correlated trajectory data
+    let walk = |sample_size| random_walk(sample_size, 0.01);
+    for &algorithm in &algorithms {
+        benchmark_generator(algorithm, "RandomWalk", walk, &options);
+    }
 }
@@ -0,0 +1,114 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Memoization of hulls for input sets that are hulled repeatedly
+//!
+//! Interactive applications often recompute the hull of a selection
+//! that did not change. A HullCache remembers the hulls it computed,
+//! keyed by a fingerprint of the input set, and hands them back
+//! without running the algorithm again.
+use convexhull::*;
+use points::Point2D;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hasher;
+
+///Hashes the coordinates of an input set
+///
+/// Two input sets have the same fingerprint when they hold the
+/// same points in the same order.
+pub fn fingerprint(input_set: &[Point2D]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for point in input_set {
+        hasher.write_u64(point.x.to_bits());
+        hasher.write_u64(point.y.to_bits());
+    }
+    hasher.finish()
+}
+
+///A cache of the hulls computed by one algorithm
+pub struct HullCache {
+    algorithm: Algorithm,
+    capacity: usize,
+    // the input set is kept to rule out fingerprint collisions
+    hulls: HashMap<u64, (Vec<Point2D>, Vec<Point2D>)>,
+    // fingerprints from the oldest to the newest entry
+    order: VecDeque<u64>,
+    hits: u64,
+    misses: u64,
+}
+
+impl HullCache {
+    ///A cache holding the hulls of at most `capacity` input sets.
+    /// The oldest hull is dropped when a new one does not fit.
+    pub fn new(algorithm: Algorithm, capacity: usize) -> HullCache {
+        HullCache {
+            algorithm,
+            capacity,
+            hulls: HashMap::new(),
+            order: VecDeque::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    ///Returns the hull of the input set, computing it only when
+    /// the same input set is not in the cache
+    ///
+    /// The input set is prepared for the algorithm with `prepare`, so
+    /// only the Graham scans sort it.
+    ///
+    /// # Panics
+    /// Panics when size of input is not atleast 3 for the Graham scans
+    pub fn hull(&mut self, input_set: &[Point2D]) -> &Vec<Point2D> {
+        let key = fingerprint(input_set);
+        let cached = self
+            .hulls
+            .get(&key)
+            .is_some_and(|(cached_input_set, _)| &cached_input_set[..] == input_set);
+        if cached {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+            let mut prepared = prepare(self.algorithm, &mut input_set.to_vec());
+            let hull = execute(self.algorithm, &mut prepared);
+            if self.hulls.insert(key, (input_set.to_vec(), hull)).is_none() {
+                self.order.push_back(key);
+            }
+            while self.order.len() > self.capacity.max(1) {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.hulls.remove(&oldest);
+                }
+            }
+        }
+        &self.hulls[&key].1
+    }
+
+    ///Number of hulls in the cache
+    pub fn len(&self) -> usize {
+        self.hulls.len()
+    }
+
+    ///True when no hull is cached
+    pub fn is_empty(&self) -> bool {
+        self.hulls.is_empty()
+    }
+
+    ///Number of calls to `hull` answered from the cache
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    ///Number of calls to `hull` that ran the algorithm
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    ///Drops all the cached hulls, the counts of hits and misses stay
+    pub fn clear(&mut self) {
+        self.hulls.clear();
+        self.order.clear();
+    }
+}
@@ -0,0 +1,492 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Rotating calipers
+//!
+//! Two parallel lines touching a convex polygon from opposite sides
+//! can be rotated around it in O(n) steps. The vertices they touch at
+//! the same time are the antipodal pairs, and measures like the
+//! diameter are found among them without looking at all vertex pairs.
+//!
+//! The functions take the vertices of a hull in either orientation,
+//! like the output of any of the algorithms in `convexhull`.
+use convexhull::convex_hull_of;
+use hull::ConvexHull;
+use points::*;
+
+/// The vertices counter clockwise
+fn counter_clockwise(hull: &[Point2D]) -> Vec<Point2D> {
+    ConvexHull::new(hull.to_vec()).into_vertices()
+}
+
+fn distance(a: &Point2D, b: &Point2D) -> f64 {
+    ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt()
+}
+
+/// For every edge (i, i + 1) the first vertex maximizing `key(i, j)`,
+/// found by advancing one index around the hull from `start`
+///
+/// The key has to rise and then fall around the hull for every edge,
+/// with its maximum moving forward from edge to edge, which holds for
+/// extents that turn with the edges.
+fn sweep<F: Fn(usize, usize) -> f64>(n: usize, start: usize, key: F) -> Vec<usize> {
+    let mut best = Vec::with_capacity(n);
+    let mut j = start;
+    for i in 0..n {
+        //at most once around the hull
+        let mut steps = 0;
+        while steps < n && key(i, (j + 1) % n) > key(i, j % n) {
+            j += 1;
+            steps += 1;
+        }
+        best.push(j % n);
+    }
+    best
+}
+
+/// For every edge (i, i + 1) the first vertex farthest from its line
+fn farthest_from_edges(hull: &[Point2D]) -> Vec<usize> {
+    let n = hull.len();
+    sweep(n, 1, |i, j| orietation(&hull[i], &hull[(i + 1) % n], &hull[j]))
+}
+
+///The antipodal pairs of a hull: the vertices that two parallel lines
+/// touch from opposite sides at once, found by antipodal_pairs
+///
+/// Every pair is returned once, as vertices of the hull in counter
+/// clockwise order, the first one earlier in it than the second.
+#[derive(Debug, Clone)]
+pub struct AntipodalPairs {
+    hull: Vec<Point2D>,
+    // for every vertex the first and the last vertex antipodal to it,
+    // counting on past the end of the hull
+    ranges: Vec<(usize, usize)>,
+    vertex: usize,
+    other: usize,
+}
+
+///Iterates over the antipodal pairs of a hull, the pairs of vertices
+/// that rotating calipers touch at the same time
+///
+/// A vertex is antipodal to the vertices from the farthest one from the
+/// edge before it to the farthest one from the edge after it, which move
+/// forward around the hull, so there are O(n) pairs, found in O(n) time.
+/// Measures like the diameter are found among them. A hull of one
+/// vertex has no pairs.
+pub fn antipodal_pairs(hull: &[Point2D]) -> AntipodalPairs {
+    let hull = if hull.is_empty() { Vec::new() } else { counter_clockwise(hull) };
+    let n = hull.len();
+    let ranges: Vec<(usize, usize)> = if n < 3 {
+        (0..n).map(|vertex| (vertex + 1, vertex + 1)).collect()
+    } else {
+        let farthest = farthest_from_edges(&hull);
+        let height = |edge: usize, vertex: usize| orietation(&hull[edge], &hull[(edge + 1) % n], &hull[vertex % n]);
+        (0..n)
+            .map(|vertex| {
+                let (before, after) = ((vertex + n - 1) % n, vertex);
+                let first = if farthest[before] <= vertex { farthest[before] + n } else { farthest[before] };
+                let mut last = if farthest[after] < first { farthest[after] + n } else { farthest[after] };
+                //an edge parallel to the one after has both of its ends farthest
+                if height(after, last + 1) == height(after, last) {
+                    last += 1;
+                }
+                (first, last)
+            })
+            .collect()
+    };
+    let other = ranges.first().map_or(0, |range| range.0);
+    AntipodalPairs { hull, ranges, vertex: 0, other }
+}
+
+impl Iterator for AntipodalPairs {
+    type Item = (Point2D, Point2D);
+
+    fn next(&mut self) -> Option<(Point2D, Point2D)> {
+        let n = self.hull.len();
+        while self.vertex < n {
+            let (vertex, other) = (self.vertex, self.other);
+            if other < self.ranges[vertex].1 {
+                self.other += 1;
+            } else {
+                self.vertex += 1;
+                self.other = self.ranges.get(self.vertex).map_or(0, |range| range.0);
+            }
+            //the other vertex of the pair returns it too
+            if vertex < other % n {
+                return Some((self.hull[vertex], self.hull[other % n]));
+            }
+        }
+        None
+    }
+}
+
+///The two hull vertices farthest apart and their distance
+///
+/// They are one of the antipodal_pairs, so this takes O(n) time.
+///
+/// # Panics
+/// Panics when the hull is empty
+pub fn hull_diameter(hull: &[Point2D]) -> (Point2D, Point2D, f64) {
+    assert!(!hull.is_empty(), "the hull has no vertices");
+    antipodal_pairs(hull).fold((hull[0], hull[0], 0.0), |best, (a, b)| {
+        let length = distance(&a, &b);
+        if length > best.2 {
+            (a, b, length)
+        } else {
+            best
+        }
+    })
+}
+
+///The narrowest slab between two parallel lines holding a hull,
+/// found by hull_width
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Slab {
+    /// the distance between the lines
+    pub width: f64,
+    /// the hull edge on one of the lines
+    pub edge: (Point2D, Point2D),
+    /// the hull vertex on the other line
+    pub vertex: Point2D,
+}
+
+///The minimum width of a hull, which is the minimum width of the
+/// points it is the hull of
+///
+/// The narrowest slab has a hull edge on one of its sides, so every
+/// edge is paired with the vertex farthest from its line in O(n) time.
+/// Hulls with fewer than three vertices have a width of 0.
+///
+/// # Panics
+/// Panics when the hull is empty
+pub fn hull_width(hull: &[Point2D]) -> Slab {
+    assert!(!hull.is_empty(), "the hull has no vertices");
+    let hull = counter_clockwise(hull);
+    let n = hull.len();
+    if n < 3 {
+        return Slab {
+            width: 0.0,
+            edge: (hull[0], hull[n - 1]),
+            vertex: hull[0],
+        };
+    }
+    let farthest = farthest_from_edges(&hull);
+    let mut narrowest: Option<Slab> = None;
+    for i in 0..n {
+        let edge = (hull[i], hull[(i + 1) % n]);
+        let vertex = hull[farthest[i]];
+        let width = orietation(&edge.0, &edge.1, &vertex) / distance(&edge.0, &edge.1);
+        if narrowest.is_none_or(|slab| width < slab.width) {
+            narrowest = Some(Slab { width, edge, vertex });
+        }
+    }
+    narrowest.unwrap()
+}
+
+///The triangle of greatest area with its corners on hull vertices,
+/// and its area
+///
+/// For every first corner the other two go around the hull like a pair
+/// of calipers: the third one only moves forward while the second one
+/// does, since the vertex farthest from a line turning with it moves
+/// forward as well. This takes O(n²) time. Hulls with fewer than three
+/// vertices have a triangle of area 0.
+///
+/// # Panics
+/// Panics when the hull is empty
+pub fn largest_inscribed_triangle(hull: &[Point2D]) -> ([Point2D; 3], f64) {
+    assert!(!hull.is_empty(), "the hull has no vertices");
+    let hull = counter_clockwise(hull);
+    let n = hull.len();
+    if n < 3 {
+        return ([hull[0], hull[n - 1], hull[n - 1]], 0.0);
+    }
+    let area = |i: usize, j: usize, k: usize| orietation(&hull[i % n], &hull[j % n], &hull[k % n]) / 2.0;
+    let mut best = ([hull[0], hull[1], hull[2]], area(0, 1, 2));
+    for i in 0..n {
+        let mut k = i + 2;
+        for j in i + 1..i + n - 1 {
+            k = k.max(j + 1);
+            while k + 1 < i + n && area(i, j, k + 1) >= area(i, j, k) {
+                k += 1;
+            }
+            if area(i, j, k) > best.1 {
+                best = ([hull[i], hull[j % n], hull[k % n]], area(i, j, k));
+            }
+        }
+    }
+    best
+}
+
+///A rectangle holding a hull, turned to lie along one of its edges
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BoundingRect {
+    /// the corners in counter clockwise order
+    pub corners: [Point2D; 4],
+    /// the area of the rectangle
+    pub area: f64,
+    /// the angle of the side from the first to the second corner to
+    /// the x axis, in radians
+    pub angle: f64,
+}
+
+///The rectangle of least area holding the points
+///
+/// The best rectangle has a side on a hull edge (Freeman and Shapira).
+/// The hull is found first, and three calipers follow its edges to the
+/// vertices farthest along them, back from them and away from them,
+/// so every edge's rectangle takes O(1) and all of them O(n).
+///
+/// # Panics
+/// Panics when there are no points, or a coordinate is NaN
+pub fn min_area_rect(points: &[Point2D]) -> BoundingRect {
+    least_rect(points, |rect| rect.area)
+}
+
+///The rectangle of least perimeter holding the points
+///
+/// It also has a side on a hull edge, but is often turned differently
+/// from the one of least area. See min_area_rect.
+///
+/// # Panics
+/// Panics when there are no points, or a coordinate is NaN
+pub fn min_perimeter_rect(points: &[Point2D]) -> BoundingRect {
+    least_rect(points, BoundingRect::perimeter)
+}
+
+/// The rectangle along a hull edge with the least measure
+fn least_rect<F: Fn(&BoundingRect) -> f64>(points: &[Point2D], measure: F) -> BoundingRect {
+    edge_rects(&convex_hull_of(points))
+        .into_iter()
+        .fold(None, |best: Option<BoundingRect>, rect| match best {
+            Some(best) if measure(&best) <= measure(&rect) => Some(best),
+            _ => Some(rect),
+        })
+        .expect("there are no points")
+}
+
+impl BoundingRect {
+    ///The length of the boundary
+    pub fn perimeter(&self) -> f64 {
+        2.0 * (distance(&self.corners[0], &self.corners[1]) + distance(&self.corners[1], &self.corners[2]))
+    }
+}
+
+/// The rectangle along every edge of a counter clockwise hull
+fn edge_rects(hull: &[Point2D]) -> Vec<BoundingRect> {
+    let n = hull.len();
+    if n == 1 {
+        return vec![BoundingRect {
+            corners: [hull[0]; 4],
+            area: 0.0,
+            angle: 0.0,
+        }];
+    }
+    let direction = |i: usize| {
+        let (from, to) = (hull[i], hull[(i + 1) % n]);
+        let length = distance(&from, &to);
+        Point2D::new((to.x - from.x) / length, (to.y - from.y) / length)
+    };
+    let along = |i: usize, j: usize| {
+        let u = direction(i);
+        u.x * hull[j].x + u.y * hull[j].y
+    };
+    let farthest = farthest_from_edges(hull);
+    let ahead = sweep(n, 1, along);
+    //from the farthest vertex of the first edge the extent along it falls
+    let behind = sweep(n, farthest[0], |i, j| -along(i, j));
+    (0..n)
+        .map(|i| {
+            let u = direction(i);
+            let v = Point2D::new(-u.y, u.x);
+            let (low, high) = (along(i, behind[i]), along(i, ahead[i]));
+            let base = v.x * hull[i].x + v.y * hull[i].y;
+            let top = v.x * hull[farthest[i]].x + v.y * hull[farthest[i]].y;
+            let corner = |a: f64, b: f64| Point2D::new(a * u.x + b * v.x, a * u.y + b * v.y);
+            BoundingRect {
+                corners: [corner(low, base), corner(high, base), corner(high, top), corner(low, top)],
+                area: (high - low) * (top - base),
+                angle: u.y.atan2(u.x),
+            }
+        })
+        .collect()
+}
+
+///The farthest-point Voronoi diagram of the hull vertices, found by
+/// farthest_point_voronoi
+///
+/// The region of a site is where it is the farthest of them. The
+/// regions are unbounded, and the diagram is a tree: its vertices are
+/// the centres of the circles through three sites holding all of them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FarthestVoronoi {
+    /// the hull vertices counter clockwise, which are the sites
+    pub sites: Vec<Point2D>,
+    /// the vertices of the diagram
+    pub vertices: Vec<Point2D>,
+    /// for every vertex the three sites it is equally far from, counter
+    /// clockwise, a triangle of the farthest-point Delaunay triangulation
+    pub triangles: Vec<[usize; 3]>,
+    /// the edges between the regions
+    pub edges: Vec<FarthestEdge>,
+}
+
+///An edge of the farthest-point Voronoi diagram, on the bisector of
+/// two sites
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FarthestEdge {
+    /// the sites of the regions on either side
+    pub sites: (usize, usize),
+    /// the vertex the edge starts at
+    pub from: usize,
+    /// the vertex it ends at, None for a ray off to infinity
+    pub to: Option<usize>,
+    /// the unit direction of the edge from its start
+    pub direction: Point2D,
+}
+
+/// The centre of the circle through three points not on a line
+fn circumcenter(a: &Point2D, b: &Point2D, c: &Point2D) -> Point2D {
+    let (bx, by) = (b.x - a.x, b.y - a.y);
+    let (cx, cy) = (c.x - a.x, c.y - a.y);
+    let d = 2.0 * (bx * cy - by * cx);
+    let (b2, c2) = (bx * bx + by * by, cx * cx + cy * cy);
+    Point2D::new(a.x + (cy * b2 - by * c2) / d, a.y + (bx * c2 - cx * b2) / d)
+}
+
+fn unit(from: &Point2D, to: &Point2D) -> Point2D {
+    let length = distance(from, to);
+    Point2D::new((to.x - from.x) / length, (to.y - from.y) / length)
+}
+
+///The farthest-point Voronoi diagram of the vertices of the hull of
+/// the points
+///
+/// Of three neighbouring vertices on the hull, the ones with the
+/// largest circle through them (the greater angle at the middle one
+/// breaking ties) have the whole hull in that circle (Skyum). The
+/// middle one is left out, a vertex of the diagram is at the centre of
+/// the circle, and the rest are found the same way, each in O(h) for
+/// all of them in O(h²) for h hull vertices. Every pair of sites two of
+/// the triangles share is an edge between their vertices, and every
+/// hull edge is a ray towards the inside of the hull.
+///
+/// Hulls of one or two vertices have no vertices and no edges, the
+/// bisector splits the plane between two.
+///
+/// # Panics
+/// Panics when a coordinate is NaN
+pub fn farthest_point_voronoi(points: &[Point2D]) -> FarthestVoronoi {
+    let sites = convex_hull_of(points);
+    let n = sites.len();
+    let mut voronoi = FarthestVoronoi { sites, vertices: Vec::new(), triangles: Vec::new(), edges: Vec::new() };
+    if n < 3 {
+        return voronoi;
+    }
+    let sites = &voronoi.sites;
+    let mut previous: Vec<usize> = (0..n).map(|i| (i + n - 1) % n).collect();
+    let mut next: Vec<usize> = (0..n).map(|i| (i + 1) % n).collect();
+    //the radius and the angle at the middle vertex, of a vertex and its neighbours
+    let circle = |before: usize, vertex: usize, after: usize| {
+        let (to_before, to_after) = (unit(&sites[vertex], &sites[before]), unit(&sites[vertex], &sites[after]));
+        let angle = (to_before.x * to_after.y - to_before.y * to_after.x)
+            .abs()
+            .atan2(to_before.x * to_after.x + to_before.y * to_after.y);
+        (distance(&sites[before], &sites[after]) / (2.0 * angle.sin()), angle)
+    };
+    let mut left = 0;
+    for remaining in (3..=n).rev() {
+        let mut removed = left;
+        if remaining > 3 {
+            let mut best = (0.0, 0.0);
+            let mut vertex = left;
+            for _ in 0..remaining {
+                let key = circle(previous[vertex], vertex, next[vertex]);
+                if key > best {
+                    best = key;
+                    removed = vertex;
+                }
+                vertex = next[vertex];
+            }
+        }
+        let (before, after) = (previous[removed], next[removed]);
+        voronoi.triangles.push([before, removed, after]);
+        voronoi.vertices.push(circumcenter(&sites[before], &sites[removed], &sites[after]));
+        next[before] = after;
+        previous[after] = before;
+        left = after;
+    }
+    //pairs of sites with the triangles they are a side of
+    let mut sides: Vec<((usize, usize), usize)> = Vec::new();
+    for (triangle, corners) in voronoi.triangles.iter().enumerate() {
+        for k in 0..3 {
+            let (a, b) = (corners[k], corners[(k + 1) % 3]);
+            sides.push(((a.min(b), a.max(b)), triangle));
+        }
+    }
+    sides.sort_by_key(|side| side.0);
+    let mut k = 0;
+    while k < sides.len() {
+        let ((a, b), from) = sides[k];
+        let edge = if k + 1 < sides.len() && sides[k + 1].0 == (a, b) {
+            let to = sides[k + 1].1;
+            k += 2;
+            //across the sites, which also holds for vertices in one place
+            let (start, end) = (voronoi.vertices[from], voronoi.vertices[to]);
+            let across = unit(&sites[a], &sites[b]);
+            let mut direction = Point2D::new(-across.y, across.x);
+            if direction.x * (end.x - start.x) + direction.y * (end.y - start.y) < 0.0 {
+                direction = Point2D::new(across.y, -across.x);
+            }
+            FarthestEdge { sites: (a, b), from, to: Some(to), direction }
+        } else {
+            k += 1;
+            //a hull edge, towards the inside of the counter clockwise hull
+            let (start, end) = if (a + 1) % n == b { (a, b) } else { (b, a) };
+            let along = unit(&sites[start], &sites[end]);
+            FarthestEdge { sites: (a, b), from, to: None, direction: Point2D::new(-along.y, along.x) }
+        };
+        voronoi.edges.push(edge);
+    }
+    voronoi
+}
+
+impl FarthestVoronoi {
+    ///The smallest circle holding the sites, as its center and radius
+    ///
+    /// Its centre is on the diagram, where the distance to the farthest
+    /// sites is least: at a vertex, or on an edge halfway between its
+    /// two sites, so this takes O(h).
+    ///
+    /// # Panics
+    /// Panics when there are no sites
+    pub fn min_enclosing_circle(&self) -> (Point2D, f64) {
+        assert!(!self.sites.is_empty(), "there are no sites");
+        let halfway = |a: usize, b: usize| {
+            let (a, b) = (self.sites[a], self.sites[b]);
+            (Point2D::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0), distance(&a, &b) / 2.0)
+        };
+        if self.sites.len() < 3 {
+            return halfway(0, self.sites.len() - 1);
+        }
+        let mut best = (self.vertices[0], f64::INFINITY);
+        for (vertex, triangle) in self.vertices.iter().zip(&self.triangles) {
+            let radius = distance(vertex, &self.sites[triangle[0]]);
+            if radius < best.1 {
+                best = (*vertex, radius);
+            }
+        }
+        for edge in &self.edges {
+            let (middle, radius) = halfway(edge.sites.0, edge.sites.1);
+            let start = self.vertices[edge.from];
+            let along = (middle.x - start.x) * edge.direction.x + (middle.y - start.y) * edge.direction.y;
+            let length = edge.to.map_or(f64::INFINITY, |to| distance(&start, &self.vertices[to]));
+            if along >= 0.0 && along <= length && radius < best.1 {
+                best = (middle, radius);
+            }
+        }
+        best
+    }
+}
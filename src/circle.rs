@@ -0,0 +1,163 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Smallest enclosing circles
+//!
+//! The smallest circle holding a point set touches two or three of its
+//! points, all of them hull vertices, so it is also the smallest circle
+//! holding the hull. It is found by adding the points one at a time and
+//! only starting over, with the new point on the circle, when a point
+//! is outside the circle so far.
+//!
+//! The largest circle inside the hull without a point in it is the
+//! other way around, centred on the boundary of the nearest-point
+//! Voronoi cells instead.
+use convexhull::convex_hull_of;
+use halfplane::half_plane_intersection;
+use hull::Line;
+#[cfg(feature = "generators")]
+use inputset::InputOrder;
+use points::*;
+
+fn distance(a: &Point2D, b: &Point2D) -> f64 {
+    ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt()
+}
+
+/// Whether the point is inside the circle, up to rounding
+fn inside(circle: &(Point2D, f64), point: &Point2D) -> bool {
+    distance(&circle.0, point) <= circle.1 * (1.0 + 1e-10)
+}
+
+/// The circle with the segment from a to b as diameter
+fn diameter_circle(a: &Point2D, b: &Point2D) -> (Point2D, f64) {
+    let center = Point2D::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0);
+    (center, distance(&center, a).max(distance(&center, b)))
+}
+
+/// The circle through three points, or the one over the two farthest
+/// apart when they are on a line
+fn circumcircle(a: &Point2D, b: &Point2D, c: &Point2D) -> (Point2D, f64) {
+    let (bx, by) = (b.x - a.x, b.y - a.y);
+    let (cx, cy) = (c.x - a.x, c.y - a.y);
+    let d = 2.0 * (bx * cy - by * cx);
+    if d == 0.0 {
+        let pairs = [diameter_circle(a, b), diameter_circle(a, c), diameter_circle(b, c)];
+        return pairs.iter().fold(pairs[0], |widest, &pair| if pair.1 > widest.1 { pair } else { widest });
+    }
+    let (b2, c2) = (bx * bx + by * by, cx * cx + cy * cy);
+    let center = Point2D::new(a.x + (cy * b2 - by * c2) / d, a.y + (bx * c2 - cx * b2) / d);
+    (center, distance(&center, a).max(distance(&center, b)).max(distance(&center, c)))
+}
+
+///The smallest circle holding the points, as its center and radius,
+/// adding the points in the order they are in
+///
+/// Welzl's algorithm, without recursion: a point outside the circle so
+/// far is on the new circle, which is found the same way among the
+/// points before it. In a random order this takes O(n) expected time,
+/// but an order like points sorted around a circle takes O(n³). See
+/// min_enclosing_circle.
+///
+/// E. Welzl, "Smallest enclosing disks (balls and ellipsoids)", New
+/// Results and New Trends in Computer Science, 1991
+///
+/// # Panics
+/// Panics when there are no points
+pub fn min_enclosing_circle_in_order(points: &[Point2D]) -> (Point2D, f64) {
+    assert!(!points.is_empty(), "there are no points");
+    let mut circle = (points[0], 0.0);
+    for i in 1..points.len() {
+        if inside(&circle, &points[i]) {
+            continue;
+        }
+        circle = (points[i], 0.0);
+        for j in 0..i {
+            if inside(&circle, &points[j]) {
+                continue;
+            }
+            circle = diameter_circle(&points[i], &points[j]);
+            for k in 0..j {
+                if !inside(&circle, &points[k]) {
+                    circle = circumcircle(&points[i], &points[j], &points[k]);
+                }
+            }
+        }
+    }
+    circle
+}
+
+///The smallest circle holding the points, as its center and radius
+///
+/// The points are shuffled and passed to min_enclosing_circle_in_order,
+/// which takes O(n) expected time whatever the order of the input set.
+///
+/// # Panics
+/// Panics when there are no points
+#[cfg(feature = "generators")]
+pub fn min_enclosing_circle(points: &[Point2D]) -> (Point2D, f64) {
+    let mut shuffled = points.to_vec();
+    InputOrder::Shuffled.arrange(&mut shuffled);
+    min_enclosing_circle_in_order(&shuffled)
+}
+
+///Same as min_enclosing_circle, but only over the hull vertices
+///
+/// Finding the hull first takes O(n log n), but is faster when most of
+/// the points are inside, since each of the few vertices left is then
+/// tested against the circle instead of every point.
+///
+/// # Panics
+/// Panics when there are no points, or a coordinate is NaN
+#[cfg(feature = "generators")]
+pub fn min_enclosing_circle_of_hull(points: &[Point2D]) -> (Point2D, f64) {
+    min_enclosing_circle(&convex_hull_of(points))
+}
+
+///The largest circle centred in the hull of the points with none of
+/// them inside it, as its center and radius
+///
+/// The center is a vertex of the Voronoi cell of one of the points,
+/// the part of the hull closer to it than to any other point. Every
+/// cell is the intersection of the half-planes of the hull edges and
+/// of the bisectors with the other points, found by
+/// half_plane_intersection, which takes O(n² log n) for all of them.
+/// Points on a line have the center halfway across the widest gap
+/// between them.
+///
+/// # Panics
+/// Panics when there are no points, or a coordinate is NaN
+pub fn max_inscribed_circle(points: &[Point2D]) -> (Point2D, f64) {
+    let hull = convex_hull_of(points);
+    assert!(!hull.is_empty(), "there are no points");
+    let mut sites = points.to_vec();
+    sites.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap()));
+    sites.dedup();
+    if hull.len() < 3 {
+        //sorted along the line, the first of the widest gaps
+        let gap = (1..sites.len()).fold((sites[0], 0.0), |widest, i| {
+            let radius = distance(&sites[i - 1], &sites[i]) / 2.0;
+            let center = Point2D::new((sites[i - 1].x + sites[i].x) / 2.0, (sites[i - 1].y + sites[i].y) / 2.0);
+            if radius > widest.1 { (center, radius) } else { widest }
+        });
+        return gap;
+    }
+    let edges: Vec<Line> = (0..hull.len()).map(|i| Line::new(hull[i], hull[(i + 1) % hull.len()])).collect();
+    let mut best = (sites[0], 0.0);
+    for site in &sites {
+        let mut planes = edges.clone();
+        //the points closer to the site than to the other one are left
+        planes.extend(sites.iter().filter(|other| *other != site).map(|other| {
+            let middle = Point2D::new((site.x + other.x) / 2.0, (site.y + other.y) / 2.0);
+            Line::new(middle, Point2D::new(middle.x + site.y - other.y, middle.y + other.x - site.x))
+        }));
+        for vertex in half_plane_intersection(&planes) {
+            let radius = distance(&vertex, site);
+            if radius > best.1 {
+                best = (vertex, radius);
+            }
+        }
+    }
+    best
+}
@@ -0,0 +1,134 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Concave hulls
+//!
+//! The footprint of a point cloud, like the buildings of a town, is
+//! often far from convex. A concave hull is a simple polygon through
+//! some of the points that still contains all of them, but follows
+//! the points into the dents that the convex hull spans over.
+use convexhull::convex_hull_of;
+use kdtree::KdTree;
+use points::*;
+use std::f64::consts::PI;
+
+/// Finds a concave hull with the k-nearest neighbours approach
+///
+/// The boundary is wrapped like in gift wrapping, but from every vertex
+/// only the k nearest of the points not yet on it are candidates. The
+/// candidate with the largest right hand turn whose edge doesn't cross
+/// the boundary so far becomes the next vertex. When no candidate
+/// fits, or the closed polygon leaves points outside, the wrap starts
+/// over with k increased by one. Small values of k follow the points
+/// closely, large ones approach the convex hull, which is returned
+/// when k reaches the number of points.
+///
+/// The hull is in counter clockwise order, starting at the point with
+/// the least y-coordinate (the leftmost one on a tie). Values of k
+/// below 3 count as 3.
+///
+/// A. Moreira and M. Y. Santos, "Concave hull: A k-nearest neighbours
+/// approach for the computation of the region occupied by a set of
+/// points", GRAPP 2007
+///
+/// # Panics
+/// Panics when a coordinate is NaN
+pub fn concave_hull(input_set: &[Point2D], k: usize) -> Vec<Point2D> {
+    let mut points = input_set.to_vec();
+    points.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap()));
+    points.dedup();
+    if points.len() < 4 {
+        return convex_hull_of(&points);
+    }
+    let tree = KdTree::build(&points);
+    for k in k.max(3)..points.len() {
+        if let Some(hull) = wrap(&points, &tree, k) {
+            return hull;
+        }
+    }
+    convex_hull_of(&points)
+}
+
+/// One attempt of concave_hull with k neighbours
+fn wrap(points: &[Point2D], tree: &KdTree, k: usize) -> Option<Vec<Point2D>> {
+    let first = (0..points.len())
+        .fold(0, |first, i| if points[i].pick_left(&points[first]) == &points[i] { i } else { first });
+    let mut used = vec![false; points.len()];
+    used[first] = true;
+    let mut hull: Vec<usize> = vec![first];
+    //the wrap starts as if it came from the left of the first point
+    let mut back = Point2D::new(-1.0, 0.0);
+    loop {
+        let current = points[hull[hull.len() - 1]];
+        //after two edges the first point may close the hull
+        let closing = hull.len() >= 3;
+        let mut candidates = tree.k_nearest_where(&current, k, |i| !used[i] || (closing && i == first));
+        let turn = |i: &usize| {
+            let to = Point2D::new(points[*i].x - current.x, points[*i].y - current.y);
+            let angle = (back.x * to.y - back.y * to.x).atan2(back.x * to.x + back.y * to.y);
+            if angle <= 0.0 {
+                angle + 2.0 * PI
+            } else {
+                angle
+            }
+        };
+        //the largest right hand turn is the least counter clockwise
+        //angle from the edge back; nearer points first on a tie
+        candidates.sort_by(|a, b| turn(a).partial_cmp(&turn(b)).unwrap());
+        let next = candidates.into_iter().find(|&candidate| {
+            //the edges touching the new one at its ends don't count
+            let skip_first = candidate == first;
+            (0..hull.len() - 1)
+                .filter(|&edge| !(skip_first && edge == 0) && edge != hull.len() - 2)
+                .all(|edge| !intersect(&points[hull[edge]], &points[hull[edge + 1]], &current, &points[candidate]))
+        })?;
+        if next == first {
+            break;
+        }
+        used[next] = true;
+        back = Point2D::new(current.x - points[next].x, current.y - points[next].y);
+        hull.push(next);
+    }
+    let polygon: Vec<Point2D> = hull.iter().map(|&i| points[i]).collect();
+    if points.iter().all(|point| contains(&polygon, point)) {
+        Some(polygon)
+    } else {
+        None
+    }
+}
+
+/// Whether the segments from a to b and from c to d have a point in common
+fn intersect(a: &Point2D, b: &Point2D, c: &Point2D, d: &Point2D) -> bool {
+    let (d1, d2) = (orietation(c, d, a), orietation(c, d, b));
+    let (d3, d4) = (orietation(a, b, c), orietation(a, b, d));
+    if ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0)) && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0)) {
+        return true;
+    }
+    (d1 == 0.0 && on_segment(c, d, a))
+        || (d2 == 0.0 && on_segment(c, d, b))
+        || (d3 == 0.0 && on_segment(a, b, c))
+        || (d4 == 0.0 && on_segment(a, b, d))
+}
+
+/// Whether a point on the line through `from` and `to` is between them
+fn on_segment(from: &Point2D, to: &Point2D, point: &Point2D) -> bool {
+    point.x >= from.x.min(to.x) && point.x <= from.x.max(to.x) && point.y >= from.y.min(to.y) && point.y <= from.y.max(to.y)
+}
+
+/// Whether the point is inside the simple polygon or on its boundary
+fn contains(polygon: &[Point2D], point: &Point2D) -> bool {
+    let mut inside = false;
+    for i in 0..polygon.len() {
+        let (a, b) = (&polygon[i], &polygon[(i + 1) % polygon.len()]);
+        if orietation(a, b, point) == 0.0 && on_segment(a, b, point) {
+            return true;
+        }
+        //crossings of a ray to the right
+        if (a.y > point.y) != (b.y > point.y) && point.x < a.x + (point.y - a.y) * (b.x - a.x) / (b.y - a.y) {
+            inside = !inside;
+        }
+    }
+    inside
+}
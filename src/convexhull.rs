@@ -5,14 +5,45 @@
 
 //! Find the convex hull using various methods
 use inputset::*;
-use points::Point2D;
+use points::{orietation, CoordNum, HasXY, Point2D};
+use prefilter::{inside, prune_akl_toussaint, quadrilateral};
+use concave::concave_hull;
+use delaunay::delaunay_hull;
+use geometry::{canonicalize, Orientation};
+use hull::PointLocator;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::thread;
+#[cfg(feature = "allocator_api")]
+use std::alloc::Allocator;
 
 ///Types of algorithms handled in this programm
 #[derive(Debug, Copy, Clone)]
 pub enum Algorithm {
     Graham,
+    /// Graham-Scan after sort_input_angle_free, see prepare
+    GrahamAngleFree,
     Jarvis,
     Chan,
+    /// chans_algorithm_with this guess schedule
+    ChanWith(GuessSchedule),
+    QuickHull,
+    KirkpatrickSeidel,
+    Incremental,
+    #[cfg(feature = "generators")]
+    RandomizedIncremental,
+    /// approximate_hull with this many strips
+    Approximate(usize),
+    #[cfg(feature = "parallel")]
+    ParallelQuickHull,
+    /// divide_and_conquer_hull on this many threads
+    DivideAndConquer(usize),
+    /// convex_hull_auto
+    Auto,
+    /// delaunay_hull, the boundary of the Delaunay triangulation
+    Delaunay,
+    /// concave_hull with this many neighbours, which isn't convex
+    Concave(usize),
 }
 
 /// Solves the convexhull problem using Graham-Scan
@@ -23,151 +54,1234 @@ pub enum Algorithm {
 /// pops from the stack each point that is not a vertex of
 /// CH(Q). When the algorithm terminates, stack S cpntains
 /// exactly the vertices of CH(Q), in counter clockwise
-/// order of their appearance on the boundary.
+/// order of their appearance on the boundary. They are returned in
+/// the canonical form of `geometry::canonicalize`, from the leftmost
+/// vertex.
 ///
 /// The psedocode for this algorithm is referred from
 /// Introduction to Algorithms (Third Edition)
 /// Authors: Thomas H. Cormen, Charles E. Leiserson, Ronald L. Rivest and Clifford Stein
-pub fn graham_scan(input_set: &mut Vec<Point2D>) -> Vec<Point2D> {
+pub fn graham_scan<T: CoordNum>(input_set: &mut [Point2D<T>]) -> Vec<Point2D<T>> {
+    let mut hull_points: Vec<Point2D<T>> = Vec::new();
+    graham_scan_into(input_set, &mut hull_points);
+    hull_points
+}
+
+///Containers the hull points can be collected into
+///
+/// Typical hulls have few vertices, so batch workloads can avoid
+/// a heap allocation per hull by collecting into a SmallVec (with
+/// the `small_hulls` feature) or by reusing one buffer for every hull.
+pub trait HullBuffer<T = f64> {
+    /// removes all the points
+    fn clear(&mut self);
+    /// adds a point at the end
+    fn push(&mut self, point: Point2D<T>);
+    /// removes the last point
+    fn pop(&mut self) -> Option<Point2D<T>>;
+    /// the points collected so far
+    fn as_slice(&self) -> &[Point2D<T>];
+    /// the points collected so far, to reorder them
+    fn as_mut_slice(&mut self) -> &mut [Point2D<T>];
+}
+
+#[cfg(not(feature = "allocator_api"))]
+impl<T> HullBuffer<T> for Vec<Point2D<T>> {
+    fn clear(&mut self) {
+        Vec::clear(self)
+    }
+
+    fn push(&mut self, point: Point2D<T>) {
+        Vec::push(self, point)
+    }
+
+    fn pop(&mut self) -> Option<Point2D<T>> {
+        Vec::pop(self)
+    }
+
+    fn as_slice(&self) -> &[Point2D<T>] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [Point2D<T>] {
+        self
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+impl<T, A: Allocator> HullBuffer<T> for Vec<Point2D<T>, A> {
+    fn clear(&mut self) {
+        Vec::clear(self)
+    }
+
+    fn push(&mut self, point: Point2D<T>) {
+        Vec::push(self, point)
+    }
+
+    fn pop(&mut self) -> Option<Point2D<T>> {
+        Vec::pop(self)
+    }
+
+    fn as_slice(&self) -> &[Point2D<T>] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [Point2D<T>] {
+        self
+    }
+}
+
+#[cfg(feature = "small_hulls")]
+extern crate smallvec;
+
+#[cfg(feature = "small_hulls")]
+impl<T, A: smallvec::Array<Item = Point2D<T>>> HullBuffer<T> for smallvec::SmallVec<A> {
+    fn clear(&mut self) {
+        smallvec::SmallVec::clear(self)
+    }
+
+    fn push(&mut self, point: Point2D<T>) {
+        smallvec::SmallVec::push(self, point)
+    }
+
+    fn pop(&mut self) -> Option<Point2D<T>> {
+        smallvec::SmallVec::pop(self)
+    }
+
+    fn as_slice(&self) -> &[Point2D<T>] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [Point2D<T>] {
+        smallvec::SmallVec::as_mut_slice(self)
+    }
+}
+
+/// Graham-Scan collecting the hull points into the given buffer
+///
+/// The buffer is cleared first, so one buffer can be reused
+/// for many input sets.
+pub fn graham_scan_into<T: CoordNum, B: HullBuffer<T>>(input_set: &[Point2D<T>], hull_points: &mut B) {
     let sorted_input_set = input_set;
 
     //initialize the stack that will maintain the candidate points
-    let mut hull_points: Vec<Point2D> = Vec::new();
-    hull_points.push(sorted_input_set[0]);
-    hull_points.push(sorted_input_set[1]);
-    hull_points.push(sorted_input_set[2]);
-    for i in 3..sorted_input_set.len() {
-        while !hull_points[hull_points.len() - 2]
-            .ccw(&hull_points[hull_points.len() - 1], &sorted_input_set[i])
-        {
+    hull_points.clear();
+    for point in sorted_input_set {
+        loop {
+            let len = hull_points.as_slice().len();
+            let stack = hull_points.as_slice();
+            //the pivot always stays
+            if len < 2 || stack[len - 2].ccw(&stack[len - 1], point) {
+                break;
+            }
             hull_points.pop();
         }
-        hull_points.push(sorted_input_set[i])
+        hull_points.push(*point)
     }
-    hull_points
+    canonicalize(hull_points.as_mut_slice());
+}
+
+/// Graham-Scan within the given slice, without allocating
+///
+/// The points are sorted in place by polar angle around the lowest
+/// point (the leftmost one on a tie) with `cmp_polar_angle`, and
+/// the stack of the scan is kept at the front of the slice. The hull
+/// points end up in the first positions of the slice, in the order of
+/// graham_scan, and their number is returned. The other positions hold
+/// the remaining points in no particular order.
+///
+/// Of the points at the same angle only the farthest can be a vertex,
+/// as in sort_input, so collinear points are left out.
+pub fn graham_scan_in_place<T: CoordNum>(input_set: &mut [Point2D<T>]) -> usize {
+    if input_set.is_empty() {
+        return 0;
+    }
+    for i in 1..input_set.len() {
+        if input_set[i].pick_left(&input_set[0]) == &input_set[i] {
+            input_set.swap(0, i);
+        }
+    }
+    let pivot = input_set[0];
+    input_set[1..].sort_unstable_by(|a, b| cmp_polar_angle(&pivot, a, b));
+
+    let mut top = 1;
+    let mut previous: Option<Point2D<T>> = None;
+    for i in 1..input_set.len() {
+        let point = input_set[i];
+        let same_angle = previous.is_some_and(|previous| T::orientation(&pivot, &previous, &point) == Orientation::Collinear);
+        if point == pivot || same_angle {
+            continue;
+        }
+        previous = Some(point);
+        while top >= 2 && !input_set[top - 2].ccw(&input_set[top - 1], &point) {
+            top -= 1;
+        }
+        input_set.swap(top, i);
+        top += 1;
+    }
+    canonicalize(&mut input_set[..top]);
+    top
 }
 
 /// Solves the convexhull problem using Jarvis-March
 ///
+/// The hull is wrapped clockwise from the lowest point of the input
+/// set and returned in the canonical form of `geometry::canonicalize`,
+/// and points on the hull edges are left out, see jarvis_march_with.
+///
 /// The psedocode for this algorithm is referred from
 /// Introduction to Algorithms (Third Edition)
 /// Authors: Thomas H. Cormen, Charles E. Leiserson, Ronald L. Rivest and Clifford Stein
-pub fn jarvis_march(input_set: &mut Vec<Point2D>) -> Vec<Point2D> {
-    let sorted_input_set = input_set;
+pub fn jarvis_march<T: CoordNum>(input_set: &mut [Point2D<T>]) -> Vec<Point2D<T>> {
+    jarvis_march_with(input_set, JarvisOptions::default())
+}
 
-    let mut last_known_hull_point = sorted_input_set[0];
-    //initialize the stack that will maintain the candidate points
-    let mut hull_points: Vec<Point2D> = Vec::new();
-    hull_points.push(last_known_hull_point);
-    for _i in 0..sorted_input_set.len() {
-        let p_i = last_known_hull_point;
-        let mut end_point = sorted_input_set[0];
-        for j in 1..sorted_input_set.len() {
-            if end_point == last_known_hull_point 
-                            || sorted_input_set[j].ccw(&p_i, &end_point) {
-                end_point = sorted_input_set[j];
-            }
-        }
-        if end_point == hull_points[0] {
-            break;
+///Settings of jarvis_march_with
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct JarvisOptions {
+    /// also return the points inside the hull edges, in their order
+    /// along the boundary
+    pub collinear: bool,
+}
+
+/// Jarvis-March with the given settings
+///
+/// Every step takes the point that leaves no other point left of the
+/// new edge, the farthest one of several on the same line, and skips
+/// copies of the current vertex. The wrap stops when it reaches the
+/// first point again, or a copy of it, so repeated points neither make
+/// it loop nor drop vertices. With `collinear` the points inside every
+/// edge are added afterwards, which keeps the O(nh) time for h vertices.
+///
+/// The hull is wrapped clockwise starting at the lowest point, the
+/// leftmost of several, which is always a vertex. The result is turned
+/// counter clockwise from the leftmost vertex, like every algorithm's.
+///
+/// # Panics
+/// Panics when the input set is empty
+pub fn jarvis_march_with<T: CoordNum>(input_set: &[Point2D<T>], options: JarvisOptions) -> Vec<Point2D<T>> {
+    let mut hull_points: Vec<Point2D<T>> = Vec::new();
+    gift_wrap(input_set, &mut hull_points);
+    if options.collinear && hull_points.len() > 1 {
+        hull_points = add_edge_points(&hull_points, input_set);
+    }
+    canonicalize(&mut hull_points);
+    hull_points
+}
+
+/// Jarvis-March with the hull allocated by the given allocator, which
+/// is the only buffer it needs, with the default JarvisOptions
+///
+/// Requires the `allocator_api` feature, which needs a nightly compiler.
+///
+/// # Panics
+/// Panics when the input set is empty
+#[cfg(feature = "allocator_api")]
+pub fn jarvis_march_in<T: CoordNum, A: Allocator>(input_set: &[Point2D<T>], alloc: A) -> Vec<Point2D<T>, A> {
+    let mut hull_points = Vec::new_in(alloc);
+    gift_wrap(input_set, &mut hull_points);
+    canonicalize(&mut hull_points);
+    hull_points
+}
+
+/// The hull vertices wrapped clockwise from the lowest point of the
+/// input set, see jarvis_march_with
+fn gift_wrap<T: CoordNum, B: HullBuffer<T>>(input_set: &[Point2D<T>], hull_points: &mut B) {
+    let start = *input_set.iter().fold(&input_set[0], |lowest, point| point.pick_left(lowest));
+    let distance = |from: &Point2D<T>, to: &Point2D<T>| T::dot(from, to, to);
+    hull_points.clear();
+    hull_points.push(start);
+    let mut current = start;
+    //a hull has at most as many vertices as there are points
+    for _ in 0..input_set.len() {
+        let mut next: Option<Point2D<T>> = None;
+        for point in input_set {
+            if *point == current {
+                continue;
+            }
+            next = match next {
+                Some(end_point) => {
+                    let turn = T::orientation(&current, &end_point, point);
+                    if turn == Orientation::CounterClockwise
+                        || (turn == Orientation::Collinear && distance(&current, point) > distance(&current, &end_point))
+                    {
+                        Some(*point)
+                    } else {
+                        Some(end_point)
+                    }
+                }
+                None => Some(*point),
+            };
+        }
+        match next {
+            Some(next) if next != start => {
+                hull_points.push(next);
+                current = next;
+            }
+            _ => break,
         }
-        hull_points.push(end_point);
-        last_known_hull_point = end_point;
+    }
+}
+
+/// The vertices with the points inside every edge between them
+fn add_edge_points<T: CoordNum>(vertices: &[Point2D<T>], input_set: &[Point2D<T>]) -> Vec<Point2D<T>> {
+    let mut hull_points: Vec<Point2D<T>> = Vec::new();
+    let zero = T::Product::default();
+    for (i, start) in vertices.iter().enumerate() {
+        let end = vertices[(i + 1) % vertices.len()];
+        let along = |point: &Point2D<T>| T::dot(start, &end, point);
+        let length = along(&end);
+        let mut inside: Vec<Point2D<T>> = input_set
+            .iter()
+            .filter(|point| {
+                T::orientation(start, &end, point) == Orientation::Collinear && along(point) > zero && along(point) < length
+            })
+            .cloned()
+            .collect();
+        inside.sort_by(|a, b| along(a).partial_cmp(&along(b)).unwrap());
+        inside.dedup();
+        hull_points.push(*start);
+        hull_points.extend(inside);
     }
     hull_points
 }
 
 /// Solves the convexhull problem using chans-algorithm
 ///
+/// A guess m of the hull size splits the input set into groups of m
+/// points whose mini hulls are found with a monotone chain. Gift
+/// wrapping then steps from hull vertex to hull vertex, finding the
+/// tangent to every mini hull with a binary search. When m steps don't
+/// close the hull the guess is squared, m = 2^(2^t), and the rounds
+/// start over. This takes O(n log h) time for h hull vertices.
+///
+/// The hull is wrapped from the point with the least y-coordinate and
+/// returned counter clockwise from the leftmost vertex. The input set
+/// doesn't need to be sorted.
+///
 /// The pseudocode for this algorithm is referred from
 /// https://en.wikipedia.org/wiki/Chan%27s_algorithm
 /// https://www.slideshare.net/amrinderarora/convex-hull-chans-algorithm-on-log-h-output-sensitive-algorithm
-pub fn chans_algorithm(input_set: &mut Vec<Point2D>) -> Vec<Point2D> {
-    let sorted_input_set = input_set;   
-
-    let first_hull_vertex = sorted_input_set[0];
-    //assign a least element (-infinity, 0). This is used
-    //for finding second hull element, when we have only one
-    //known hull vertex in the output set.
-    //Note: point_off is not a point of input_set
-    let point_off = Point2D::new(first_hull_vertex.x - 1.0, 0.);
-
-    //t is the iteration needed to find m.
-    //m is the magic number of chans algorithm. It is required to
-    //be greater than or equal to h (number of vertices)
-    for t in 1..sorted_input_set.len() {
-        let mut m = 2_i32.pow(2).pow(t as u32) as usize;
-        // split the input into smaller chunks using m
-        let mut total_number_of_chunks = sorted_input_set.len() / m;
-        //ensure that the last chunk has atleat 3 elements in it.
-        //else the algorithm will panic.
-        let mut size_of_last_set 
-                    = sorted_input_set.len() - (total_number_of_chunks * m);
-        while size_of_last_set > 0 && size_of_last_set < 3 {
-            m += 1;
-            total_number_of_chunks = sorted_input_set.len() / m;
-            size_of_last_set 
-                    = sorted_input_set.len() - (total_number_of_chunks * m);
-        }
-
-        let mut chunks_set = sorted_input_set.chunks(m);
-        let mut hull_set_of_chuncks: Vec<Vec<Point2D>> = Vec::new();
-        for chunk_set in chunks_set {
-            let mut sorted_chunk = sort_input(&mut chunk_set.to_vec());
-            hull_set_of_chuncks.push(graham_scan(&mut sorted_chunk));
-        }
-        let mut hull_points: Vec<Point2D> = Vec::new();
-        hull_points.push(first_hull_vertex);
-        // Find 1 hull point in each iteration.
-        // If not all the hull points are found within m iterations
-        // reset m and restart the algorithm.
-        for i in 0..(m - 1) {
-            let mut hull_candidates_of_chuncks: Vec<Point2D> = Vec::new();
-            //pick candidates from each chunk
-            for hull_set_of_chunck in &hull_set_of_chuncks {
-                hull_candidates_of_chuncks.push(jarvis_binary_search(
-                    if hull_points.len() > 1 {
-                        &hull_points[i - 1]
-                    } else {
-                        &point_off
-                    },
-                    &hull_points[i],
-                    hull_set_of_chunck,
-                ));
-            }
-            //find the next hull point from the chosen candidates
-            let next_hull_point = jarvis_binary_search(
-                if hull_points.len() > 1 {
-                    &hull_points[i - 1]
+/// T. Chan, "Optimal output-sensitive convex hull algorithms in two
+/// and three dimensions", Discrete & Computational Geometry, 1996
+pub fn chans_algorithm<T: CoordNum>(input_set: &mut [Point2D<T>]) -> Vec<Point2D<T>> {
+    chans_algorithm_with(input_set, GuessSchedule::Squaring)
+}
+
+///How chans_algorithm grows its guess m of the hull size each round
+///
+/// A round with guess m costs O(n log m). Squaring keeps the total at
+/// O(n log h) with few rounds, doubling needs more rounds, but never
+/// overshoots h by more than twice, which makes its groups smaller
+/// and its tangent searches shorter.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub enum GuessSchedule {
+    /// m = 2^(2^t): 4, 16, 256, 65536, ...
+    #[default]
+    Squaring,
+    /// m = 2^t: 4, 8, 16, 32, ..., taking O(n log² h) time
+    Doubling,
+    /// m = start · factor^t, to start at a known estimate of h; factors
+    /// below 2 count as 2
+    Geometric { start: usize, factor: usize },
+}
+
+impl GuessSchedule {
+    /// The guess of the round t = 0, 1, ..., up to `max`
+    pub fn guess(&self, t: u32, max: usize) -> usize {
+        let guess = match *self {
+            GuessSchedule::Squaring => 1u32.checked_shl(t + 1).and_then(|exponent| 1usize.checked_shl(exponent)),
+            GuessSchedule::Doubling => 1usize.checked_shl(t + 2),
+            GuessSchedule::Geometric { start, factor } => factor
+                .max(2)
+                .checked_pow(t)
+                .and_then(|power| power.checked_mul(start.max(1))),
+        };
+        guess.unwrap_or(max).min(max)
+    }
+}
+
+/// Chan's algorithm with the guesses of the hull size taken from a
+/// schedule, see chans_algorithm
+pub fn chans_algorithm_with<T: CoordNum>(input_set: &[Point2D<T>], schedule: GuessSchedule) -> Vec<Point2D<T>> {
+    //duplicates would sit on the mini hulls of several groups,
+    //where the tangent search can't tell them from the current vertex
+    let mut seen: HashSet<(u64, u64)> = HashSet::new();
+    let points: Vec<Point2D<T>> = input_set
+        .iter()
+        .cloned()
+        .filter(|point| seen.insert((point.x.to_bits(), point.y.to_bits())))
+        .collect();
+    if points.is_empty() {
+        return Vec::new();
+    }
+    let first = (0..points.len())
+        .fold(0, |first, i| if points[i].pick_left(&points[first]) == &points[i] { i } else { first });
+
+    for t in 0.. {
+        let m = schedule.guess(t, points.len());
+        let mini_hulls: Vec<Vec<Point2D<T>>> = points.chunks(m).map(convex_hull_exact).collect();
+
+        // the current hull vertex as (mini hull, position in it)
+        let mut current = (first / m, 0);
+        current.1 = mini_hulls[current.0]
+            .iter()
+            .position(|vertex| *vertex == points[first])
+            .unwrap();
+        let mut hull_points: Vec<Point2D<T>> = vec![points[first]];
+        for _ in 0..m {
+            let top = mini_hulls[current.0][current.1];
+            let mut next: Option<(usize, usize)> = None;
+            for (group, mini_hull) in mini_hulls.iter().enumerate() {
+                let candidate = if group == current.0 {
+                    (current.1 + 1) % mini_hull.len()
                 } else {
-                    &point_off
-                },
-                &hull_points[i],
-                &hull_candidates_of_chuncks,
-            );
-            //iterate or end
-            if next_hull_point == hull_points[0] {
+                    tangent(&top, mini_hull)
+                };
+                if mini_hull[candidate] == top {
+                    continue;
+                }
+                let better = match next {
+                    None => true,
+                    Some((best_group, best)) => {
+                        wraps_before(&top, &mini_hull[candidate], &mini_hulls[best_group][best])
+                    }
+                };
+                if better {
+                    next = Some((group, candidate));
+                }
+            }
+            let next = match next {
+                Some(next) => next,
+                // a single distinct point
+                None => return hull_points,
+            };
+            if mini_hulls[next.0][next.1] == hull_points[0] {
+                canonicalize(&mut hull_points);
                 return hull_points;
+            }
+            hull_points.push(mini_hulls[next.0][next.1]);
+            current = next;
+        }
+    }
+    unreachable!()
+}
+
+/// True when gift wrapping from `top` should step to `a` rather
+/// than `b`: `a` is right of the ray from `top` to `b`, or on that
+/// ray and farther away
+fn wraps_before<T: CoordNum>(top: &Point2D<T>, a: &Point2D<T>, b: &Point2D<T>) -> bool {
+    let turn = T::orientation(top, b, a);
+    turn == Orientation::Clockwise
+        || (turn == Orientation::Collinear && T::dot(top, a, b) > T::Product::default() && T::dot(top, a, a) > T::dot(top, b, b))
+}
+
+/// Finds the vertex of a counter clockwise mini hull that gift
+/// wrapping steps to from a point outside of it, in O(log m)
+///
+/// Seen from the point, the direction to the vertices turns counter
+/// clockwise and then clockwise along the mini hull. The wanted vertex
+/// is where it turns back, which a binary search relative to the
+/// first vertex finds. A short walk to a better neighbour settles
+/// collinear vertices.
+fn tangent<T: CoordNum>(top: &Point2D<T>, mini_hull: &[Point2D<T>]) -> usize {
+    let len = mini_hull.len();
+    if len < 3 {
+        return (0..len).fold(0, |best, i| if wraps_before(top, &mini_hull[i], &mini_hull[best]) { i } else { best });
+    }
+    let rising = |i: usize| T::orientation(top, &mini_hull[i], &mini_hull[(i + 1) % len]) != Orientation::Clockwise;
+    let above_first = |i: usize| T::orientation(top, &mini_hull[0], &mini_hull[i]) == Orientation::CounterClockwise;
+    //the vertices from the wanted one up to the end of the list pass the test
+    let rising_at_first = rising(0);
+    let past_tangent = |i: usize| {
+        if rising_at_first {
+            rising(i) && !above_first(i)
+        } else {
+            rising(i) || above_first(i)
+        }
+    };
+    let (mut low, mut high) = (1, len);
+    while low < high {
+        let mid = (low + high) / 2;
+        if past_tangent(mid) {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    let mut best = low % len;
+    loop {
+        let next = (best + 1) % len;
+        let previous = (best + len - 1) % len;
+        if wraps_before(top, &mini_hull[next], &mini_hull[best]) {
+            best = next;
+        } else if wraps_before(top, &mini_hull[previous], &mini_hull[best]) {
+            best = previous;
+        } else {
+            return best;
+        }
+    }
+}
+
+/// Solves the convexhull problem using QuickHull
+///
+/// The leftmost and the rightmost point split the input set into the
+/// points below and above the line through them. On each side the
+/// point farthest from the line is a hull vertex; the points inside
+/// the triangle it makes are dropped and the two outer groups are
+/// split the same way. This takes O(n log n) time on typical input
+/// sets but O(n²) in the worst case, when the splits are lopsided.
+///
+/// The hull is in counter clockwise order, starting at the leftmost
+/// point (the lowest one on a tie). The input set doesn't need to be sorted.
+///
+/// W. Eddy, "A new convex hull algorithm for planar sets", ACM
+/// Transactions on Mathematical Software, 1977
+pub fn quick_hull<T: CoordNum>(input_set: &[Point2D<T>]) -> Vec<Point2D<T>> {
+    let mut hull_points: Vec<Point2D<T>> = Vec::new();
+    quick_hull_into(input_set, &mut hull_points, &Vec::<Point2D<T>>::new);
+    hull_points
+}
+
+/// QuickHull with the hull and the points of every split allocated by
+/// the given allocator
+///
+/// Requires the `allocator_api` feature, which needs a nightly compiler.
+#[cfg(feature = "allocator_api")]
+pub fn quick_hull_in<T: CoordNum, A: Allocator + Clone>(input_set: &[Point2D<T>], alloc: A) -> Vec<Point2D<T>, A> {
+    let mut hull_points = Vec::new_in(alloc.clone());
+    quick_hull_into(input_set, &mut hull_points, &|| Vec::new_in(alloc.clone()));
+    hull_points
+}
+
+/// QuickHull collecting the hull into `hull_points`, with the points
+/// of every split collected into a buffer from `new_buffer`
+fn quick_hull_into<T, H, B, F>(input_set: &[Point2D<T>], hull_points: &mut H, new_buffer: &F)
+where
+    T: CoordNum,
+    H: HullBuffer<T>,
+    B: HullBuffer<T>,
+    F: Fn() -> B,
+{
+    hull_points.clear();
+    if input_set.is_empty() {
+        return;
+    }
+    let compare = |a: &&Point2D<T>, b: &&Point2D<T>| {
+        a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap())
+    };
+    let leftmost = *input_set.iter().min_by(compare).unwrap();
+    let rightmost = *input_set.iter().max_by(compare).unwrap();
+    hull_points.push(leftmost);
+    if leftmost == rightmost {
+        return;
+    }
+    let (mut below, mut above) = (new_buffer(), new_buffer());
+    for point in input_set {
+        match T::orientation(&leftmost, &rightmost, point) {
+            Orientation::Clockwise => below.push(*point),
+            Orientation::CounterClockwise => above.push(*point),
+            Orientation::Collinear => {}
+        }
+    }
+    quick_hull_chain(&leftmost, &rightmost, below.as_slice(), hull_points, new_buffer);
+    hull_points.push(rightmost);
+    quick_hull_chain(&rightmost, &leftmost, above.as_slice(), hull_points, new_buffer);
+}
+
+/// Adds the hull vertices between `from` and `to` among the points,
+/// which are all right of the line from `from` to `to`
+fn quick_hull_chain<T, H, B, F>(from: &Point2D<T>, to: &Point2D<T>, points: &[Point2D<T>], hull_points: &mut H, new_buffer: &F)
+where
+    T: CoordNum,
+    H: HullBuffer<T>,
+    B: HullBuffer<T>,
+    F: Fn() -> B,
+{
+    let farthest = match farthest_from(from, to, points) {
+        Some(farthest) => farthest,
+        None => return,
+    };
+    let before = outside(from, &farthest, points, new_buffer());
+    quick_hull_chain(from, &farthest, before.as_slice(), hull_points, new_buffer);
+    hull_points.push(farthest);
+    let after = outside(&farthest, to, points, new_buffer());
+    quick_hull_chain(&farthest, to, after.as_slice(), hull_points, new_buffer);
+}
+
+/// The point farthest right of the line from `from` to `to`
+fn farthest_from<T: CoordNum>(from: &Point2D<T>, to: &Point2D<T>, points: &[Point2D<T>]) -> Option<Point2D<T>> {
+    //of equally far points the one nearest to `from` is a vertex
+    points
+        .iter()
+        .min_by(|a, b| {
+            T::cross(from, to, a)
+                .partial_cmp(&T::cross(from, to, b))
+                .unwrap()
+                .then(T::dot(from, to, a).partial_cmp(&T::dot(from, to, b)).unwrap())
+        })
+        .cloned()
+}
+
+/// The points right of the line from `start` to `end`, collected into
+/// the buffer
+fn outside<T: CoordNum, B: HullBuffer<T>>(start: &Point2D<T>, end: &Point2D<T>, points: &[Point2D<T>], mut outside: B) -> B {
+    for point in points {
+        if T::orientation(start, end, point) == Orientation::Clockwise {
+            outside.push(*point);
+        }
+    }
+    outside
+}
+
+#[cfg(feature = "parallel")]
+extern crate rayon;
+
+/// Groups smaller than this are split on the calling thread
+#[cfg(feature = "parallel")]
+const PARALLEL_CUTOFF: usize = 1 << 14;
+
+/// Solves the convexhull problem using QuickHull on all cores
+///
+/// The splits are the ones of quick_hull, but the two groups of
+/// every split are processed at once with `rayon::join`, on the
+/// threads of the rayon pool. Groups of fewer than 16384 points are
+/// processed sequentially, where spawning costs more than it saves.
+///
+/// The hull is the one quick_hull returns.
+#[cfg(feature = "parallel")]
+pub fn parallel_quick_hull<T: CoordNum>(input_set: &[Point2D<T>]) -> Vec<Point2D<T>> {
+    if input_set.is_empty() {
+        return Vec::new();
+    }
+    let compare = |a: &&Point2D<T>, b: &&Point2D<T>| {
+        a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap())
+    };
+    let leftmost = *input_set.iter().min_by(compare).unwrap();
+    let rightmost = *input_set.iter().max_by(compare).unwrap();
+    if leftmost == rightmost {
+        return vec![leftmost];
+    }
+    let (lower, upper) = rayon::join(
+        || parallel_quick_hull_chain(&leftmost, &rightmost, &outside(&leftmost, &rightmost, input_set, Vec::new())),
+        || parallel_quick_hull_chain(&rightmost, &leftmost, &outside(&rightmost, &leftmost, input_set, Vec::new())),
+    );
+    let mut hull_points: Vec<Point2D<T>> = Vec::with_capacity(lower.len() + upper.len() + 2);
+    hull_points.push(leftmost);
+    hull_points.extend(lower);
+    hull_points.push(rightmost);
+    hull_points.extend(upper);
+    hull_points
+}
+
+/// The hull vertices between `from` and `to` among the points, found
+/// like quick_hull_chain does
+#[cfg(feature = "parallel")]
+fn parallel_quick_hull_chain<T: CoordNum>(from: &Point2D<T>, to: &Point2D<T>, points: &[Point2D<T>]) -> Vec<Point2D<T>> {
+    let mut hull_points: Vec<Point2D<T>> = Vec::new();
+    if points.len() < PARALLEL_CUTOFF {
+        quick_hull_chain(from, to, points, &mut hull_points, &Vec::<Point2D<T>>::new);
+        return hull_points;
+    }
+    let farthest = match farthest_from(from, to, points) {
+        Some(farthest) => farthest,
+        None => return hull_points,
+    };
+    let (before, after) = rayon::join(
+        || parallel_quick_hull_chain(from, &farthest, &outside(from, &farthest, points, Vec::new())),
+        || parallel_quick_hull_chain(&farthest, to, &outside(&farthest, to, points, Vec::new())),
+    );
+    hull_points.extend(before);
+    hull_points.push(farthest);
+    hull_points.extend(after);
+    hull_points
+}
+
+/// Solves the convexhull problem by divide and conquer on several threads
+///
+/// The input set is cut into `threads` chunks of about equal size
+/// that are separated by x-coordinate (and y on ties), by repeated
+/// selection in O(n log threads) time. Every chunk has its upper and
+/// lower hull found by kirkpatrick_seidel's method on its own thread,
+/// and the hulls of neighbouring chunks are merged by the tangents
+/// (bridges) over and under both, in time linear in their sizes.
+///
+/// The hull is in counter clockwise order, starting at the leftmost
+/// point (the lowest one on a tie). With one thread nothing is spawned.
+pub fn divide_and_conquer_hull<T: CoordNum>(input_set: &[Point2D<T>], threads: usize) -> Vec<Point2D<T>> {
+    if input_set.is_empty() {
+        return Vec::new();
+    }
+    let mut points = input_set.to_vec();
+    let mut chunks: Vec<&mut [Point2D<T>]> = Vec::new();
+    split_chunks(&mut points, threads.max(1).min(input_set.len()), &mut chunks);
+    let chains: Vec<_> = if chunks.len() == 1 {
+        vec![(lower_hull(chunks[0]), upper_hull(chunks[0]))]
+    } else {
+        thread::scope(|scope| {
+            let handles: Vec<_> = chunks
+                .into_iter()
+                .map(|chunk| scope.spawn(move || (lower_hull(chunk), upper_hull(chunk))))
+                .collect();
+            handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+        })
+    };
+    let mut chains = chains.into_iter();
+    let (mut hull_points, mut upper) = chains.next().unwrap();
+    for (lower_right, upper_right) in chains {
+        hull_points = merge_chains(hull_points, &lower_right, false);
+        upper = merge_chains(upper, &upper_right, true);
+    }
+    upper.reverse();
+    if upper.first() == hull_points.last() {
+        upper.remove(0);
+    }
+    if upper.last() == hull_points.first() {
+        upper.pop();
+    }
+    hull_points.extend(upper);
+    hull_points
+}
+
+/// Cuts the points into at most `parts` slices of about equal size,
+/// each one left of the next
+///
+/// Points with the same x-coordinate stay in one slice, since a column
+/// split between two of them would leave the inner points of a vertical
+/// hull edge in both chains.
+fn split_chunks<'a, T: CoordNum>(points: &'a mut [Point2D<T>], parts: usize, chunks: &mut Vec<&'a mut [Point2D<T>]>) {
+    if parts <= 1 || points.len() < 2 {
+        chunks.push(points);
+        return;
+    }
+    let left_parts = parts / 2;
+    let middle = points.len() * left_parts / parts;
+    points.select_nth_unstable_by(middle, |a, b| {
+        a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap())
+    });
+    //the column at the middle goes to the right, or to the left when
+    //nothing else is left of it
+    let x = points[middle].x;
+    let before = move_first(&mut points[..middle], |point| point.x < x);
+    let middle = if before > 0 { before } else { middle + move_first(&mut points[middle..], |point| point.x == x) };
+    if middle == points.len() {
+        chunks.push(points);
+        return;
+    }
+    let (left, right) = points.split_at_mut(middle);
+    split_chunks(left, left_parts, chunks);
+    split_chunks(right, parts - left_parts, chunks);
+}
+
+/// Moves the points the predicate holds for before the others, and
+/// returns how many there are
+fn move_first<T, F: Fn(&Point2D<T>) -> bool>(points: &mut [Point2D<T>], predicate: F) -> usize {
+    let mut count = 0;
+    for i in 0..points.len() {
+        if predicate(&points[i]) {
+            points.swap(count, i);
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Joins the upper chains (`upper` true) or the lower chains of two
+/// hulls, the left one before the right one, at their bridge
+fn merge_chains<T: CoordNum>(mut left: Vec<Point2D<T>>, right: &[Point2D<T>], upper: bool) -> Vec<Point2D<T>> {
+    //the turn that keeps a vertex on the chain
+    let kept = if upper { Orientation::Clockwise } else { Orientation::CounterClockwise };
+    let (mut i, mut j) = (left.len() - 1, 0);
+    loop {
+        let mut moved = false;
+        //a vertex under the line to the other chain is not on the bridge
+        while i > 0 && T::orientation(&left[i - 1], &left[i], &right[j]) != kept {
+            i -= 1;
+            moved = true;
+        }
+        while j + 1 < right.len() && T::orientation(&left[i], &right[j], &right[j + 1]) != kept {
+            j += 1;
+            moved = true;
+        }
+        if !moved {
+            break;
+        }
+    }
+    left.truncate(i + 1);
+    left.extend_from_slice(&right[j..]);
+    left
+}
+
+/// Solves the convexhull problem using the Kirkpatrick–Seidel algorithm
+///
+/// The upper and the lower hull are found separately by "marriage
+/// before conquest": the hull edge (bridge) crossing the vertical line
+/// through the median x-coordinate is found first, in linear time with
+/// prune and search, and only the points outside the bridge are
+/// recursed on. This takes O(n log h) time for h hull vertices.
+///
+/// The hull is in counter clockwise order, starting at the leftmost
+/// point (the lowest one on a tie). The input set doesn't need to be sorted.
+///
+/// D. Kirkpatrick and R. Seidel, "The ultimate planar convex hull
+/// algorithm?", SIAM Journal on Computing, 1986
+pub fn kirkpatrick_seidel<T: CoordNum>(input_set: &[Point2D<T>]) -> Vec<Point2D<T>> {
+    if input_set.is_empty() {
+        return Vec::new();
+    }
+    let mut hull_points = lower_hull(input_set);
+    let mut upper = upper_hull(input_set);
+    upper.reverse();
+    if upper.first() == hull_points.last() {
+        upper.remove(0);
+    }
+    if upper.last() == hull_points.first() {
+        upper.pop();
+    }
+    hull_points.extend(upper);
+    hull_points
+}
+
+/// The upper hull of the input set, from its leftmost to its rightmost vertex
+///
+/// This is the chain of hull vertices seen from above. Its ends are
+/// the highest of the points with the least and with the greatest
+/// x-coordinate, and vertical hull edges at the ends are left out.
+/// It is found like in kirkpatrick_seidel, in O(n log h) time for h
+/// vertices of the chain, without computing the other chain.
+pub fn upper_hull<T: CoordNum>(input_set: &[Point2D<T>]) -> Vec<Point2D<T>> {
+    if input_set.is_empty() {
+        return Vec::new();
+    }
+    //the highest of the leftmost and of the rightmost points
+    let leftmost = input_set.iter().skip(1).fold(input_set[0], |leftmost, point| {
+        if point.x < leftmost.x || (point.x == leftmost.x && point.y > leftmost.y) { *point } else { leftmost }
+    });
+    let rightmost = input_set.iter().skip(1).fold(input_set[0], |rightmost, point| {
+        if point.x > rightmost.x || (point.x == rightmost.x && point.y > rightmost.y) { *point } else { rightmost }
+    });
+    let mut hull_points: Vec<Point2D<T>> = Vec::new();
+    if leftmost == rightmost {
+        hull_points.push(leftmost);
+        return hull_points;
+    }
+    let mut points: Vec<Point2D<T>> = input_set
+        .iter()
+        .cloned()
+        .filter(|point| point.x > leftmost.x && point.x < rightmost.x)
+        .collect();
+    points.push(leftmost);
+    points.push(rightmost);
+    connect(&leftmost, &rightmost, points, &mut hull_points);
+    hull_points
+}
+
+/// The lower hull of the input set, from its leftmost to its rightmost vertex
+///
+/// The ends of the chain are the lowest of the points with the least
+/// and with the greatest x-coordinate. See upper_hull.
+pub fn lower_hull<T: CoordNum>(input_set: &[Point2D<T>]) -> Vec<Point2D<T>> {
+    //the lower hull is the upper hull of the input set mirrored at the x axis
+    let mirrored: Vec<Point2D<T>> = input_set.iter().map(|point| Point2D::new(point.x, -point.y)).collect();
+    upper_hull(&mirrored)
+        .into_iter()
+        .map(|point| Point2D::new(point.x, -point.y))
+        .collect()
+}
+
+/// Adds the upper hull vertices from `left` to `right`, the only
+/// points with the least and the greatest x-coordinate among the points
+fn connect<T: CoordNum>(left: &Point2D<T>, right: &Point2D<T>, mut points: Vec<Point2D<T>>, hull_points: &mut Vec<Point2D<T>>) {
+    let median = (points.len() - 1) / 2;
+    points.select_nth_unstable_by(median, |a, b| a.x.partial_cmp(&b.x).unwrap());
+    let split = points[median].x;
+    let (bridge_left, bridge_right) = bridge(points.clone(), split);
+    if bridge_left == *left {
+        hull_points.push(*left);
+    } else {
+        let mut left_points: Vec<Point2D<T>> = points.iter().cloned().filter(|point| point.x < bridge_left.x).collect();
+        left_points.push(bridge_left);
+        connect(left, &bridge_left, left_points, hull_points);
+    }
+    if bridge_right == *right {
+        hull_points.push(*right);
+    } else {
+        let mut right_points: Vec<Point2D<T>> = points.iter().cloned().filter(|point| point.x > bridge_right.x).collect();
+        right_points.push(bridge_right);
+        connect(&bridge_right, right, right_points, hull_points);
+    }
+}
+
+/// Finds the upper hull edge crossing the vertical line at `split`
+///
+/// The points are paired up and the median slope of the pairs gives
+/// a supporting line. Its contact points show on which side of the
+/// split the bridge is, which rules out one point of a quarter of
+/// the pairs. Slopes are compared with cross products, so that no
+/// division is needed.
+fn bridge<T: CoordNum>(mut points: Vec<Point2D<T>>, split: T) -> (Point2D<T>, Point2D<T>) {
+    let origin = Point2D::new(T::default(), T::default());
+    let zero = T::Product::default();
+    loop {
+        if points.len() == 2 {
+            return if points[0].x <= points[1].x {
+                (points[0], points[1])
+            } else {
+                (points[1], points[0])
+            };
+        }
+        let mut candidates: Vec<Point2D<T>> = Vec::new();
+        let mut pairs: Vec<(Point2D<T>, Point2D<T>)> = Vec::new();
+        for pair in points.chunks(2) {
+            if pair.len() == 1 {
+                candidates.push(pair[0]);
+            } else if pair[0].x == pair[1].x {
+                //only the higher point of a vertical pair can be on the upper hull
+                candidates.push(if pair[0].y > pair[1].y { pair[0] } else { pair[1] });
+            } else if pair[0].x < pair[1].x {
+                pairs.push((pair[0], pair[1]));
+            } else {
+                pairs.push((pair[1], pair[0]));
+            }
+        }
+        if pairs.is_empty() {
+            points = candidates;
+            continue;
+        }
+        let slope_order = |a: &(Point2D<T>, Point2D<T>), b: &(Point2D<T>, Point2D<T>)| {
+            zero.partial_cmp(&T::cross(&origin, &difference(&a.0, &a.1), &difference(&b.0, &b.1))).unwrap()
+        };
+        let median = pairs.len() / 2;
+        pairs.select_nth_unstable_by(median, slope_order);
+        let direction = difference(&pairs[median].0, &pairs[median].1);
+        //height above a line with the median slope, scaled by direction.x
+        let products = |point: &Point2D<T>| (direction.x.widen() * point.y.widen(), direction.y.widen() * point.x.widen());
+        let height = |point: &Point2D<T>| {
+            let (rise, run) = products(point);
+            rise - run
+        };
+        let larger = |a: T::Product, b: T::Product| if b > a { b } else { a };
+        let max_height = points.iter().map(height).reduce(larger).unwrap();
+        //points within the rounding error of the highest one touch the line too,
+        //else a bridge with the median slope could lose one of its ends
+        let abs = |value: T::Product| if value < zero { -value } else { value };
+        let scale = points
+            .iter()
+            .map(|point| {
+                let (rise, run) = products(point);
+                abs(rise) + abs(run)
+            })
+            .fold(zero, larger);
+        let on_line = points
+            .iter()
+            .filter(|point| height(point) >= max_height - T::rounding_error(scale));
+        let lowest_x = on_line.clone().map(|point| point.x).reduce(|lowest, x| if x < lowest { x } else { lowest }).unwrap();
+        let highest_x = on_line.clone().map(|point| point.x).reduce(|highest, x| if x > highest { x } else { highest }).unwrap();
+        if lowest_x <= split && highest_x > split {
+            let first = *on_line.clone().find(|point| point.x == lowest_x).unwrap();
+            let last = *on_line.clone().find(|point| point.x == highest_x).unwrap();
+            return (first, last);
+        }
+        for &(low, high) in &pairs {
+            // positive when the pair is steeper than the median
+            let steeper = T::cross(&origin, &direction, &difference(&low, &high));
+            if highest_x <= split {
+                //the bridge is less steep than the median slope
+                if steeper < zero {
+                    candidates.push(low);
+                }
+                candidates.push(high);
+            } else {
+                //the bridge is steeper than the median slope
+                candidates.push(low);
+                if steeper > zero {
+                    candidates.push(high);
+                }
+            }
+        }
+        points = candidates;
+    }
+}
+
+/// The vector from one point to another
+fn difference<T: CoordNum>(from: &Point2D<T>, to: &Point2D<T>) -> Point2D<T> {
+    Point2D::new(to.x - from.x, to.y - from.y)
+}
+
+/// Solves the convexhull problem by inserting the points one at a time
+///
+/// The hull starts as a triangle of the first points, and every
+/// point outside of it remembers the hull edge crossed by the segment
+/// from a fixed point inside the triangle to it (its conflict). When a
+/// point is inserted, the edges it sees are replaced by two edges
+/// through it, and only the points in conflict with a removed edge
+/// have to be looked at again. The points are inserted in the order
+/// of the input set: a random order takes O(n log n) expected time,
+/// but adversarial orders (like points sorted around the hull) take
+/// O(n²). See randomized_incremental_hull.
+///
+/// The hull is in counter clockwise order, starting at the leftmost
+/// point (the lowest one on a tie).
+///
+/// K. Clarkson and P. Shor, "Applications of random sampling in
+/// computational geometry, II", Discrete & Computational Geometry, 1989
+pub fn incremental_hull<T: CoordNum>(input_set: &[Point2D<T>]) -> Vec<Point2D<T>> {
+    let points = input_set;
+    if points.is_empty() {
+        return Vec::new();
+    }
+    let second = match (1..points.len()).find(|&i| points[i] != points[0]) {
+        Some(second) => second,
+        None => return vec![points[0]],
+    };
+    let third = match (second + 1..points.len())
+        .find(|&i| T::orientation(&points[0], &points[second], &points[i]) != Orientation::Collinear)
+    {
+        Some(third) => third,
+        //all the points are on a line
+        None => return convex_hull_exact(points),
+    };
+    let mut triangle = [0, second, third];
+    if T::orientation(&points[0], &points[second], &points[third]) == Orientation::Clockwise {
+        triangle.swap(1, 2);
+    }
+    //the fixed point inside is the centroid of the triangle
+    let corners = [points[0], points[second], points[third]];
+    let around_center = |from: &Point2D<T>, to: &Point2D<T>| T::centroid_orientation(&corners, from, to);
+
+    //the hull is a cyclic list, and an edge is named by its first vertex
+    let mut next: Vec<usize> = vec![0; points.len()];
+    let mut previous: Vec<usize> = vec![0; points.len()];
+    for i in 0..3 {
+        next[triangle[i]] = triangle[(i + 1) % 3];
+        previous[triangle[(i + 1) % 3]] = triangle[i];
+    }
+    //the edge a point outside of the hull is in conflict with,
+    // and the points in conflict with each edge
+    let mut conflict: Vec<Option<usize>> = vec![None; points.len()];
+    let mut conflicts: Vec<Vec<usize>> = vec![Vec::new(); points.len()];
+    for point in 0..points.len() {
+        let edge = triangle.iter().cloned().find(|&start| {
+            around_center(&points[start], &points[point]) != Orientation::Clockwise
+                && around_center(&points[next[start]], &points[point]) == Orientation::Clockwise
+        });
+        if let Some(edge) = edge {
+            if T::orientation(&points[edge], &points[next[edge]], &points[point]) == Orientation::Clockwise {
+                conflict[point] = Some(edge);
+                conflicts[edge].push(point);
+            }
+        }
+    }
+
+    let mut vertex = triangle[0];
+    for point in 0..points.len() {
+        let edge = match conflict[point] {
+            Some(edge) => edge,
+            None => continue,
+        };
+        //the edges the point sees, vertices collinear with it are dropped too
+        let mut first = edge;
+        while T::orientation(&points[previous[first]], &points[first], &points[point]) != Orientation::CounterClockwise {
+            first = previous[first];
+        }
+        let mut last = next[edge];
+        while T::orientation(&points[last], &points[next[last]], &points[point]) != Orientation::CounterClockwise {
+            last = next[last];
+        }
+        let mut orphans = ::std::mem::take(&mut conflicts[first]);
+        let mut removed = next[first];
+        while removed != last {
+            orphans.append(&mut conflicts[removed]);
+            removed = next[removed];
+        }
+        next[first] = point;
+        previous[point] = first;
+        next[point] = last;
+        previous[last] = point;
+        conflict[point] = None;
+        vertex = point;
+
+        for orphan in orphans {
+            if orphan == point {
+                continue;
+            }
+            let start = if around_center(&points[point], &points[orphan]) == Orientation::Clockwise {
+                first
+            } else {
+                point
+            };
+            if T::orientation(&points[start], &points[next[start]], &points[orphan]) == Orientation::Clockwise {
+                conflict[orphan] = Some(start);
+                conflicts[start].push(orphan);
             } else {
-                hull_points.push(next_hull_point);
+                conflict[orphan] = None;
             }
         }
     }
-    Vec::new()
+
+    let mut hull_points: Vec<Point2D<T>> = vec![points[vertex]];
+    let mut current = next[vertex];
+    while current != vertex {
+        hull_points.push(points[current]);
+        current = next[current];
+    }
+    let start = (0..hull_points.len())
+        .min_by(|&a, &b| {
+            let (a, b) = (&hull_points[a], &hull_points[b]);
+            a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap())
+        })
+        .unwrap();
+    hull_points.rotate_left(start);
+    hull_points
+}
+
+/// Solves the convexhull problem with incremental_hull, inserting
+/// the points in a random order
+///
+/// Shuffling first makes the O(n log n) expected time independent
+/// of the order of the input set.
+#[cfg(feature = "generators")]
+pub fn randomized_incremental_hull<T: CoordNum>(input_set: &[Point2D<T>]) -> Vec<Point2D<T>> {
+    let mut shuffled = input_set.to_vec();
+    InputOrder::Shuffled.arrange(&mut shuffled);
+    incremental_hull(&shuffled)
+}
+
+/// Finds an approximate hull of the input set in linear time
+///
+/// The x range of the input set is cut into `strips` vertical strips
+/// of equal width, and only the lowest and the highest point of every
+/// strip are kept, together with the lowest and the highest of the
+/// points with the smallest and the largest x-coordinate. The hull of
+/// these at most 2 * strips + 4 points is returned, in the order of
+/// `hull_indices`. This takes O(n + strips log strips) time.
+///
+/// The vertices are points of the input set, so the approximation is
+/// inside the exact hull. A point of the input set outside of the
+/// approximation is at most one strip width, (max x - min x) / strips,
+/// away from it.
+///
+/// J. Bentley, M. Faust and F. Preparata, "Approximation algorithms
+/// for convex hulls", Communications of the ACM, 1982
+pub fn approximate_hull<T: CoordNum>(input_set: &[Point2D<T>], strips: usize) -> Vec<Point2D<T>> {
+    if input_set.is_empty() {
+        return Vec::new();
+    }
+    let strips = strips.max(1);
+    let (mut min_x, mut max_x) = (input_set[0].x, input_set[0].x);
+    for point in input_set {
+        if point.x < min_x {
+            min_x = point.x;
+        }
+        if point.x > max_x {
+            max_x = point.x;
+        }
+    }
+    //the strip is only rounded to f64, the points kept are exact
+    let strip = |x: T| {
+        if max_x > min_x {
+            (((x - min_x).to_f64() / (max_x - min_x).to_f64() * strips as f64) as usize).min(strips - 1)
+        } else {
+            0
+        }
+    };
+
+    let mut lowest: Vec<Option<Point2D<T>>> = vec![None; strips];
+    let mut highest: Vec<Option<Point2D<T>>> = vec![None; strips];
+    let (mut left_lowest, mut left_highest) = (None, None);
+    let (mut right_lowest, mut right_highest) = (None, None);
+    let below = |point: &Point2D<T>, other: Option<Point2D<T>>| other.is_none_or(|other| point.y < other.y);
+    let above = |point: &Point2D<T>, other: Option<Point2D<T>>| other.is_none_or(|other| point.y > other.y);
+    for point in input_set {
+        let index = strip(point.x);
+        if below(point, lowest[index]) {
+            lowest[index] = Some(*point);
+        }
+        if above(point, highest[index]) {
+            highest[index] = Some(*point);
+        }
+        if point.x == min_x {
+            if below(point, left_lowest) {
+                left_lowest = Some(*point);
+            }
+            if above(point, left_highest) {
+                left_highest = Some(*point);
+            }
+        }
+        if point.x == max_x {
+            if below(point, right_lowest) {
+                right_lowest = Some(*point);
+            }
+            if above(point, right_highest) {
+                right_highest = Some(*point);
+            }
+        }
+    }
+    let kept: Vec<Point2D<T>> = lowest
+        .into_iter()
+        .chain(highest)
+        .chain(vec![left_lowest, left_highest, right_lowest, right_highest])
+        .flatten()
+        .collect();
+    convex_hull_exact(&kept)
+}
+
+/// Input sets smaller than this are given to QuickHull right away
+const AUTO_SMALL: usize = 64;
+
+/// Solves the convexhull problem with the algorithm that is expected
+/// to be the fastest on the input set
+///
+/// QuickHull is the fastest of the algorithms here on the benchmark
+/// inputs from about 10^3 points on. Jarvis march only beats it for
+/// hulls of a few vertices, and dropping the points inside the
+/// Akl–Toussaint quadrilateral before QuickHull beats both there.
+/// That pass also pays off for disks and squares, but it is a loss
+/// once less than about half of the points are dropped, as on a ring,
+/// so it is only made when a sample of 256 points shows that at least
+/// half of them would be.
+///
+/// The hull is in counter clockwise order, starting at the leftmost
+/// point (the lowest one on a tie), like quick_hull's.
+pub fn convex_hull_auto<T: CoordNum>(input_set: &[Point2D<T>]) -> Vec<Point2D<T>> {
+    if input_set.len() < AUTO_SMALL {
+        return quick_hull(input_set);
+    }
+    let corners = quadrilateral(input_set);
+    let step = (input_set.len() / 256).max(1);
+    let (sampled, dropped) = input_set
+        .iter()
+        .step_by(step)
+        .fold((0, 0), |(sampled, dropped), point| {
+            (sampled + 1, dropped + inside(&corners, point) as usize)
+        });
+    if 2 * dropped >= sampled {
+        quick_hull(&prune_akl_toussaint(input_set))
+    } else {
+        quick_hull(input_set)
+    }
 }
 
 /// Prepares the input set for executing the algorithm.
 /// 
 /// Finds the first hull element and sorts the rest 
 /// of the elements based on angle formed with the first
-/// hull element
+/// hull element. With the `robust` feature, and for integer
+/// coordinates, the angles are compared with exact turns, as in
+/// `sort_input_angle_free`.
 /// 
 /// # Panics
-/// Panics when size of input is not atleast 3
+/// Panics when size of input is not atleast 3. Debug builds
+/// also panic when a coordinate is NaN or infinite, see `validate`.
 /// 
-pub fn sort_input(input_set: &mut Vec<Point2D>) -> Vec<Point2D> {
+pub fn sort_input<T: CoordNum>(input_set: &mut Vec<Point2D<T>>) -> Vec<Point2D<T>> {
     //panic when input_set has less than or equalto 2 elements
     assert!(input_set.len() > 2);
+    if cfg!(debug_assertions) {
+        if let Err(invalid_point) = validate(input_set) {
+            panic!("{}", invalid_point);
+        }
+    }
 
     //find the pivot point in the input set with the
     //minimum y-coordinate, or the leftmost such point
@@ -179,14 +1293,625 @@ pub fn sort_input(input_set: &mut Vec<Point2D>) -> Vec<Point2D> {
     //angle in counter clockwise order around pivot point.
     //(if more than one point has the same angle, remove all
     //but the one that is farthest from pivot point)
-    sort_polar_angle_ccw(input_set)
+    //exact turns only help graham_scan with an exact order
+    if cfg!(feature = "robust") || T::EXACT_TURNS {
+        sort_polar_angle_cross(input_set)
+    } else {
+        sort_polar_angle_ccw(input_set)
+    }
+}
+
+/// Prepares the input set like sort_input, sorting by cross products
+/// with `sort_polar_angle_cross` instead of by computed angles
+///
+/// # Panics
+/// Panics when size of input is not atleast 3. Debug builds
+/// also panic when a coordinate is NaN or infinite, see `validate`.
+pub fn sort_input_angle_free<T: CoordNum>(input_set: &mut Vec<Point2D<T>>) -> Vec<Point2D<T>> {
+    assert!(input_set.len() > 2);
+    if cfg!(debug_assertions) {
+        if let Err(invalid_point) = validate(input_set) {
+            panic!("{}", invalid_point);
+        }
+    }
+    set_pivot(input_set);
+    sort_polar_angle_cross(input_set)
+}
+
+/// Prepares the input set for the algorithm
+///
+/// Only the Graham scans need the polar angle order, Algorithm::Graham
+/// gets it from sort_input and Algorithm::GrahamAngleFree from
+/// sort_input_angle_free. Every other algorithm takes a copy of the
+/// input set in the order it is in.
+pub fn prepare<T: CoordNum>(algorithm: Algorithm, input_set: &mut Vec<Point2D<T>>) -> Vec<Point2D<T>> {
+    match algorithm {
+        Algorithm::Graham => sort_input(input_set),
+        Algorithm::GrahamAngleFree => sort_input_angle_free(input_set),
+        _ => input_set.clone(),
+    }
+}
+
+/// Same as sort_input, with all the buffers allocated by the given allocator
+///
+/// Requires the `allocator_api` feature, which needs a nightly compiler.
+///
+/// # Panics
+/// Panics when size of input is not atleast 3
+#[cfg(feature = "allocator_api")]
+pub fn sort_input_in<T: CoordNum, A: Allocator + Clone>(input_set: &mut Vec<Point2D<T>>, alloc: A) -> Vec<Point2D<T>, A> {
+    assert!(input_set.len() > 2);
+    set_pivot(input_set);
+    if cfg!(feature = "robust") || T::EXACT_TURNS {
+        sort_polar_angle_cross_in(input_set, alloc)
+    } else {
+        sort_polar_angle_ccw_in(input_set, alloc)
+    }
+}
+
+/// Graham-Scan with the candidate stack allocated by the given allocator,
+/// e.g. a bump allocator that is reset after every hull
+///
+/// Requires the `allocator_api` feature, which needs a nightly compiler.
+#[cfg(feature = "allocator_api")]
+pub fn graham_scan_in<T: CoordNum, A: Allocator>(input_set: &[Point2D<T>], alloc: A) -> Vec<Point2D<T>, A> {
+    let mut hull_points = Vec::new_in(alloc);
+    graham_scan_into(input_set, &mut hull_points);
+    hull_points
+}
+
+/// Finds the positions of the hull vertices in the input set
+///
+/// The indices are in counter clockwise order, starting at the
+/// vertex with the least x-coordinate (least y-coordinate on a tie).
+/// Points in the middle of a hull edge are not vertices.
+///
+/// This uses Andrew's monotone chain algorithm, which sorts the
+/// points by x-coordinate instead of by polar angle.
+/// A.M. Andrew, "Another efficient algorithm for convex hulls in
+/// two dimensions", Information Processing Letters, 1979
+///
+/// # Panics
+/// Panics when a coordinate is NaN
+pub fn hull_indices<P: HasXY>(input_set: &[P]) -> Vec<usize> {
+    let points: Vec<Point2D> = input_set.iter().map(|item| item.xy()).collect();
+    let mut sorted: Vec<usize> = (0..points.len()).collect();
+    sorted.sort_by(|&a, &b| {
+        points[a]
+            .x
+            .partial_cmp(&points[b].x)
+            .unwrap()
+            .then(points[a].y.partial_cmp(&points[b].y).unwrap())
+    });
+    sorted.dedup_by(|a, b| points[*a] == points[*b]);
+    monotone_chain(&points, sorted)
+}
+
+/// The hull indices of points whose indices are sorted by x-coordinate
+/// and then y-coordinate, without repeated points, in O(n)
+fn monotone_chain(points: &[Point2D], sorted: Vec<usize>) -> Vec<usize> {
+    monotone_chain_by(sorted.len(), |a, b, c| points[sorted[a]].ccw(&points[sorted[b]], &points[sorted[c]]))
+        .into_iter()
+        .map(|position| sorted[position])
+        .collect()
+}
+
+/// Andrew's monotone chain over n points that are sorted by
+/// x-coordinate and then y-coordinate, without repeated points, in O(n)
+///
+/// The points are only seen through `counter_clockwise(a, b, c)`, which
+/// tells whether the path over the points at positions a, b and c turns
+/// counter clockwise, so the turns can be taken in any arithmetic. The
+/// positions of the hull vertices come back in the order of
+/// `hull_indices`.
+pub fn monotone_chain_by<F: FnMut(usize, usize, usize) -> bool>(n: usize, mut counter_clockwise: F) -> Vec<usize> {
+    if n < 3 {
+        return (0..n).collect();
+    }
+
+    //lower chain from left to right and then upper chain from right to left,
+    //popping every point that does not make a counter clockwise turn
+    let mut hull: Vec<usize> = Vec::new();
+    for pass in 0..2 {
+        let start = hull.len();
+        for i in 0..n {
+            let point = if pass == 0 { i } else { n - 1 - i };
+            while hull.len() >= start + 2 && !counter_clockwise(hull[hull.len() - 2], hull[hull.len() - 1], point) {
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        // the last point of a chain is the first point of the next one
+        hull.pop();
+    }
+    hull
+}
+
+/// Hull of an input set that is already sorted by x-coordinate and
+/// then by y-coordinate, in O(n) time
+///
+/// This skips the sort of convex_hull_of and returns the same hull.
+/// The order is checked in a linear pass, and input sets out of order
+/// are sorted like in convex_hull_of after all.
+///
+/// # Panics
+/// Panics when a coordinate is NaN
+pub fn convex_hull_of_sorted<P: HasXY + Clone>(input_set: &[P]) -> Vec<P> {
+    let points: Vec<Point2D> = input_set.iter().map(|item| item.xy()).collect();
+    let in_order = points
+        .windows(2)
+        .all(|pair| pair[0].x.partial_cmp(&pair[1].x).unwrap().then(pair[0].y.partial_cmp(&pair[1].y).unwrap()).is_le());
+    if !in_order {
+        return convex_hull_of(input_set);
+    }
+    let mut sorted: Vec<usize> = (0..points.len()).collect();
+    sorted.dedup_by(|a, b| points[*a] == points[*b]);
+    monotone_chain(&points, sorted)
+        .into_iter()
+        .map(|index| input_set[index].clone())
+        .collect()
+}
+
+/// Hull of the union of two hulls, like the hulls of neighbouring
+/// tiles, in O(|a| + |b|) time
+///
+/// The two chains of every hull between its leftmost and rightmost
+/// vertex are already sorted by x-coordinate, so the vertices of both
+/// hulls are sorted by merging them. The monotone chain of
+/// convex_hull_of_sorted then finds the tangents bridging the hulls,
+/// whether they overlap or not. The hulls can be in either
+/// orientation, and the hull is the one convex_hull_of returns for all
+/// their vertices.
+///
+/// # Panics
+/// Panics when a coordinate is NaN
+pub fn merge_hulls(a: &[Point2D], b: &[Point2D]) -> Vec<Point2D> {
+    convex_hull_of_sorted(&merge_sorted(&sorted_vertices(a), &sorted_vertices(b)))
+}
+
+/// The vertices of a convex polygon sorted by x-coordinate and then
+/// y-coordinate, by merging its chains from the first to the last one
+fn sorted_vertices(hull: &[Point2D]) -> Vec<Point2D> {
+    let n = hull.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let order = |&i: &usize, &j: &usize| hull[i].x.partial_cmp(&hull[j].x).unwrap().then(hull[i].y.partial_cmp(&hull[j].y).unwrap());
+    let first = (0..n).min_by(order).unwrap();
+    let last = (0..n).max_by(order).unwrap();
+    let forward: Vec<Point2D> = (0..=(last + n - first) % n).map(|k| hull[(first + k) % n]).collect();
+    let backward: Vec<Point2D> = (0..=(first + n - last) % n).map(|k| hull[(first + n - k) % n]).collect();
+    merge_sorted(&forward, &backward)
+}
+
+/// Merges two point sets sorted by x-coordinate and then y-coordinate
+fn merge_sorted(a: &[Point2D], b: &[Point2D]) -> Vec<Point2D> {
+    let mut merged: Vec<Point2D> = Vec::with_capacity(a.len() + b.len());
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if (b[j].x, b[j].y) < (a[i].x, a[i].y) {
+            merged.push(b[j]);
+            j += 1;
+        } else {
+            merged.push(a[i]);
+            i += 1;
+        }
+    }
+    merged.extend_from_slice(&a[i..]);
+    merged.extend_from_slice(&b[j..]);
+    merged
+}
+
+/// Hull of items carrying a payload, like `(Point2D, T)` pairs
+///
+/// The hull vertices come back with their payload attached, in the
+/// order of `hull_indices`. Every other algorithm takes such items
+/// through `execute_on`.
+pub fn convex_hull_of<P: HasXY + Clone>(input_set: &[P]) -> Vec<P> {
+    hull_indices(input_set)
+        .into_iter()
+        .map(|index| input_set[index].clone())
+        .collect()
+}
+
+/// Whether every point of the input set is a hull vertex
+///
+/// Copies of a vertex count as that vertex, but points on a hull edge
+/// between its ends don't. Takes O(n log n) time.
+///
+/// # Panics
+/// Panics when a coordinate is NaN
+pub fn in_convex_position<P: HasXY>(input_set: &[P]) -> bool {
+    let mut points: Vec<Point2D> = input_set.iter().map(|item| item.xy()).collect();
+    points.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap()));
+    points.dedup();
+    let n = points.len();
+    monotone_chain(&points, (0..n).collect()).len() == n
+}
+
+///Hull of points with coordinates of any CoordNum, in the order of
+/// convex_hull_of, with the turns taken by `CoordNum::orientation`
+///
+/// The points are sorted and joined into a monotone chain without
+/// converting them to f64, so for integer coordinates every turn is
+/// exact and the hull is correct even for points a hair off a line,
+/// which f64 sees on it.
+///
+/// # Panics
+/// Panics when a coordinate is NaN
+pub fn convex_hull_exact<T: CoordNum>(input_set: &[Point2D<T>]) -> Vec<Point2D<T>> {
+    let mut points = input_set.to_vec();
+    points.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap()));
+    points.dedup();
+    monotone_chain_by(points.len(), |a, b, c| {
+        T::orientation(&points[a], &points[b], &points[c]) == Orientation::CounterClockwise
+    })
+    .into_iter()
+    .map(|position| points[position])
+    .collect()
+}
+
+/// Peels the hulls off the input set one after the other (onion peeling)
+///
+/// The first layer is the hull of the input set, the second the hull
+/// of the remaining points and so on, each in the order of
+/// `hull_indices`, until no point is left. Points on a hull edge but
+/// not at a vertex, and extra copies of a vertex, belong to inner
+/// layers. With k layers this takes O(k n log n) time.
+pub fn convex_layers<P: HasXY + Clone>(input_set: &[P]) -> Vec<Vec<P>> {
+    let mut layers: Vec<Vec<P>> = Vec::new();
+    let mut remaining = input_set.to_vec();
+    while !remaining.is_empty() {
+        let mask = hull_mask(&remaining);
+        layers.push(convex_hull_of(&remaining));
+        remaining = remaining
+            .into_iter()
+            .zip(mask)
+            .filter(|&(_, on_hull)| !on_hull)
+            .map(|(point, _)| point)
+            .collect();
+    }
+    layers
+}
+
+/// The peeling depth of a point in the input set: the number of
+/// convex_layers whose hull holds it, inside or on the boundary
+///
+/// Points outside the hull have depth 0, and every point of the input
+/// set the number of its own layer, counting from 1; the deepest are a
+/// robust centre of the input set. This is the onion depth, which is
+/// not the same as the Tukey (halfspace) depth. See peeling_depths for
+/// many points.
+///
+/// # Panics
+/// Panics when a coordinate is NaN
+pub fn peeling_depth<P: HasXY + Clone>(input_set: &[P], point: &Point2D) -> usize {
+    peeling_depths(input_set, &[*point])[0]
+}
+
+/// The peeling depth of every one of the points in the input set
+///
+/// The layers are peeled once, in O(k n log n) for k layers, and every
+/// layer is prepared as a PointLocator. The layers nest, so each point
+/// takes a binary search over them in O(log k log n).
+///
+/// # Panics
+/// Panics when a coordinate is NaN
+pub fn peeling_depths<P: HasXY + Clone>(input_set: &[P], points: &[Point2D]) -> Vec<usize> {
+    let locators: Vec<PointLocator> = convex_layers(input_set)
+        .iter()
+        .map(|layer| PointLocator::new(&layer.iter().map(|item| item.xy()).collect::<Vec<Point2D>>()))
+        .collect();
+    points
+        .iter()
+        .map(|point| {
+            //the number of layers holding the point, inner layers first out
+            let (mut low, mut high) = (0, locators.len());
+            while low < high {
+                let mid = high - (high - low) / 2;
+                if locators[mid - 1].contains(point) {
+                    low = mid;
+                } else {
+                    high = mid - 1;
+                }
+            }
+            low
+        })
+        .collect()
+}
+
+/// Finds the hull of a simple polygon or polyline in linear time
+///
+/// The vertices must be given in their order along the polygon,
+/// which must not cross itself. The hull is in the order of
+/// `hull_indices`. A closing vertex equal to the first one is fine.
+///
+/// This uses Melkman's algorithm, which keeps the hull in a deque
+/// whose both ends are the last added vertex.
+/// A. Melkman, "On-line construction of the convex hull of a simple
+/// polyline", Information Processing Letters, 1987
+pub fn melkman(polyline: &[Point2D]) -> Vec<Point2D> {
+    let mut vertices: Vec<Point2D> = polyline.to_vec();
+    vertices.dedup();
+    // the vertices before the first turn lie on one line
+    let turn = match (2..vertices.len()).find(|&i| orietation(&vertices[0], &vertices[1], &vertices[i]) != 0.0) {
+        Some(turn) => turn,
+        None => return convex_hull_of(&vertices),
+    };
+    let (first, last) = (vertices[0], vertices[turn - 1]);
+    let mut deque: VecDeque<Point2D> = VecDeque::new();
+    if first.ccw(&last, &vertices[turn]) {
+        deque.extend(&[vertices[turn], first, last, vertices[turn]]);
+    } else {
+        deque.extend(&[vertices[turn], last, first, vertices[turn]]);
+    }
+
+    for vertex in &vertices[turn + 1..] {
+        let len = deque.len();
+        //a vertex inside the current hull leaves it unchanged
+        if deque[0].ccw(&deque[1], vertex) && deque[len - 2].ccw(&deque[len - 1], vertex) {
+            continue;
+        }
+        while deque.len() > 2 && !deque[deque.len() - 2].ccw(&deque[deque.len() - 1], vertex) {
+            deque.pop_back();
+        }
+        deque.push_back(*vertex);
+        while deque.len() > 2 && !vertex.ccw(&deque[0], &deque[1]) {
+            deque.pop_front();
+        }
+        deque.push_front(*vertex);
+    }
+
+    // both ends hold the last added vertex
+    deque.pop_back();
+    // the vertices before the first turn were never at an end, so they
+    // can still be in the middle of an edge
+    let len = deque.len();
+    let mut hull_points: Vec<Point2D> = (0..len)
+        .filter(|&i| deque[(i + len - 1) % len].ccw(&deque[i], &deque[(i + 1) % len]))
+        .map(|i| deque[i])
+        .collect();
+    let start = hull_indices(&hull_points)[0];
+    hull_points.rotate_left(start);
+    hull_points
+}
+
+/// Marks which points of the input set are hull vertices
+///
+/// The mask has one entry per input point, which makes it easy to
+/// join with other per-point data. When a vertex appears several
+/// times in the input set only one of its copies is marked.
+///
+/// # Panics
+/// Panics when a coordinate is NaN
+pub fn hull_mask<P: HasXY>(input_set: &[P]) -> Vec<bool> {
+    let mut mask = vec![false; input_set.len()];
+    for index in hull_indices(input_set) {
+        mask[index] = true;
+    }
+    mask
+}
+
+/// Groups the points by a key, like a cluster id or a category, and
+/// computes the hull of every group
+///
+/// The groups are hulled in parallel on all the available cores.
+/// The hull vertices are in the order of `hull_indices`.
+///
+/// # Panics
+/// Panics when a coordinate is NaN
+pub fn convex_hulls_by_key<P, K, F>(input_set: &[P], key_fn: F) -> HashMap<K, Vec<P>>
+where
+    P: HasXY + Clone + Send,
+    K: Hash + Eq + Send,
+    F: Fn(&P) -> K,
+{
+    let mut groups: HashMap<K, Vec<P>> = HashMap::new();
+    for item in input_set {
+        groups.entry(key_fn(item)).or_default().push(item.clone());
+    }
+
+    //deal the groups out to one batch per core
+    let threads = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    let mut batches: Vec<Vec<(K, Vec<P>)>> = (0..threads).map(|_| Vec::new()).collect();
+    for (i, group) in groups.into_iter().enumerate() {
+        batches[i % threads].push(group);
+    }
+    thread::scope(|scope| {
+        let handles: Vec<_> = batches
+            .into_iter()
+            .map(|batch| {
+                scope.spawn(move || {
+                    batch
+                        .into_iter()
+                        .map(|(key, group)| (key, convex_hull_of(&group)))
+                        .collect::<Vec<(K, Vec<P>)>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
+
+/// Groups hulled one after the other share a buffer, with the `parallel`
+/// feature this many at a time
+#[cfg(feature = "parallel")]
+const GROUPED_BATCH: usize = 256;
+
+/// Computes the hull of every group, for many small input sets like
+/// the features of a map
+///
+/// The hulls are in the order of the groups, their vertices in the
+/// order of `hull_indices`. The groups are sorted in one buffer that
+/// is reused for all of them, so the hulls and the positions of their
+/// vertices are the only allocations. With the `parallel` feature batches of groups are
+/// hulled on the threads of the rayon pool, each with its own buffer.
+///
+/// # Panics
+/// Panics when a coordinate is NaN
+pub fn convex_hulls_grouped(groups: &[Vec<Point2D>]) -> Vec<Vec<Point2D>> {
+    #[cfg(feature = "parallel")]
+    {
+        use self::rayon::prelude::*;
+        let batches: Vec<Vec<Vec<Point2D>>> = groups.par_chunks(GROUPED_BATCH).map(hull_batch).collect();
+        batches.into_iter().flatten().collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    hull_batch(groups)
+}
+
+/// The hulls of the groups, found with monotone_chain_by in a shared buffer
+fn hull_batch(groups: &[Vec<Point2D>]) -> Vec<Vec<Point2D>> {
+    let mut sorted: Vec<Point2D> = Vec::new();
+    groups
+        .iter()
+        .map(|group| {
+            sorted.clear();
+            sorted.extend_from_slice(group);
+            sorted.sort_unstable_by(|a, b| a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap()));
+            sorted.dedup();
+            monotone_chain_by(sorted.len(), |a, b, c| sorted[a].ccw(&sorted[b], &sorted[c]))
+                .into_iter()
+                .map(|index| sorted[index])
+                .collect()
+        })
+        .collect()
+}
+
+///The points of an input set split by where they lie relative to its hull
+///
+/// Every index of the input set is in exactly one of the lists.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Partition {
+    /// hull vertices in counter clockwise order, as from `hull_indices`
+    pub hull: Vec<usize>,
+    /// points on the hull boundary that are not vertices,
+    /// like points in the middle of an edge or duplicates of a vertex
+    pub boundary: Vec<usize>,
+    /// points strictly inside the hull
+    pub interior: Vec<usize>,
+}
+
+/// Splits the input set into hull vertices, other boundary points and
+/// strictly interior points, for pipelines that process the leftover
+/// points in a next stage
+///
+/// # Panics
+/// Panics when a coordinate is NaN
+pub fn partition_hull<P: HasXY>(input_set: &[P]) -> Partition {
+    let hull = hull_indices(input_set);
+    let mut is_vertex = vec![false; input_set.len()];
+    for &index in &hull {
+        is_vertex[index] = true;
+    }
+    let hull_points: Vec<Point2D> = hull.iter().map(|&index| input_set[index].xy()).collect();
+    let mut boundary: Vec<usize> = Vec::new();
+    let mut interior: Vec<usize> = Vec::new();
+    for index in 0..input_set.len() {
+        if is_vertex[index] {
+            continue;
+        }
+        if strictly_inside(&hull_points, &input_set[index].xy()) {
+            interior.push(index);
+        } else {
+            boundary.push(index);
+        }
+    }
+    Partition {
+        hull,
+        boundary,
+        interior,
+    }
+}
+
+/// True when the point is inside a counter clockwise convex polygon
+/// and not on its boundary. Binary searches the wedge of the fan
+/// around the first vertex containing the point, in O(log h).
+fn strictly_inside(hull_points: &[Point2D], point: &Point2D) -> bool {
+    let len = hull_points.len();
+    if len < 3 {
+        return false;
+    }
+    let first = &hull_points[0];
+    if orietation(first, &hull_points[1], point) <= 0.0
+        || orietation(first, &hull_points[len - 1], point) >= 0.0
+    {
+        return false;
+    }
+    let mut low = 1;
+    let mut high = len - 1;
+    while high - low > 1 {
+        let mid = (low + high) / 2;
+        if orietation(first, &hull_points[mid], point) >= 0.0 {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    orietation(&hull_points[low], &hull_points[high], point) > 0.0
+}
+
+///Runs an algorithm for f64 points on the positions of the items, and
+/// returns the items its hull vertices are the positions of
+///
+/// Of items at the same position, like distinct i64 points beyond 2^53
+/// that f64 rounds together, the first one is returned.
+pub fn with_f64_positions<P, F>(input_set: &[P], algorithm: F) -> Vec<P>
+where
+    P: HasXY + Clone,
+    F: FnOnce(&[Point2D]) -> Vec<Point2D>,
+{
+    let positions: Vec<Point2D> = input_set.iter().map(|item| item.xy()).collect();
+    let key = |position: &Point2D| (CoordNum::to_bits(position.x), CoordNum::to_bits(position.y));
+    let mut first: HashMap<(u64, u64), usize> = HashMap::new();
+    for (index, position) in positions.iter().enumerate() {
+        first.entry(key(position)).or_insert(index);
+    }
+    algorithm(&positions).iter().map(|vertex| input_set[first[&key(vertex)]].clone()).collect()
+}
+
+///Executes an algorithm on items with a position, like points with a
+/// payload, and returns the items at the hull vertices
+///
+/// The positions are prepared for the algorithm with `prepare`, and
+/// the hull is mapped back to the items by `with_f64_positions`, so
+/// every algorithm returns the items in the order it returns points.
+///
+/// # Panics
+/// Panics when prepare does, when size of input is not atleast 3
+pub fn execute_on<P: HasXY + Clone>(algorithm: Algorithm, input_set: &[P]) -> Vec<P> {
+    with_f64_positions(input_set, |positions| {
+        let mut prepared = prepare(algorithm, &mut positions.to_vec());
+        execute(algorithm, &mut prepared)
+    })
 }
 
 /// Executes an algorithm for given inputset of point and returns the hull points
-pub fn execute(algorithm: Algorithm, input_set: &mut Vec<Point2D>) -> Vec<Point2D> {
+///
+/// Every algorithm runs on the coordinate type of the points, except
+/// Delaunay and Concave, which take their positions as f64 and return
+/// the points these came from, see `with_f64_positions`.
+pub fn execute<T: CoordNum>(algorithm: Algorithm, input_set: &mut [Point2D<T>]) -> Vec<Point2D<T>> {
     match algorithm {
         Algorithm::Graham => graham_scan(input_set),
+        Algorithm::GrahamAngleFree => graham_scan(input_set),
         Algorithm::Jarvis => jarvis_march(input_set),
         Algorithm::Chan => chans_algorithm(input_set),
+        Algorithm::ChanWith(schedule) => chans_algorithm_with(input_set, schedule),
+        Algorithm::QuickHull => quick_hull(input_set),
+        Algorithm::KirkpatrickSeidel => kirkpatrick_seidel(input_set),
+        Algorithm::Incremental => incremental_hull(input_set),
+        #[cfg(feature = "generators")]
+        Algorithm::RandomizedIncremental => randomized_incremental_hull(input_set),
+        Algorithm::Approximate(strips) => approximate_hull(input_set, strips),
+        #[cfg(feature = "parallel")]
+        Algorithm::ParallelQuickHull => parallel_quick_hull(input_set),
+        Algorithm::DivideAndConquer(threads) => divide_and_conquer_hull(input_set, threads),
+        Algorithm::Auto => convex_hull_auto(input_set),
+        Algorithm::Delaunay => with_f64_positions(input_set, delaunay_hull),
+        Algorithm::Concave(k) => with_f64_positions(input_set, |positions| concave_hull(positions, k)),
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,265 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Delaunay triangulations
+//!
+//! The Delaunay triangulation joins the points into triangles with no
+//! point inside the circle through the corners of any of them. It is
+//! the dual of the Voronoi diagram, and the edges on its boundary are
+//! the edges of the convex hull, so the hull falls out of it as well.
+//! Triangles are index triples into the input set, like the faces of
+//! `hull3d`.
+use geometry::canonicalize;
+use convexhull::convex_hull_of;
+use points::*;
+use std::collections::{HashMap, HashSet};
+
+///A triangle of the triangulation, as indices of its corners in the
+/// input set, in counter clockwise order
+pub type Triangle = [usize; 3];
+
+/// The corner of the triangles outside of the hull, one for every hull
+/// edge, which stands for a point at infinity
+const INFINITE: usize = usize::MAX;
+
+#[derive(Debug, Clone)]
+struct Cell {
+    corners: [usize; 3],
+    // the triangle across the edge opposite every corner
+    across: [usize; 3],
+    // the point that last tested the triangle, and whether its circle
+    // held that point
+    tested: (usize, bool),
+}
+
+impl Cell {
+    fn new(corners: [usize; 3]) -> Cell {
+        Cell { corners, across: [INFINITE; 3], tested: (INFINITE, false) }
+    }
+
+    ///The edge opposite a corner, in counter clockwise order
+    fn edge(&self, corner: usize) -> (usize, usize) {
+        (self.corners[(corner + 1) % 3], self.corners[(corner + 2) % 3])
+    }
+}
+
+/// Positive when d is inside the circle through the counter clockwise
+/// triangle abc, zero on it and negative outside
+fn in_circle(a: &Point2D, b: &Point2D, c: &Point2D, d: &Point2D) -> f64 {
+    let (ax, ay) = (a.x - d.x, a.y - d.y);
+    let (bx, by) = (b.x - d.x, b.y - d.y);
+    let (cx, cy) = (c.x - d.x, c.y - d.y);
+    (ax * ax + ay * ay) * (bx * cy - cx * by) - (bx * bx + by * by) * (ax * cy - cx * ay)
+        + (cx * cx + cy * cy) * (ax * by - bx * ay)
+}
+
+/// The Delaunay triangulation built so far over the points
+struct Mesh<'a> {
+    points: &'a [Point2D],
+    cells: Vec<Cell>,
+    // where the walk to the next point starts
+    last: usize,
+}
+
+impl<'a> Mesh<'a> {
+    /// Whether the circle of the cell holds the point, where the circle
+    /// of a cell at infinity is the half-plane outside of its hull edge
+    fn holds(&self, cell: usize, point: usize) -> bool {
+        let corners = self.cells[cell].corners;
+        let p = &self.points[point];
+        match corners.iter().position(|&corner| corner == INFINITE) {
+            None => in_circle(&self.points[corners[0]], &self.points[corners[1]], &self.points[corners[2]], p) > 0.0,
+            Some(k) => {
+                let (a, b) = (&self.points[corners[(k + 1) % 3]], &self.points[corners[(k + 2) % 3]]);
+                let side = orietation(a, b, p);
+                //on the hull edge itself, which the new point splits
+                side > 0.0 || (side == 0.0 && (p.x - a.x) * (p.x - b.x) + (p.y - a.y) * (p.y - b.y) < 0.0)
+            }
+        }
+    }
+
+    /// The cell holding the point, or a cell at infinity when the point
+    /// is outside of the hull, walking from the last new cell
+    fn locate(&self, point: usize) -> usize {
+        let p = &self.points[point];
+        let mut cell = self.last;
+        'walk: loop {
+            let current = &self.cells[cell];
+            if current.corners.contains(&INFINITE) {
+                return cell;
+            }
+            for corner in 0..3 {
+                let (a, b) = current.edge(corner);
+                if orietation(&self.points[a], &self.points[b], p) < 0.0 {
+                    cell = current.across[corner];
+                    continue 'walk;
+                }
+            }
+            return cell;
+        }
+    }
+
+    /// Adds a point, replacing the cells whose circle holds it by fans
+    /// from the point to the boundary of their union
+    fn insert(&mut self, point: usize) {
+        let start = self.locate(point);
+        if !self.holds(start, point) {
+            return;
+        }
+        let mut cavity = vec![start];
+        self.cells[start].tested = (point, true);
+        let mut i = 0;
+        while i < cavity.len() {
+            let across = self.cells[cavity[i]].across;
+            for &other in &across {
+                if self.cells[other].tested.0 != point {
+                    let bad = self.holds(other, point);
+                    self.cells[other].tested = (point, bad);
+                    if bad {
+                        cavity.push(other);
+                    }
+                }
+            }
+            i += 1;
+        }
+        //the boundary edges with the cell outside of them
+        let mut fan: Vec<usize> = Vec::new();
+        for &cell in &cavity {
+            for corner in 0..3 {
+                let outside = self.cells[cell].across[corner];
+                if self.cells[outside].tested != (point, true) {
+                    let (a, b) = self.cells[cell].edge(corner);
+                    let mut new = Cell::new([a, b, point]);
+                    new.across[2] = outside;
+                    new.tested = (point, false);
+                    let back = (0..3).find(|&k| self.cells[outside].edge(k) == (b, a)).unwrap();
+                    self.cells[outside].across[back] = self.cells.len();
+                    fan.push(self.cells.len());
+                    self.cells.push(new);
+                }
+            }
+        }
+        //the new cells around the point share their edges to it
+        let first = self.cells.len() - fan.len();
+        for &cell in &fan {
+            let [a, b, _] = self.cells[cell].corners;
+            self.cells[cell].across[0] = first + (0..fan.len()).find(|&k| self.cells[first + k].corners[0] == b).unwrap();
+            self.cells[cell].across[1] = first + (0..fan.len()).find(|&k| self.cells[first + k].corners[1] == a).unwrap();
+        }
+        for &cell in &cavity {
+            self.cells[cell].corners = [INFINITE; 3];
+        }
+        self.last = *fan.iter().find(|&&cell| !self.cells[cell].corners.contains(&INFINITE)).unwrap_or(&fan[0]);
+    }
+}
+
+/// The points in rows of about the same y, back and forth along x, so
+/// that the walk to every next point is short
+fn insertion_order(points: &[Point2D]) -> Vec<usize> {
+    let (low, high) = bounding_box(points);
+    let rows = ((points.len() as f64 / 2.0).sqrt().ceil() as usize).max(1);
+    let height = (high.y - low.y).max(f64::MIN_POSITIVE);
+    let row = |point: &Point2D| (((point.y - low.y) / height * rows as f64) as usize).min(rows - 1);
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by(|&i, &j| {
+        let (a, b) = (&points[i], &points[j]);
+        row(a).cmp(&row(b)).then_with(|| {
+            let along = a.x.partial_cmp(&b.x).unwrap();
+            if row(a) % 2 == 0 { along } else { along.reverse() }
+        })
+    });
+    order
+}
+
+///The Delaunay triangulation of the input set
+///
+/// Bowyer–Watson: the points are added one at a time, and every new
+/// point removes the triangles whose circle holds it and joins the
+/// corners of the hole to itself. Triangles with a corner at infinity
+/// on the hull edges make points outside of the hull the same case,
+/// without a large triangle around the input set. The points are added
+/// in rows, so finding the triangle of the next point takes few steps,
+/// and the whole takes about O(n log n) time for evenly spread points.
+///
+/// Of copies of a point only the first is a corner. Input sets with all
+/// points on a line have no triangles.
+///
+/// A. Bowyer, "Computing Dirichlet tessellations", and D. F. Watson,
+/// "Computing the n-dimensional Delaunay tessellation with application
+/// to Voronoi polytopes", The Computer Journal, 1981
+///
+/// # Panics
+/// Panics when a coordinate is NaN
+pub fn delaunay_triangulation(input_set: &[Point2D]) -> Vec<Triangle> {
+    if input_set.len() < 3 {
+        return Vec::new();
+    }
+    let order = insertion_order(input_set);
+    //the first three points not on a line
+    let a = order[0];
+    let b = match order.iter().find(|&&i| input_set[i] != input_set[a]) {
+        Some(&b) => b,
+        None => return Vec::new(),
+    };
+    let c = match order.iter().find(|&&i| orietation(&input_set[a], &input_set[b], &input_set[i]) != 0.0) {
+        Some(&c) => c,
+        None => return Vec::new(),
+    };
+    let (b, c) = if orietation(&input_set[a], &input_set[b], &input_set[c]) > 0.0 { (b, c) } else { (c, b) };
+    let mut cells = vec![Cell::new([a, b, c]), Cell::new([b, a, INFINITE]), Cell::new([c, b, INFINITE]), Cell::new([a, c, INFINITE])];
+    cells[0].across = [2, 3, 1];
+    cells[1].across = [3, 2, 0];
+    cells[2].across = [1, 3, 0];
+    cells[3].across = [2, 1, 0];
+    let mut mesh = Mesh { points: input_set, cells, last: 0 };
+    for point in order {
+        if point != a && point != b && point != c {
+            mesh.insert(point);
+        }
+    }
+    mesh.cells
+        .into_iter()
+        .map(|cell| cell.corners)
+        .filter(|corners| !corners.contains(&INFINITE))
+        .collect()
+}
+
+///Solves the convexhull problem as the boundary of the Delaunay
+/// triangulation
+///
+/// The hull edges are the edges of only one triangle. This is slower
+/// than the other algorithms, for the comparison of finding the hull on
+/// its own with finding it on the way to the triangulation. Points on
+/// the hull edges are left out, and the hull is in the canonical form
+/// of `geometry::canonicalize`.
+///
+/// # Panics
+/// Panics when a coordinate is NaN
+pub fn delaunay_hull(input_set: &[Point2D]) -> Vec<Point2D> {
+    let triangles = delaunay_triangulation(input_set);
+    if triangles.is_empty() {
+        return convex_hull_of(input_set);
+    }
+    let edges: HashSet<(usize, usize)> = triangles
+        .iter()
+        .flat_map(|triangle| vec![(triangle[0], triangle[1]), (triangle[1], triangle[2]), (triangle[2], triangle[0])])
+        .collect();
+    //the edges whose reverse is no edge of a triangle
+    let boundary: HashMap<usize, usize> = edges.iter().filter(|&&(a, b)| !edges.contains(&(b, a))).cloned().collect();
+    let start = *boundary.keys().next().unwrap();
+    let mut ring = vec![input_set[start]];
+    let mut vertex = boundary[&start];
+    while vertex != start {
+        ring.push(input_set[vertex]);
+        vertex = boundary[&vertex];
+    }
+    let n = ring.len();
+    let mut hull: Vec<Point2D> = (0..n)
+        .filter(|&i| orietation(&ring[(i + n - 1) % n], &ring[i], &ring[(i + 1) % n]) != 0.0)
+        .map(|i| ring[i])
+        .collect();
+    canonicalize(&mut hull);
+    hull
+}
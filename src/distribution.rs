@@ -0,0 +1,174 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Composable point distributions for describing input sets
+//!
+//! A distribution is built from a few basic shapes and
+//! combinators, and then sampled to produce an input set.
+//!
+//! # Example
+//!
+//! ```
+//! use rustalgo::distribution::Dist;
+//! let input_set = Dist::disk(1.0)
+//!     .translate(2.0, 0.0)
+//!     .mix(Dist::gaussian(0.5), 0.3)
+//!     .take(1_000);
+//! assert_eq!(1_000, input_set.len());
+//! ```
+use points::Point2D;
+use std::f64;
+
+extern crate rand;
+use self::rand::distributions::Normal;
+use self::rand::prelude::*;
+
+///A distribution of points in the plane
+#[derive(Debug, Clone)]
+pub enum Dist {
+    /// uniform inside a disk centered at the origin
+    Disk(f64),
+    /// uniform inside a ring centered at the origin
+    Ring(f64, f64),
+    /// uniform inside an axis aligned rectangle centered at the origin
+    Rectangle(f64, f64),
+    /// normal with the same standard deviation on both axes
+    Gaussian(f64),
+    /// another distribution moved by an offset
+    Translate(Box<Dist>, f64, f64),
+    /// another distribution stretched along the axes
+    Scale(Box<Dist>, f64, f64),
+    /// one of two distributions, picking the second with the given weight
+    Mix(Box<Dist>, Box<Dist>, f64),
+    /// one of several distributions, picked in proportion to their weights
+    Mixture(Vec<(Dist, f64)>),
+}
+
+impl Dist {
+    ///Uniform distribution inside a disk of the given radius
+    pub fn disk(radius: f64) -> Dist {
+        Dist::Disk(radius)
+    }
+
+    ///Uniform distribution between two circles of the given radii
+    pub fn ring(inner_radius: f64, outer_radius: f64) -> Dist {
+        Dist::Ring(inner_radius, outer_radius)
+    }
+
+    ///Uniform distribution inside a rectangle of the given size
+    pub fn rectangle(width: f64, height: f64) -> Dist {
+        Dist::Rectangle(width, height)
+    }
+
+    ///Normal distribution around the origin
+    pub fn gaussian(std_dev: f64) -> Dist {
+        Dist::Gaussian(std_dev)
+    }
+
+    ///Moves every sampled point by (dx, dy)
+    pub fn translate(self, dx: f64, dy: f64) -> Dist {
+        Dist::Translate(Box::new(self), dx, dy)
+    }
+
+    ///Multiplies the coordinates of every sampled point by (sx, sy)
+    pub fn scale(self, sx: f64, sy: f64) -> Dist {
+        Dist::Scale(Box::new(self), sx, sy)
+    }
+
+    ///Samples from `other` with probability `weight` and
+    /// from this distribution otherwise
+    ///
+    /// # Panics
+    /// Panics if the weight is not within [0, 1]
+    pub fn mix(self, other: Dist, weight: f64) -> Dist {
+        assert!((0.0..=1.0).contains(&weight));
+        Dist::Mix(Box::new(self), Box::new(other), weight)
+    }
+
+    ///Samples from one of the components, picked in proportion
+    /// to its weight. The weights don't need to add up to one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustalgo::distribution::Dist;
+    /// // 90% gaussian blob and 10% uniform ring
+    /// let mixture = Dist::mixture(vec![
+    ///     (Dist::gaussian(0.5), 9.0),
+    ///     (Dist::ring(1.8, 2.0), 1.0),
+    /// ]);
+    /// assert_eq!(100, mixture.take(100).len());
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if there are no components, a weight is negative
+    /// or all the weights are zero
+    pub fn mixture(components: Vec<(Dist, f64)>) -> Dist {
+        assert!(components.iter().all(|&(_, weight)| weight >= 0.0));
+        assert!(components.iter().map(|&(_, weight)| weight).sum::<f64>() > 0.0);
+        Dist::Mixture(components)
+    }
+
+    ///Draws one point from the distribution
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> Point2D {
+        match *self {
+            Dist::Disk(radius) => Dist::Ring(0.0, radius).sample(rng),
+            Dist::Ring(inner_radius, outer_radius) => {
+                // the square root keeps the points uniform over the area
+                let theta = 2.0 * f64::consts::PI * rng.gen::<f64>();
+                let inner = inner_radius.powi(2);
+                let radius = (inner + (outer_radius.powi(2) - inner) * rng.gen::<f64>()).sqrt();
+                Point2D::new(radius * theta.cos(), radius * theta.sin())
+            }
+            Dist::Rectangle(width, height) => Point2D::new(
+                width * (rng.gen::<f64>() - 0.5),
+                height * (rng.gen::<f64>() - 0.5),
+            ),
+            Dist::Gaussian(std_dev) => {
+                let normal = Normal::new(0.0, std_dev);
+                Point2D::new(rng.sample(normal), rng.sample(normal))
+            }
+            Dist::Translate(ref dist, dx, dy) => {
+                let point = dist.sample(rng);
+                Point2D::new(point.x + dx, point.y + dy)
+            }
+            Dist::Scale(ref dist, sx, sy) => {
+                let point = dist.sample(rng);
+                Point2D::new(point.x * sx, point.y * sy)
+            }
+            Dist::Mix(ref first, ref second, weight) => {
+                if rng.gen::<f64>() < weight {
+                    second.sample(rng)
+                } else {
+                    first.sample(rng)
+                }
+            }
+            Dist::Mixture(ref components) => {
+                let total: f64 = components.iter().map(|&(_, weight)| weight).sum();
+                let mut pick = total * rng.gen::<f64>();
+                for &(ref dist, weight) in components {
+                    if pick < weight {
+                        return dist.sample(rng);
+                    }
+                    pick -= weight;
+                }
+                // rounding can leave pick just above the last weight
+                components
+                    .iter()
+                    .rev()
+                    .find(|&&(_, weight)| weight > 0.0)
+                    .unwrap()
+                    .0
+                    .sample(rng)
+            }
+        }
+    }
+
+    ///Generates an input set of n points from the distribution
+    pub fn take(&self, n: u64) -> Vec<Point2D> {
+        let mut rng = thread_rng();
+        (0..n).map(|_| self.sample(&mut rng)).collect()
+    }
+}
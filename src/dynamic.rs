@@ -0,0 +1,650 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Hulls of input sets that grow one point at a time
+//!
+//! Streamed points don't have to be collected and hulled again after
+//! every new point: a DynamicHull takes them as they come and its
+//! hull can be read at any time. Streams too long to keep even
+//! their hull are summarized approximately by a HullSketch.
+use convexhull::convex_hull_of;
+use points::*;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::ops::Bound::{Excluded, Unbounded};
+
+///The hull of the points inserted so far
+///
+/// The upper and the lower hull are kept in ordered maps from x to y,
+/// so an insertion takes O(log n) amortized time: finding whether the
+/// point is outside and where it goes is logarithmic, and every vertex
+/// it makes obsolete is removed once.
+///
+/// # Example
+///
+/// ```
+/// use rustalgo::dynamic::DynamicHull;
+/// use rustalgo::points::Point2D;
+/// let mut hull = DynamicHull::new();
+/// hull.insert(Point2D::new(0.0, 0.0));
+/// hull.insert(Point2D::new(4.0, 0.0));
+/// hull.insert(Point2D::new(0.0, 4.0));
+/// assert!(!hull.insert(Point2D::new(1.0, 1.0)));
+/// assert_eq!(3, hull.hull().len());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DynamicHull {
+    upper: Chain,
+    // the upper hull of the points mirrored at the x axis
+    lower: Chain,
+}
+
+impl DynamicHull {
+    ///An empty hull
+    pub fn new() -> DynamicHull {
+        DynamicHull::default()
+    }
+
+    ///Adds a point and returns whether it changed the hull
+    ///
+    /// Points inside the hull or on its boundary change nothing.
+    ///
+    /// # Panics
+    /// Panics when a coordinate is NaN
+    pub fn insert(&mut self, point: Point2D) -> bool {
+        let upper = self.upper.insert(point.x, point.y);
+        let lower = self.lower.insert(point.x, -point.y);
+        upper || lower
+    }
+
+    ///Whether the point is inside the hull or on its boundary
+    pub fn contains(&self, point: &Point2D) -> bool {
+        self.upper.covers(point.x, point.y) && self.lower.covers(point.x, -point.y)
+    }
+
+    ///Whether no point was inserted
+    pub fn is_empty(&self) -> bool {
+        self.upper.vertices.is_empty()
+    }
+
+    ///The current hull in counter clockwise order, starting at the
+    /// leftmost point (the lowest one on a tie)
+    pub fn hull(&self) -> Vec<Point2D> {
+        let mut hull_points: Vec<Point2D> = self
+            .lower
+            .vertices
+            .iter()
+            .map(|(x, y)| Point2D::new(x.0, -y))
+            .collect();
+        let mut upper: Vec<Point2D> = self
+            .upper
+            .vertices
+            .iter()
+            .rev()
+            .map(|(x, y)| Point2D::new(x.0, *y))
+            .collect();
+        if upper.first() == hull_points.last() {
+            upper.remove(0);
+        }
+        if upper.last() == hull_points.first() {
+            upper.pop();
+        }
+        hull_points.extend(upper);
+        hull_points
+    }
+}
+
+impl Extend<Point2D> for DynamicHull {
+    fn extend<I: IntoIterator<Item = Point2D>>(&mut self, points: I) {
+        for point in points {
+            self.insert(point);
+        }
+    }
+}
+
+impl FromIterator<Point2D> for DynamicHull {
+    fn from_iter<I: IntoIterator<Item = Point2D>>(points: I) -> DynamicHull {
+        let mut hull = DynamicHull::new();
+        hull.extend(points);
+        hull
+    }
+}
+
+/// An x-coordinate ordering the vertices of a chain
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Key(f64);
+
+impl Eq for Key {}
+
+impl Ord for Key {
+    fn cmp(&self, other: &Key) -> Ordering {
+        self.0.partial_cmp(&other.0).expect("NaN coordinate")
+    }
+}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Key) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The upper hull of some points, a map from x to y of its vertices
+#[derive(Debug, Clone, Default)]
+struct Chain {
+    vertices: BTreeMap<Key, f64>,
+}
+
+impl Chain {
+    /// Whether the point is on or below the chain
+    fn covers(&self, x: f64, y: f64) -> bool {
+        if let Some(&height) = self.vertices.get(&Key(x)) {
+            return y <= height;
+        }
+        let left = self.vertices.range(..Key(x)).next_back();
+        let right = self.vertices.range((Excluded(Key(x)), Unbounded)).next();
+        match (left, right) {
+            (Some((left_x, left_y)), Some((right_x, right_y))) => {
+                let (left, right) = (Point2D::new(left_x.0, *left_y), Point2D::new(right_x.0, *right_y));
+                orietation(&left, &right, &Point2D::new(x, y)) <= 0.0
+            }
+            _ => false,
+        }
+    }
+
+    /// Adds the point unless it is covered and returns whether it was added
+    fn insert(&mut self, x: f64, y: f64) -> bool {
+        if self.covers(x, y) {
+            return false;
+        }
+        self.vertices.insert(Key(x), y);
+        let point = Point2D::new(x, y);
+        // drop the vertices on both sides that no longer make a right turn
+        loop {
+            let obsolete = {
+                let mut right = self.vertices.range((Excluded(Key(x)), Unbounded));
+                match (right.next(), right.next()) {
+                    (Some((a_x, a_y)), Some((b_x, b_y))) => {
+                        let (a, b) = (Point2D::new(a_x.0, *a_y), Point2D::new(b_x.0, *b_y));
+                        if orietation(&point, &a, &b) >= 0.0 { Some(*a_x) } else { None }
+                    }
+                    _ => None,
+                }
+            };
+            match obsolete {
+                Some(key) => self.vertices.remove(&key),
+                None => break,
+            };
+        }
+        loop {
+            let obsolete = {
+                let mut left = self.vertices.range(..Key(x)).rev();
+                match (left.next(), left.next()) {
+                    (Some((a_x, a_y)), Some((b_x, b_y))) => {
+                        let (a, b) = (Point2D::new(a_x.0, *a_y), Point2D::new(b_x.0, *b_y));
+                        if orietation(&b, &a, &point) >= 0.0 { Some(*a_x) } else { None }
+                    }
+                    _ => None,
+                }
+            };
+            match obsolete {
+                Some(key) => self.vertices.remove(&key),
+                None => break,
+            };
+        }
+        true
+    }
+}
+
+///The hull of a set of points that grows and shrinks
+///
+/// The points are the leaves of a balanced search tree ordered by x
+/// (and y on ties). Every inner node keeps the bridges of its subtree,
+/// the edges of its upper and lower hull that join the hulls of its
+/// two children, as in the hull trees of Overmars and van Leeuwen.
+/// A bridge is found in O(log n) time by walking down both children
+/// at once, so inserting or removing a point takes O(log² n) amortized
+/// time: the bridges on the path to its leaf are found again, and
+/// subtrees that got out of balance are rebuilt. Reading the hull
+/// takes O(h log n) time for h vertices.
+///
+/// A point inserted several times has to be removed as many times.
+///
+/// M. Overmars and J. van Leeuwen, "Maintenance of configurations in
+/// the plane", Journal of Computer and System Sciences, 1981
+#[derive(Debug, Clone, Default)]
+pub struct FullyDynamicHull {
+    root: Option<Node>,
+    len: usize,
+}
+
+impl FullyDynamicHull {
+    ///An empty hull
+    pub fn new() -> FullyDynamicHull {
+        FullyDynamicHull::default()
+    }
+
+    ///The number of points, counting repeated ones
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    ///Whether there are no points
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    ///Adds a point
+    ///
+    /// # Panics
+    /// Panics when a coordinate is NaN
+    pub fn insert(&mut self, point: Point2D) {
+        self.root = Some(match self.root.take() {
+            Some(root) => root.insert(point),
+            None => Node::Leaf(point, 1),
+        });
+        self.len += 1;
+    }
+
+    ///Removes one copy of a point and returns whether there was one
+    pub fn remove(&mut self, point: &Point2D) -> bool {
+        match self.root.take() {
+            Some(root) => {
+                let found = root.contains(point);
+                self.root = if found { root.remove(point) } else { Some(root) };
+                if found {
+                    self.len -= 1;
+                }
+                found
+            }
+            None => false,
+        }
+    }
+
+    ///The current hull in counter clockwise order, starting at the
+    /// leftmost point (the lowest one on a tie)
+    pub fn hull(&self) -> Vec<Point2D> {
+        let root = match self.root {
+            Some(ref root) => root,
+            None => return Vec::new(),
+        };
+        let mut hull_points: Vec<Point2D> = Vec::new();
+        root.chain(-1.0, &root.low(), &root.high(), &mut hull_points);
+        let mut upper: Vec<Point2D> = Vec::new();
+        root.chain(1.0, &root.low(), &root.high(), &mut upper);
+        // only the extreme points of the first and the last column
+        // are vertices, which the bridges don't know
+        trim_columns(&mut hull_points, -1.0);
+        trim_columns(&mut upper, 1.0);
+        upper.reverse();
+        if upper.first() == hull_points.last() {
+            upper.remove(0);
+        }
+        if upper.last() == hull_points.first() {
+            upper.pop();
+        }
+        hull_points.extend(upper);
+        hull_points
+    }
+}
+
+impl Extend<Point2D> for FullyDynamicHull {
+    fn extend<I: IntoIterator<Item = Point2D>>(&mut self, points: I) {
+        for point in points {
+            self.insert(point);
+        }
+    }
+}
+
+impl FromIterator<Point2D> for FullyDynamicHull {
+    fn from_iter<I: IntoIterator<Item = Point2D>>(points: I) -> FullyDynamicHull {
+        let mut hull = FullyDynamicHull::new();
+        hull.extend(points);
+        hull
+    }
+}
+
+///An approximate hull of a stream of points in constant memory
+///
+/// The sketch keeps the extreme point of the stream in each of k
+/// evenly spaced directions, with k = ⌈π / atan(ε)⌉, so it takes
+/// O(1/ε) memory however many points are inserted, and O(1/ε) time
+/// per point. The hull of the kept points is an ε-kernel: it is inside
+/// the exact hull, and every inserted point is at most ε times the
+/// diameter of the points away from it.
+///
+/// P. Agarwal, S. Har-Peled and K. Varadarajan, "Approximating extent
+/// measures of points", Journal of the ACM, 2004
+#[derive(Debug, Clone)]
+pub struct HullSketch {
+    // unit vectors of the directions
+    directions: Vec<(f64, f64)>,
+    // the point furthest in each direction
+    extremes: Vec<Option<Point2D>>,
+}
+
+impl HullSketch {
+    ///An empty sketch accurate to `epsilon` times the diameter
+    ///
+    /// # Panics
+    /// Panics when epsilon is not positive
+    pub fn new(epsilon: f64) -> HullSketch {
+        assert!(epsilon > 0.0, "epsilon must be positive");
+        let count = (::std::f64::consts::PI / epsilon.atan()).ceil() as usize;
+        let directions: Vec<(f64, f64)> = (0..count)
+            .map(|i| {
+                let angle = 2.0 * ::std::f64::consts::PI * i as f64 / count as f64;
+                (angle.cos(), angle.sin())
+            })
+            .collect();
+        HullSketch {
+            extremes: vec![None; count],
+            directions,
+        }
+    }
+
+    ///The number of directions, the most points the sketch keeps
+    pub fn directions(&self) -> usize {
+        self.directions.len()
+    }
+
+    ///Takes a point of the stream
+    pub fn insert(&mut self, point: Point2D) {
+        for (&(x, y), extreme) in self.directions.iter().zip(self.extremes.iter_mut()) {
+            let further = extreme.is_none_or(|extreme| point.x * x + point.y * y > extreme.x * x + extreme.y * y);
+            if further {
+                *extreme = Some(point);
+            }
+        }
+    }
+
+    ///The approximate hull in the order of `convexhull::hull_indices`
+    pub fn hull(&self) -> Vec<Point2D> {
+        let mut kept: Vec<Point2D> = self.extremes.iter().filter_map(|&extreme| extreme).collect();
+        kept.sort_by(compare);
+        kept.dedup();
+        convex_hull_of(&kept)
+    }
+}
+
+impl Extend<Point2D> for HullSketch {
+    fn extend<I: IntoIterator<Item = Point2D>>(&mut self, points: I) {
+        for point in points {
+            self.insert(point);
+        }
+    }
+}
+
+/// A subtree of a FullyDynamicHull
+#[derive(Debug, Clone)]
+enum Node {
+    /// a point and the number of its copies
+    Leaf(Point2D, usize),
+    Branch(Box<Branch>),
+}
+
+#[derive(Debug, Clone)]
+struct Branch {
+    left: Node,
+    right: Node,
+    leaves: usize,
+    low: Point2D,
+    high: Point2D,
+    // the ends of the bridges, in the left and the right child
+    upper: (Point2D, Point2D),
+    lower: (Point2D, Point2D),
+}
+
+impl Node {
+    /// A branch over two subtrees, the left one holding the smaller points
+    fn join(left: Node, right: Node) -> Node {
+        let split = (left.high().x + right.low().x) / 2.0;
+        let upper = bridge(&left, &right, split, 1.0);
+        let lower = bridge(&left, &right, split, -1.0);
+        Node::Branch(Box::new(Branch {
+            leaves: left.leaves() + right.leaves(),
+            low: left.low(),
+            high: right.high(),
+            upper,
+            lower,
+            left,
+            right,
+        }))
+    }
+
+    /// A balanced tree over leaves in increasing order
+    fn build(leaves: &[(Point2D, usize)]) -> Node {
+        if leaves.len() == 1 {
+            return Node::Leaf(leaves[0].0, leaves[0].1);
+        }
+        let middle = leaves.len() / 2;
+        Node::join(Node::build(&leaves[..middle]), Node::build(&leaves[middle..]))
+    }
+
+    /// Joins two subtrees, or rebuilds them when one has more than
+    /// three quarters of the leaves
+    fn balance(left: Node, right: Node) -> Node {
+        let (left_leaves, right_leaves) = (left.leaves(), right.leaves());
+        if 4 * left_leaves.max(right_leaves) <= 3 * (left_leaves + right_leaves) {
+            return Node::join(left, right);
+        }
+        let mut leaves: Vec<(Point2D, usize)> = Vec::new();
+        left.collect_leaves(&mut leaves);
+        right.collect_leaves(&mut leaves);
+        Node::build(&leaves)
+    }
+
+    fn collect_leaves(self, leaves: &mut Vec<(Point2D, usize)>) {
+        match self {
+            Node::Leaf(point, copies) => leaves.push((point, copies)),
+            Node::Branch(branch) => {
+                let branch = *branch;
+                branch.left.collect_leaves(leaves);
+                branch.right.collect_leaves(leaves);
+            }
+        }
+    }
+
+    fn insert(self, point: Point2D) -> Node {
+        match self {
+            Node::Leaf(leaf, copies) => match compare(&point, &leaf) {
+                Ordering::Equal => Node::Leaf(leaf, copies + 1),
+                Ordering::Less => Node::join(Node::Leaf(point, 1), Node::Leaf(leaf, copies)),
+                Ordering::Greater => Node::join(Node::Leaf(leaf, copies), Node::Leaf(point, 1)),
+            },
+            Node::Branch(branch) => {
+                let branch = *branch;
+                if compare(&point, &branch.left.high()) != Ordering::Greater {
+                    Node::balance(branch.left.insert(point), branch.right)
+                } else {
+                    Node::balance(branch.left, branch.right.insert(point))
+                }
+            }
+        }
+    }
+
+    /// Removes a point of the subtree, None when nothing is left
+    fn remove(self, point: &Point2D) -> Option<Node> {
+        match self {
+            Node::Leaf(leaf, copies) => {
+                if copies > 1 {
+                    Some(Node::Leaf(leaf, copies - 1))
+                } else {
+                    None
+                }
+            }
+            Node::Branch(branch) => {
+                let branch = *branch;
+                if compare(point, &branch.left.high()) != Ordering::Greater {
+                    match branch.left.remove(point) {
+                        Some(left) => Some(Node::balance(left, branch.right)),
+                        None => Some(branch.right),
+                    }
+                } else {
+                    match branch.right.remove(point) {
+                        Some(right) => Some(Node::balance(branch.left, right)),
+                        None => Some(branch.left),
+                    }
+                }
+            }
+        }
+    }
+
+    fn contains(&self, point: &Point2D) -> bool {
+        match *self {
+            Node::Leaf(leaf, _) => leaf == *point,
+            Node::Branch(ref branch) => {
+                if compare(point, &branch.left.high()) != Ordering::Greater {
+                    branch.left.contains(point)
+                } else {
+                    branch.right.contains(point)
+                }
+            }
+        }
+    }
+
+    fn leaves(&self) -> usize {
+        match *self {
+            Node::Leaf(..) => 1,
+            Node::Branch(ref branch) => branch.leaves,
+        }
+    }
+
+    fn low(&self) -> Point2D {
+        match *self {
+            Node::Leaf(point, _) => point,
+            Node::Branch(ref branch) => branch.low,
+        }
+    }
+
+    fn high(&self) -> Point2D {
+        match *self {
+            Node::Leaf(point, _) => point,
+            Node::Branch(ref branch) => branch.high,
+        }
+    }
+
+    fn children(&self) -> Option<(&Node, &Node)> {
+        match *self {
+            Node::Leaf(..) => None,
+            Node::Branch(ref branch) => Some((&branch.left, &branch.right)),
+        }
+    }
+
+    /// The ends of the upper (sign 1) or lower (sign -1) bridge
+    fn bridge(&self, sign: f64) -> (Point2D, Point2D) {
+        match *self {
+            Node::Leaf(point, _) => (point, point),
+            Node::Branch(ref branch) => {
+                if sign > 0.0 {
+                    branch.upper
+                } else {
+                    branch.lower
+                }
+            }
+        }
+    }
+
+    /// Adds the vertices of the upper (sign 1) or lower (sign -1)
+    /// hull of the subtree from `low` to `high`, from left to right
+    fn chain(&self, sign: f64, low: &Point2D, high: &Point2D, chain: &mut Vec<Point2D>) {
+        match *self {
+            Node::Leaf(point, _) => {
+                if compare(low, &point) != Ordering::Greater && compare(&point, high) != Ordering::Greater {
+                    chain.push(point);
+                }
+            }
+            Node::Branch(ref branch) => {
+                let (left_end, right_end) = self.bridge(sign);
+                let left_high = if compare(high, &left_end) == Ordering::Less { *high } else { left_end };
+                if compare(low, &left_high) != Ordering::Greater {
+                    branch.left.chain(sign, low, &left_high, chain);
+                }
+                let right_low = if compare(low, &right_end) == Ordering::Greater { *low } else { right_end };
+                if compare(&right_low, high) != Ordering::Greater {
+                    branch.right.chain(sign, &right_low, high, chain);
+                }
+            }
+        }
+    }
+}
+
+/// Finds the upper (sign 1) or lower (sign -1) bridge between the
+/// hulls of two subtrees, separated by the vertical line at `split`
+///
+/// Both subtrees are walked down at once, and each step drops the half
+/// of one hull that can't hold the end of the bridge. For the lower
+/// bridge the points are mirrored at the x axis.
+fn bridge(left: &Node, right: &Node, split: f64, sign: f64) -> (Point2D, Point2D) {
+    let mirror = |point: Point2D| Point2D::new(point.x, sign * point.y);
+    let (mut x, mut y) = (left, right);
+    loop {
+        let ((a, b), (c, d)) = (x.bridge(sign), y.bridge(sign));
+        let (children_x, children_y) = (x.children(), y.children());
+        if children_x.is_none() && children_y.is_none() {
+            return (a, c);
+        }
+        let (a, b, c, d) = (mirror(a), mirror(b), mirror(c), mirror(d));
+        // a point of the right hull on or above the edge at x: the
+        // bridge leaves the left hull before it
+        if let Some((x_left, _)) = children_x {
+            if orietation(&a, &b, &c) >= 0.0 {
+                x = x_left;
+                continue;
+            }
+        }
+        // and the other way around
+        if let Some((_, y_right)) = children_y {
+            if orietation(&c, &d, &b) >= 0.0 {
+                y = y_right;
+                continue;
+            }
+        }
+        match (children_x, children_y) {
+            (None, Some((y_left, _))) => y = y_left,
+            (Some((_, x_right)), None) => x = x_right,
+            (Some((_, x_right)), Some((y_left, _))) => {
+                // the edge that is lower at the split can't hold the bridge end
+                // beyond it; vertical edges are only at the ends of a hull
+                if a.x == b.x {
+                    x = x_right;
+                } else if c.x == d.x {
+                    y = y_left;
+                } else {
+                    let height_x = a.y + (b.y - a.y) * (split - a.x) / (b.x - a.x);
+                    let height_y = c.y + (d.y - c.y) * (split - c.x) / (d.x - c.x);
+                    if height_x <= height_y {
+                        y = y_left;
+                    } else {
+                        x = x_right;
+                    }
+                }
+            }
+            (None, None) => unreachable!(),
+        }
+    }
+}
+
+/// Keeps only the highest (sign 1) or lowest (sign -1) point of the
+/// first and of the last column of a chain
+fn trim_columns(chain: &mut Vec<Point2D>, sign: f64) {
+    while chain.len() > 1 && chain[0].x == chain[1].x {
+        let lower = if sign * chain[0].y < sign * chain[1].y { 0 } else { 1 };
+        chain.remove(lower);
+    }
+    while chain.len() > 1 && chain[chain.len() - 1].x == chain[chain.len() - 2].x {
+        let last = chain.len() - 1;
+        let lower = if sign * chain[last].y < sign * chain[last - 1].y { last } else { last - 1 };
+        chain.remove(lower);
+    }
+}
+
+/// Orders points by x and then by y
+///
+/// # Panics
+/// Panics when a coordinate is NaN
+fn compare(a: &Point2D, b: &Point2D) -> Ordering {
+    a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap())
+}
@@ -0,0 +1,112 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Lower envelopes of lines
+//!
+//! The minimum of lines y = m x + b at x is a dot product of (x, 1)
+//! with the points (m, b), so only the lines of the lower hull of those
+//! points are ever the minimum. Dynamic programs often have recurrences
+//! of this form, and keeping just that hull answers them quickly.
+
+///The lower envelope of lines added in order of decreasing slope,
+/// also known as the convex hull trick
+///
+/// Every line is added in O(1) amortized time, like the points of a
+/// monotone chain, and the minimum at any x takes O(log n). Queries at
+/// increasing x can use `min_at_increasing` in O(1) amortized time
+/// instead. For the maximum, add the lines negated and negate the
+/// result.
+#[derive(Debug, Clone, Default)]
+pub struct ConvexHullTrick {
+    // (slope, intercept) of the lines on the envelope, slopes decreasing
+    lines: Vec<(f64, f64)>,
+    // where min_at_increasing left off
+    pointer: usize,
+}
+
+impl ConvexHullTrick {
+    ///An empty envelope
+    pub fn new() -> ConvexHullTrick {
+        ConvexHullTrick::default()
+    }
+
+    ///Adds the line y = slope · x + intercept
+    ///
+    /// # Panics
+    /// Panics when the slope is greater than the slope of the line
+    /// added before
+    pub fn add(&mut self, slope: f64, intercept: f64) {
+        if let Some(&(last_slope, last_intercept)) = self.lines.last() {
+            assert!(slope <= last_slope, "the slopes have to decrease");
+            if slope == last_slope {
+                if intercept >= last_intercept {
+                    return;
+                }
+                self.lines.pop();
+            }
+        }
+        //the last line is no longer the minimum anywhere when the new
+        //one meets the one before it no later than it does
+        while self.lines.len() >= 2 {
+            let (m1, b1) = self.lines[self.lines.len() - 2];
+            let (m2, b2) = self.lines[self.lines.len() - 1];
+            if (intercept - b1) * (m1 - m2) <= (b2 - b1) * (m1 - slope) {
+                self.lines.pop();
+            } else {
+                break;
+            }
+        }
+        self.lines.push((slope, intercept));
+        self.pointer = self.pointer.min(self.lines.len() - 1);
+    }
+
+    ///The least value of the lines at x, or None when there are no lines
+    pub fn min_at(&self, x: f64) -> Option<f64> {
+        if self.lines.is_empty() {
+            return None;
+        }
+        let value = |i: usize| self.lines[i].0 * x + self.lines[i].1;
+        //further along the envelope the lines are the minimum at greater x
+        let (mut low, mut high) = (0, self.lines.len() - 1);
+        while low < high {
+            let mid = (low + high) / 2;
+            if value(mid) <= value(mid + 1) {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        Some(value(low))
+    }
+
+    ///Same as min_at for queries with x never decreasing since the
+    /// last lines were added
+    pub fn min_at_increasing(&mut self, x: f64) -> Option<f64> {
+        if self.lines.is_empty() {
+            return None;
+        }
+        let value = |line: &(f64, f64)| line.0 * x + line.1;
+        while self.pointer + 1 < self.lines.len() && value(&self.lines[self.pointer + 1]) <= value(&self.lines[self.pointer]) {
+            self.pointer += 1;
+        }
+        Some(value(&self.lines[self.pointer]))
+    }
+
+    ///The lines on the envelope as (slope, intercept), from the one
+    /// that is the minimum for the least x to the one for the greatest
+    pub fn lines(&self) -> &[(f64, f64)] {
+        &self.lines
+    }
+
+    ///The number of lines on the envelope
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    ///Whether no line was added
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Geometry utilities
+//!
+//! Small helpers on polygons given as their vertices in order, like the
+//! hulls of the algorithms, which don't all go around the same way.
+use hull::twice_signed_area;
+use points::*;
+
+///Which way a polygon goes around, or a path of three points turns
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+    /// no area, or no turn
+    Collinear,
+}
+
+impl Orientation {
+    /// The orientation of a signed area or cross product
+    fn of(value: f64) -> Orientation {
+        if value > 0.0 {
+            Orientation::CounterClockwise
+        } else if value < 0.0 {
+            Orientation::Clockwise
+        } else {
+            Orientation::Collinear
+        }
+    }
+}
+
+///The area enclosed by the polygon, positive when it goes counter
+/// clockwise and negative when it goes clockwise (shoelace formula)
+pub fn signed_area(polygon: &[Point2D]) -> f64 {
+    twice_signed_area(polygon) / 2.0
+}
+
+///Which way the polygon goes around, by the sign of its area
+///
+/// Polygons without area, like the hulls of collinear points or of
+/// fewer than three points, are Collinear.
+pub fn orientation(polygon: &[Point2D]) -> Orientation {
+    Orientation::of(twice_signed_area(polygon))
+}
+
+///Which way the path from a through b to c turns
+pub fn turn(a: &Point2D, b: &Point2D, c: &Point2D) -> Orientation {
+    Orientation::of(orietation(a, b, c))
+}
+
+///Rotates and turns the vertices of a hull into its canonical form:
+/// counter clockwise, from the vertex with the least x-coordinate (the
+/// lowest one on a tie)
+///
+/// Hulls of the same points are then equal vertex for vertex, whichever
+/// algorithm found them; all of the algorithms in `convexhull` return
+/// this form, which is the order of `convexhull::hull_indices`.
+/// Collinear points along the edges keep their place in the ring. The
+/// way around is the turn at that vertex, which `CoordNum::orientation`
+/// takes exactly for integer coordinates.
+pub fn canonicalize<T: CoordNum>(hull: &mut [Point2D<T>]) {
+    let first = (0..hull.len()).fold(0, |first, i| {
+        let (point, least) = (&hull[i], &hull[first]);
+        if point.x < least.x || (point.x == least.x && point.y < least.y) {
+            i
+        } else {
+            first
+        }
+    });
+    hull.rotate_left(first);
+    let len = hull.len();
+    if len > 2 && T::orientation(&hull[len - 1], &hull[0], &hull[1]) == Orientation::Clockwise {
+        hull[1..].reverse();
+    }
+}
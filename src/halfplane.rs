@@ -0,0 +1,99 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Intersections of half-planes
+//!
+//! A convex polygon is both the hull of its vertices and the common
+//! part of the half-planes on the inner side of its edges. Finding the
+//! polygon from the half-planes is the dual of finding the hull, and the
+//! sweep below is the Graham scan of that dual: the lines sorted by
+//! angle, with the ones that no longer bound the region popped off.
+use convexhull::convex_hull_of;
+use hull::Line;
+use points::*;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+
+///The convex polygon common to the half-planes, each the points left of
+/// a line or on it
+///
+/// The lines are sorted by the angle of their direction, and every line
+/// removes the lines at both ends of a deque whose crossing it cuts off,
+/// in O(n log n) time. The polygon is in the order of convex_hull_of.
+/// It is empty when the half-planes have no area in common, and when
+/// that area is unbounded; add the half-planes of a large box to bound
+/// it.
+///
+/// # Panics
+/// Panics when a coordinate is NaN, or a line has the same point twice
+pub fn half_plane_intersection(planes: &[Line]) -> Vec<Point2D> {
+    assert!(planes.iter().all(|line| line.from != line.to), "a line needs two points");
+    let mut lines: Vec<&Line> = planes.iter().collect();
+    //of lines in the same direction the innermost one first
+    lines.sort_by(|a, b| by_direction(a, b).then_with(|| 0.0.partial_cmp(&b.side(&a.from)).unwrap()));
+    lines.dedup_by(|later, first| by_direction(later, first) == Ordering::Equal);
+    //consecutive directions half a circle or more apart leave it unbounded
+    let n = lines.len();
+    if n < 3 || (0..n).any(|i| turn(lines[i], lines[(i + 1) % n]) <= 0.0) {
+        return Vec::new();
+    }
+    let mut deque: VecDeque<&Line> = VecDeque::new();
+    for line in lines {
+        while deque.len() >= 2 && line.side(&crossing(deque[deque.len() - 2], deque[deque.len() - 1])) < 0.0 {
+            deque.pop_back();
+        }
+        while deque.len() >= 2 && line.side(&crossing(deque[0], deque[1])) < 0.0 {
+            deque.pop_front();
+        }
+        //all the lines between two opposite ones were cut off
+        if deque.back().is_some_and(|&last| turn(last, line) <= 0.0) {
+            return Vec::new();
+        }
+        deque.push_back(line);
+    }
+    while deque.len() >= 3 && deque[0].side(&crossing(deque[deque.len() - 2], deque[deque.len() - 1])) < 0.0 {
+        deque.pop_back();
+    }
+    while deque.len() >= 3 && deque[deque.len() - 1].side(&crossing(deque[0], deque[1])) < 0.0 {
+        deque.pop_front();
+    }
+    let m = deque.len();
+    if m < 3 || turn(deque[m - 1], deque[0]) <= 0.0 {
+        return Vec::new();
+    }
+    let corners: Vec<Point2D> = (0..m).map(|i| crossing(deque[i], deque[(i + 1) % m])).collect();
+    let polygon = convex_hull_of(&corners);
+    if polygon.len() < 3 {
+        return Vec::new();
+    }
+    polygon
+}
+
+/// Orders lines by the angle of their direction from the x axis,
+/// counter clockwise from 0 up to a full turn
+fn by_direction(a: &Line, b: &Line) -> Ordering {
+    let upper = |line: &Line| {
+        let (dx, dy) = (line.to.x - line.from.x, line.to.y - line.from.y);
+        dy > 0.0 || (dy == 0.0 && dx > 0.0)
+    };
+    match (upper(a), upper(b)) {
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        _ => 0.0.partial_cmp(&turn(a, b)).unwrap(),
+    }
+}
+
+/// The cross product of the directions, positive when the second line
+/// turns left from the first one by less than half a circle
+fn turn(first: &Line, second: &Line) -> f64 {
+    (first.to.x - first.from.x) * (second.to.y - second.from.y) - (first.to.y - first.from.y) * (second.to.x - second.from.x)
+}
+
+/// The point where two lines that are not parallel cross
+fn crossing(first: &Line, second: &Line) -> Point2D {
+    let (side_from, side_to) = (second.side(&first.from), second.side(&first.to));
+    let t = side_from / (side_from - side_to);
+    Point2D::new(first.from.x + t * (first.to.x - first.from.x), first.from.y + t * (first.to.y - first.from.y))
+}
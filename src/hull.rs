@@ -0,0 +1,1022 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! A computed convex hull and the operations on it
+use convexhull::convex_hull_of_sorted;
+use geometry::{orientation, Orientation};
+use points::*;
+
+///A directed line passing through two points
+///
+/// Points to the left of the line (counter clockwise
+/// side when walking from `from` to `to`) are on its
+/// positive side.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Line {
+    /// a point on the line
+    pub from: Point2D,
+    /// a second point on the line, giving its direction
+    pub to: Point2D,
+}
+
+impl Line {
+    ///Constructor for Line
+    pub fn new(from: Point2D, to: Point2D) -> Line {
+        Line { from, to }
+    }
+
+    ///Positive when the point is left of the line, negative
+    /// when it is right of the line and zero when it is on it
+    pub fn side(&self, point: &Point2D) -> f64 {
+        orietation(&self.from, &self.to, point)
+    }
+
+    ///Finds the point where the segment a-b crosses the line
+    fn intersection(&self, point_a: &Point2D, point_b: &Point2D) -> Point2D {
+        let side_a = self.side(point_a);
+        let side_b = self.side(point_b);
+        let t = side_a / (side_a - side_b);
+        Point2D::new(
+            point_a.x + t * (point_b.x - point_a.x),
+            point_a.y + t * (point_b.y - point_a.y),
+        )
+    }
+}
+
+///The vertices of a convex hull
+///
+/// The vertices are always kept in counter clockwise
+/// order, whichever algorithm produced them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvexHull {
+    vertices: Vec<Point2D>,
+}
+
+impl ConvexHull {
+    ///Wraps the hull points returned by any of the algorithms
+    ///
+    /// Clockwise hulls (like the ones from other libraries) are
+    /// reversed so that the vertices are counter clockwise.
+    pub fn new(mut vertices: Vec<Point2D>) -> ConvexHull {
+        if orientation(&vertices) == Orientation::Clockwise {
+            vertices.reverse();
+        }
+        ConvexHull { vertices }
+    }
+
+    ///The hull vertices in counter clockwise order
+    pub fn vertices(&self) -> &[Point2D] {
+        &self.vertices
+    }
+
+    ///Converts the hull back to its vertices
+    pub fn into_vertices(self) -> Vec<Point2D> {
+        self.vertices
+    }
+
+    ///Cuts the hull into the two convex pieces on either side of a line
+    ///
+    /// The first piece is the part left of the line and the second
+    /// the part right of it. A side is None when the hull has no area
+    /// on that side of the line.
+    pub fn split(&self, line: &Line) -> (Option<ConvexHull>, Option<ConvexHull>) {
+        let mut left: Vec<Point2D> = Vec::new();
+        let mut right: Vec<Point2D> = Vec::new();
+        let len = self.vertices.len();
+        for i in 0..len {
+            let current = self.vertices[i];
+            let next = self.vertices[(i + 1) % len];
+            let side_current = line.side(&current);
+            let side_next = line.side(&next);
+            if side_current >= 0.0 {
+                left.push(current);
+            }
+            if side_current <= 0.0 {
+                right.push(current);
+            }
+            // the edge crosses the line, both pieces get the crossing point
+            if (side_current > 0.0 && side_next < 0.0) || (side_current < 0.0 && side_next > 0.0) {
+                let crossing = line.intersection(&current, &next);
+                left.push(crossing);
+                right.push(crossing);
+            }
+        }
+        (ConvexHull::piece(left), ConvexHull::piece(right))
+    }
+
+    ///Area enclosed by the hull
+    pub fn area(&self) -> f64 {
+        twice_signed_area(&self.vertices) / 2.0
+    }
+
+    ///Length of the boundary of the hull
+    pub fn perimeter(&self) -> f64 {
+        hull_perimeter(&self.vertices)
+    }
+
+    ///The centre of mass of the area of the hull, see hull_centroid
+    pub fn centroid(&self) -> Option<Point2D> {
+        hull_centroid(&self.vertices)
+    }
+
+    ///The part of the plane covered by both hulls, or None
+    /// when they don't overlap, see hull_intersection
+    pub fn intersection(&self, other: &ConvexHull) -> Option<ConvexHull> {
+        ConvexHull::piece(hull_intersection(&self.vertices, &other.vertices))
+    }
+
+    ///Compares the hull with another one, like the same hull
+    /// computed by a different tool
+    ///
+    /// Vertices match when they are at most `tolerance` apart, so
+    /// the comparison doesn't depend on the first vertex or orientation.
+    pub fn diff(&self, other: &ConvexHull, tolerance: f64) -> HullDiff {
+        let unmatched = |vertices: &[Point2D], others: &[Point2D]| -> Vec<Point2D> {
+            vertices
+                .iter()
+                .filter(|vertex| {
+                    !others.iter().any(|other| {
+                        (vertex.x - other.x).powi(2) + (vertex.y - other.y).powi(2) <= tolerance.powi(2)
+                    })
+                })
+                .cloned()
+                .collect()
+        };
+        HullDiff {
+            only_in_first: unmatched(&self.vertices, &other.vertices),
+            only_in_second: unmatched(&other.vertices, &self.vertices),
+            symmetric_difference_area: hull_symmetric_difference_area(&self.vertices, &other.vertices),
+        }
+    }
+
+    /// A piece of a split is only a hull when it covers some area
+    fn piece(vertices: Vec<Point2D>) -> Option<ConvexHull> {
+        if vertices.len() > 2 && twice_signed_area(&vertices) > 0.0 {
+            Some(ConvexHull { vertices })
+        } else {
+            None
+        }
+    }
+}
+
+///The differences between two hulls found by ConvexHull::diff
+#[derive(Debug, Clone, PartialEq)]
+pub struct HullDiff {
+    /// vertices of the first hull with no vertex of the second one nearby
+    pub only_in_first: Vec<Point2D>,
+    /// vertices of the second hull with no vertex of the first one nearby
+    pub only_in_second: Vec<Point2D>,
+    /// area covered by exactly one of the hulls
+    pub symmetric_difference_area: f64,
+}
+
+impl HullDiff {
+    ///True when every vertex has a match in the other hull
+    pub fn vertices_match(&self) -> bool {
+        self.only_in_first.is_empty() && self.only_in_second.is_empty()
+    }
+}
+
+#[cfg(feature = "parallel")]
+extern crate rayon;
+
+/// Query points answered one after the other, with the `parallel`
+/// feature on each thread
+#[cfg(feature = "parallel")]
+const LOCATOR_BATCH: usize = 4096;
+
+///A hull prepared for many containment queries
+///
+/// The vertices are stored counter clockwise without repeats, so that
+/// every query is a binary search over the fan of triangles from the
+/// first vertex, in O(log n) instead of the O(n) of testing every edge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointLocator {
+    // counter clockwise, or the two ends of a hull without area
+    vertices: Vec<Point2D>,
+    // a point strictly inside a hull with area
+    inside: Point2D,
+    // the bounding box of the vertices, to reject points far outside
+    bounds: (Point2D, Point2D),
+}
+
+impl PointLocator {
+    ///Prepares the hull vertices returned by any of the algorithms,
+    /// in either orientation
+    ///
+    /// # Panics
+    /// Panics when a coordinate is NaN
+    pub fn new(hull: &[Point2D]) -> PointLocator {
+        let mut vertices = without_repeats(hull);
+        if vertices.len() > 2 && twice_signed_area(&vertices) == 0.0 {
+            //a segment is kept as its two ends
+            let order = |a: &&Point2D, b: &&Point2D| a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap());
+            let low = *vertices.iter().min_by(order).unwrap();
+            let high = *vertices.iter().max_by(order).unwrap();
+            vertices = vec![low, high];
+        }
+        //the centre of the first triangle of the fan with some area
+        let n = vertices.len();
+        let inside = (1..n.saturating_sub(1))
+            .find(|&k| orietation(&vertices[0], &vertices[k], &vertices[k + 1]) > 0.0)
+            .map_or(Point2D::new(0.0, 0.0), |k| {
+                Point2D::new(
+                    (vertices[0].x + vertices[k].x + vertices[k + 1].x) / 3.0,
+                    (vertices[0].y + vertices[k].y + vertices[k + 1].y) / 3.0,
+                )
+            });
+        let bounds = if n > 0 { bounding_box(&vertices) } else { (inside, inside) };
+        PointLocator { vertices, inside, bounds }
+    }
+
+    ///Whether the point is inside the hull or on its boundary
+    pub fn contains(&self, point: &Point2D) -> bool {
+        let vertices = &self.vertices;
+        let n = vertices.len();
+        if n < 3 {
+            return match n {
+                0 => false,
+                1 => vertices[0] == *point,
+                _ => {
+                    let (a, b) = (&vertices[0], &vertices[1]);
+                    orietation(a, b, point) == 0.0
+                        && point.x >= a.x.min(b.x)
+                        && point.x <= a.x.max(b.x)
+                        && point.y >= a.y.min(b.y)
+                        && point.y <= a.y.max(b.y)
+                }
+            };
+        }
+        let (low, high) = self.bounds;
+        if point.x < low.x || point.x > high.x || point.y < low.y || point.y > high.y {
+            return false;
+        }
+        let first = &vertices[0];
+        //outside of the angle at the first vertex
+        if orietation(first, &vertices[1], point) < 0.0 || orietation(first, &vertices[n - 1], point) > 0.0 {
+            return false;
+        }
+        //the last triangle (first, i, i + 1) with the point left of
+        //its side from the first vertex
+        let (mut low, mut high) = (1, n - 2);
+        while low < high {
+            let mid = high - (high - low) / 2;
+            if orietation(first, &vertices[mid], point) >= 0.0 {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        orietation(&vertices[low], &vertices[low + 1], point) >= 0.0
+    }
+
+    ///Whether each of the points is inside the hull or on its boundary
+    ///
+    /// With the `parallel` feature batches of points are answered on
+    /// the threads of the rayon pool.
+    pub fn contains_all(&self, points: &[Point2D]) -> Vec<bool> {
+        #[cfg(feature = "parallel")]
+        {
+            use self::rayon::prelude::*;
+            let batches: Vec<Vec<bool>> = points
+                .par_chunks(LOCATOR_BATCH)
+                .map(|batch| batch.iter().map(|point| self.contains(point)).collect())
+                .collect();
+            batches.concat()
+        }
+        #[cfg(not(feature = "parallel"))]
+        points.iter().map(|point| self.contains(point)).collect()
+    }
+
+    ///The prepared vertices in counter clockwise order
+    pub fn vertices(&self) -> &[Point2D] {
+        &self.vertices
+    }
+
+    ///The point of the boundary of the hull nearest to the point, and
+    /// its distance
+    ///
+    /// The distance to the boundary can fall and rise several times
+    /// around the hull, but not along the edges seen from a point outside
+    /// of it. Those are found by a binary search for the edge in the
+    /// direction of the point from inside the hull, and two more for the
+    /// tangents from the point, so the nearest edge takes O(log n) to
+    /// find. Points inside the hull take O(n).
+    ///
+    /// # Panics
+    /// Panics when the hull is empty
+    pub fn closest_point(&self, point: &Point2D) -> (Point2D, f64) {
+        let vertices = &self.vertices;
+        let n = vertices.len();
+        assert!(n > 0, "the hull has no vertices");
+        let nearest = |i: usize| nearest_on_segment(&vertices[i % n], &vertices[(i + 1) % n], point);
+        let nearest_to_all = || (1..n).map(nearest).fold(nearest(0), |best, edge| if edge.1 < best.1 { edge } else { best });
+        if n < 3 {
+            return nearest_to_all();
+        }
+        let direction = Point2D::new(point.x - self.inside.x, point.y - self.inside.y);
+        let facing = self.wedge(&direction);
+        let visible = |i: usize| orietation(&vertices[i % n], &vertices[(i + 1) % n], point) < 0.0;
+        //the edge in the other direction is not seen, so the visible ones
+        //run from there to the facing one and on to the next hidden one
+        let hidden = self.wedge(&Point2D::new(-direction.x, -direction.y));
+        if !visible(facing) || visible(hidden) {
+            return nearest_to_all();
+        }
+        let (mut low, mut high) = (0, (hidden + n - facing) % n - 1);
+        while low < high {
+            let mid = high - (high - low) / 2;
+            if visible(facing + mid) {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        let last = (facing + low) % n;
+        let (mut low, mut high) = (1, (facing + n - hidden) % n);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if visible(hidden + mid) {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        let first = (hidden + low) % n;
+        //the first seen edge whose end is no nearer than some point on it
+        let nearer_on = |i: usize| {
+            let (from, to) = (&vertices[i % n], &vertices[(i + 1) % n]);
+            (point.x - to.x) * (to.x - from.x) + (point.y - to.y) * (to.y - from.y) > 0.0
+        };
+        let (mut low, mut high) = (0, (last + n - first) % n);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if nearer_on(first + mid) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        nearest(first + low)
+    }
+
+    /// The edge (i, i + 1) that a ray from inside the hull in the
+    /// direction crosses
+    fn wedge(&self, direction: &Point2D) -> usize {
+        let vertices = &self.vertices;
+        let inside = &self.inside;
+        let start = Point2D::new(vertices[0].x - inside.x, vertices[0].y - inside.y);
+        let cross = |u: &Point2D, w: &Point2D| u.x * w.y - u.y * w.x;
+        //the angles from the first vertex, counter clockwise
+        let first_half = |u: &Point2D| cross(&start, u) > 0.0 || (cross(&start, u) == 0.0 && start.x * u.x + start.y * u.y >= 0.0);
+        let not_after = |u: &Point2D| {
+            if first_half(u) != first_half(direction) {
+                first_half(u)
+            } else {
+                cross(u, direction) >= 0.0
+            }
+        };
+        let (mut low, mut high) = (0, vertices.len() - 1);
+        while low < high {
+            let mid = high - (high - low) / 2;
+            if not_after(&Point2D::new(vertices[mid].x - inside.x, vertices[mid].y - inside.y)) {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        low
+    }
+}
+
+/// The point of the segment nearest to the point, and its distance
+fn nearest_on_segment(from: &Point2D, to: &Point2D, point: &Point2D) -> (Point2D, f64) {
+    let (dx, dy) = (to.x - from.x, to.y - from.y);
+    let length = dx * dx + dy * dy;
+    let t = if length > 0.0 { (((point.x - from.x) * dx + (point.y - from.y) * dy) / length).clamp(0.0, 1.0) } else { 0.0 };
+    let nearest = Point2D::new(from.x + t * dx, from.y + t * dy);
+    (nearest, ((point.x - nearest.x).powi(2) + (point.y - nearest.y).powi(2)).sqrt())
+}
+
+///The point of the boundary of a hull returned by any of the
+/// algorithms nearest to the point, and its distance
+///
+/// This prepares a PointLocator first, which takes O(n). For many
+/// queries against the same hull, prepare it once and use
+/// PointLocator::closest_point.
+///
+/// # Panics
+/// Panics when the hull is empty, or a coordinate is NaN
+pub fn closest_point_on_hull(hull: &[Point2D], point: &Point2D) -> (Point2D, f64) {
+    PointLocator::new(hull).closest_point(point)
+}
+
+///Where a ray enters and leaves a hull, found by ray_intersect
+///
+/// The points of the ray are `origin + t · direction` for t from 0 on.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RayHit {
+    /// t where the ray enters the hull, 0 when it starts inside
+    pub entry: f64,
+    /// t where the ray leaves the hull
+    pub exit: f64,
+    /// the point where the ray enters the hull
+    pub entry_point: Point2D,
+    /// the point where the ray leaves the hull
+    pub exit_point: Point2D,
+}
+
+///Where the ray from the origin in the direction enters and leaves a
+/// hull returned by any of the algorithms, or None when it misses the
+/// hull
+///
+/// The inside of a hull is on the inner side of every edge line, so the
+/// ray is cut down to the part inside each of them in turn, which takes
+/// O(n). A ray only touching the hull enters and leaves it at the same
+/// point. Hulls without area are never hit.
+///
+/// # Panics
+/// Panics when the direction is zero
+pub fn ray_intersect(hull: &[Point2D], origin: &Point2D, direction: &Point2D) -> Option<RayHit> {
+    assert!(direction.x != 0.0 || direction.y != 0.0, "the direction is zero");
+    let (entry, exit) = clip_line(&without_repeats(hull), origin, direction, 0.0, f64::INFINITY)?;
+    let at = |t: f64| Point2D::new(origin.x + t * direction.x, origin.y + t * direction.y);
+    Some(RayHit {
+        entry,
+        exit,
+        entry_point: at(entry),
+        exit_point: at(exit),
+    })
+}
+
+///The part of the segment from `from` to `to` inside a hull returned by
+/// any of the algorithms, or None when the segment misses the hull
+///
+/// This is the Cyrus–Beck clipping of ray_intersect with t from 0 to 1,
+/// so a segment only touching the hull is clipped to that point. The
+/// ends of the segment inside the hull are returned unchanged. Hulls
+/// without area clip every segment away.
+///
+/// M. Cyrus and J. Beck, "Generalized two- and three-dimensional
+/// clipping", Computers & Graphics, 1978
+pub fn clip_segment(hull: &[Point2D], from: &Point2D, to: &Point2D) -> Option<(Point2D, Point2D)> {
+    let direction = Point2D::new(to.x - from.x, to.y - from.y);
+    let (low, high) = clip_line(&without_repeats(hull), from, &direction, 0.0, 1.0)?;
+    let at = |t: f64| {
+        if t == 0.0 {
+            *from
+        } else if t == 1.0 {
+            *to
+        } else {
+            Point2D::new(from.x + t * direction.x, from.y + t * direction.y)
+        }
+    };
+    Some((at(low), at(high)))
+}
+
+/// The range of t from `low` to `high` where `origin + t · direction`
+/// is inside a counter clockwise hull, or None when there is no such t
+fn clip_line(hull: &[Point2D], origin: &Point2D, direction: &Point2D, mut low: f64, mut high: f64) -> Option<(f64, f64)> {
+    let n = hull.len();
+    if n < 3 || twice_signed_area(hull) <= 0.0 {
+        return None;
+    }
+    for i in 0..n {
+        let (from, to) = (&hull[i], &hull[(i + 1) % n]);
+        //how far inside the edge line the point at t is, times its length
+        let at_origin = orietation(from, to, origin);
+        let change = (to.x - from.x) * direction.y - (to.y - from.y) * direction.x;
+        if change == 0.0 {
+            if at_origin < 0.0 {
+                return None;
+            }
+        } else if change > 0.0 {
+            low = low.max(-at_origin / change);
+        } else {
+            high = high.min(-at_origin / change);
+        }
+        if low > high {
+            return None;
+        }
+    }
+    Some((low, high))
+}
+
+///Area enclosed by the hull vertices returned by any of the algorithms,
+/// in either orientation
+pub fn hull_area(hull: &[Point2D]) -> f64 {
+    twice_signed_area(hull).abs() / 2.0
+}
+
+///Length of the boundary through the hull vertices returned by any of
+/// the algorithms, closing edge included
+pub fn hull_perimeter(hull: &[Point2D]) -> f64 {
+    let len = hull.len();
+    (0..len)
+        .map(|i| {
+            let (current, next) = (hull[i], hull[(i + 1) % len]);
+            ((next.x - current.x).powi(2) + (next.y - current.y).powi(2)).sqrt()
+        })
+        .sum()
+}
+
+///The centre of mass of the area enclosed by the hull vertices returned
+/// by any of the algorithms, or None when there are no vertices
+///
+/// Every edge makes a triangle with the first vertex, and the centroids
+/// of those triangles are averaged weighted by their signed areas. This
+/// differs from the mean of the vertices when they are spread unevenly.
+/// Hulls without area, a segment or a point, have the centroid of their
+/// vertices instead.
+pub fn hull_centroid(hull: &[Point2D]) -> Option<Point2D> {
+    let first = *hull.first()?;
+    //relative to the first vertex for precision far from the origin
+    let (mut area, mut x, mut y) = (0.0, 0.0, 0.0);
+    for i in 1..hull.len().saturating_sub(1) {
+        let (a, b) = (hull[i], hull[i + 1]);
+        let (ax, ay, bx, by) = (a.x - first.x, a.y - first.y, b.x - first.x, b.y - first.y);
+        let cross = ax * by - bx * ay;
+        area += cross;
+        x += (ax + bx) * cross;
+        y += (ay + by) * cross;
+    }
+    if area == 0.0 {
+        let count = hull.len() as f64;
+        return Some(Point2D::new(
+            hull.iter().map(|vertex| vertex.x).sum::<f64>() / count,
+            hull.iter().map(|vertex| vertex.y).sum::<f64>() / count,
+        ));
+    }
+    Some(Point2D::new(first.x + x / (3.0 * area), first.y + y / (3.0 * area)))
+}
+
+///The hull with the vertices left out that are within epsilon of the
+/// boundary without them
+///
+/// Douglas–Peucker on the closed ring: it is split at the first vertex
+/// and the one farthest from it, and every chain keeps the vertex
+/// farthest from the segment between its ends when that is more than
+/// epsilon away, splitting the chain there. The vertices kept are in
+/// the order and orientation of the hull, so the result is convex and
+/// inside it. Nearly collinear hulls may come out as the two ends of a
+/// segment.
+///
+/// D. Douglas and T. Peucker, "Algorithms for the reduction of the
+/// number of points required to represent a digitized line or its
+/// caricature", The Canadian Cartographer, 1973
+pub fn simplify_hull(hull: &[Point2D], epsilon: f64) -> Vec<Point2D> {
+    let n = hull.len();
+    if n < 3 {
+        return hull.to_vec();
+    }
+    let distance = |a: &Point2D, b: &Point2D| ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt();
+    let far = (1..n).fold(1, |far, i| if distance(&hull[0], &hull[i]) > distance(&hull[0], &hull[far]) { i } else { far });
+    let mut keep = vec![false; n];
+    keep[0] = true;
+    keep[far] = true;
+    //chains from index to index, the last one ending at the first vertex
+    let mut chains = vec![(0, far), (far, n)];
+    while let Some((from, to)) = chains.pop() {
+        let (mut farthest, mut gap) = (from, epsilon);
+        for i in from + 1..to {
+            let distance = nearest_on_segment(&hull[from], &hull[to % n], &hull[i]).1;
+            if distance > gap {
+                farthest = i;
+                gap = distance;
+            }
+        }
+        if farthest != from {
+            keep[farthest] = true;
+            chains.push((from, farthest));
+            chains.push((farthest, to));
+        }
+    }
+    (0..n).filter(|&i| keep[i]).map(|i| hull[i]).collect()
+}
+
+/// Shoelace formula without the halving, positive for
+/// counter clockwise polygons
+pub fn twice_signed_area(polygon: &[Point2D]) -> f64 {
+    let len = polygon.len();
+    let mut area = 0.0;
+    for i in 0..len {
+        let current = polygon[i];
+        let next = polygon[(i + 1) % len];
+        area += current.x * next.y - next.x * current.y;
+    }
+    area
+}
+
+///The hull vertex farthest in a direction, the support function of
+/// the hull
+///
+/// The vertices rise in the direction and then fall around the hull,
+/// so the edges going up and down are told apart by a binary search in
+/// O(log n) (D. Sunday, "Extreme points of convex polygons"). Either
+/// orientation works, the vertices must not repeat. On a tie any of
+/// the farthest vertices is returned, for a zero direction any vertex.
+///
+/// # Panics
+/// Panics when the hull is empty
+pub fn extreme_point(hull: &[Point2D], direction: &Point2D) -> Point2D {
+    assert!(!hull.is_empty(), "the hull has no vertices");
+    let n = hull.len();
+    let height = |point: &Point2D| direction.x * point.x + direction.y * point.y;
+    let farthest = || *hull.iter().fold(&hull[0], |best, vertex| if height(vertex) > height(best) { vertex } else { best });
+    if n < 8 {
+        return farthest();
+    }
+    //only hulls without area have no turn, the others turn on their first vertices
+    let turn = match (0..n).map(|i| orietation(&hull[i], &hull[(i + 1) % n], &hull[(i + 2) % n])).find(|&turn| turn != 0.0) {
+        Some(turn) => turn,
+        None => return farthest(),
+    };
+    //counter clockwise, with vertex(n) the first vertex again
+    let vertex = |i: usize| if turn > 0.0 { hull[i % n] } else { hull[(n - i % n) % n] };
+    let up = |i: usize| height(&vertex(i + 1)) > height(&vertex(i));
+    let above = |i: usize, j: usize| height(&vertex(i)) > height(&vertex(j));
+    let (mut a, mut b) = (0, n);
+    let mut up_a = up(0);
+    if !up_a && !above(n - 1, 0) {
+        return vertex(0);
+    }
+    loop {
+        let c = (a + b) / 2;
+        let up_c = up(c);
+        if !up_c && !above(c - 1, c) {
+            return vertex(c);
+        }
+        //keep the part of the hull with the rise to the farthest vertex
+        if up_a {
+            if !up_c || above(a, c) {
+                b = c;
+            } else {
+                a = c;
+                up_a = up_c;
+            }
+        } else if !up_c && above(c, a) {
+            b = c;
+        } else {
+            a = c;
+            up_a = up_c;
+        }
+        if b <= a + 1 {
+            //rounding made the vertices look like they don't rise and fall once
+            return farthest();
+        }
+    }
+}
+
+///The extent of the hull along a direction, the length of its shadow
+/// on a line in that direction
+///
+/// The extreme_point in the direction and the one against it are the
+/// ends of the shadow, so this takes two O(log n) queries. The
+/// direction needn't be of unit length; a zero direction has width 0.
+///
+/// # Panics
+/// Panics when the hull is empty
+pub fn width_in_direction(hull: &[Point2D], direction: &Point2D) -> f64 {
+    let length = (direction.x * direction.x + direction.y * direction.y).sqrt();
+    let (ahead, behind) = (extreme_point(hull, direction), extreme_point(hull, &Point2D::new(-direction.x, -direction.y)));
+    if length == 0.0 {
+        return 0.0;
+    }
+    (direction.x * (ahead.x - behind.x) + direction.y * (ahead.y - behind.y)) / length
+}
+
+///The part of the plane covered by both of two hulls returned by any
+/// of the algorithms, in O(n + m) time
+///
+/// Both hulls are cut into a lower and an upper chain at their
+/// leftmost and rightmost vertices. Over the x-range they share, the
+/// intersection is above the higher of the lower chains and below the
+/// lower of the upper chains. These two are found by walking the chains
+/// from left to right, and walking them once more gives the part where
+/// they don't cross over. The intersection is in the order of
+/// convex_hull_of, and empty when the hulls only touch or don't
+/// overlap at all.
+///
+/// # Panics
+/// Panics when a coordinate is NaN
+pub fn hull_intersection(a: &[Point2D], b: &[Point2D]) -> Vec<Point2D> {
+    //hulls whose bounding boxes share no area can't either
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let ((low_a, high_a), (low_b, high_b)) = (bounding_box(a), bounding_box(b));
+    if low_a.x.max(low_b.x) >= high_a.x.min(high_b.x) || low_a.y.max(low_b.y) >= high_a.y.min(high_b.y) {
+        return Vec::new();
+    }
+    let ((lower_a, upper_a), (lower_b, upper_b)) = match (chains(a), chains(b)) {
+        (Some(a), Some(b)) => (a, b),
+        _ => return Vec::new(),
+    };
+    let lower = walk(&lower_a, &lower_b, |first, own, other| own > other || (first && own == other));
+    let upper = walk(&upper_a, &upper_b, |first, own, other| own < other || (first && own == other));
+    let points = walk(&lower, &upper, |first, own, other| if first { own <= other } else { own >= other });
+    let intersection = convex_hull_of_sorted(&points);
+    if intersection.len() < 3 || twice_signed_area(&intersection) <= 0.0 {
+        return Vec::new();
+    }
+    intersection
+}
+
+///Area covered by both of two hulls returned by any of the algorithms
+pub fn hull_overlap_area(a: &[Point2D], b: &[Point2D]) -> f64 {
+    hull_area(&hull_intersection(a, b))
+}
+
+///Area covered by exactly one of two hulls returned by any of the
+/// algorithms
+///
+/// This is the area of both less twice their overlap, in O(n + m)
+/// time. Unlike comparing the vertices, it measures how much two hulls
+/// that differ by rounding disagree: it is close to 0 for such hulls,
+/// and the sum of the areas for hulls that don't overlap.
+pub fn hull_symmetric_difference_area(a: &[Point2D], b: &[Point2D]) -> f64 {
+    (hull_area(a) + hull_area(b) - 2.0 * hull_overlap_area(a, b)).max(0.0)
+}
+
+///Intersection over union of two hulls returned by any of the
+/// algorithms, the area covered by both divided by the area covered by
+/// either
+///
+/// It is 1 for the same hull, 0 for hulls that don't overlap, and 0
+/// when neither hull has an area.
+pub fn hull_iou(a: &[Point2D], b: &[Point2D]) -> f64 {
+    let overlap = hull_overlap_area(a, b);
+    let union = hull_area(a) + hull_area(b) - overlap;
+    if union > 0.0 {
+        (overlap / union).min(1.0)
+    } else {
+        0.0
+    }
+}
+
+///The Minkowski sum of two hulls returned by any of the algorithms,
+/// all the sums of a point of one and a point of the other
+///
+/// The edges of both hulls, counter clockwise from their lowest
+/// vertices, are already sorted by angle, so they are merged like in a
+/// merge sort and joined head to tail in O(n + m) time. The sum is in
+/// counter clockwise order, starting at the point with the least
+/// y-coordinate (the leftmost one on a tie), and empty when a hull is.
+/// Repeated vertices are dropped, collinear ones kept.
+///
+/// # Panics
+/// Panics when a coordinate is NaN
+pub fn minkowski_sum(a: &[Point2D], b: &[Point2D]) -> Vec<Point2D> {
+    let (a, b) = (from_lowest(a), from_lowest(b));
+    merge_edges(&a, &b)
+        .into_iter()
+        .map(|(i, j)| Point2D::new(a[i].x + b[j].x, a[i].y + b[j].y))
+        .collect()
+}
+
+/// The vertices of the Minkowski sum of two polygons from from_lowest,
+/// as the indices of the vertices of both adding up to them
+fn merge_edges(a: &[Point2D], b: &[Point2D]) -> Vec<(usize, usize)> {
+    let (n, m) = (a.len(), b.len());
+    let mut sum: Vec<(usize, usize)> = Vec::with_capacity(n + m);
+    if n == 0 || m == 0 {
+        return sum;
+    }
+    let edge = |polygon: &[Point2D], i: usize| {
+        let (from, to) = (polygon[i % polygon.len()], polygon[(i + 1) % polygon.len()]);
+        Point2D::new(to.x - from.x, to.y - from.y)
+    };
+    let vertex = |(i, j): (usize, usize)| Point2D::new(a[i].x + b[j].x, a[i].y + b[j].y);
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        let pair = (i % n, j % m);
+        //repeated vertices come from edges of a polygon without area
+        if sum.last().map(|&last| vertex(last)) != Some(vertex(pair)) {
+            sum.push(pair);
+        }
+        //the edge turned less goes first, parallel ones together
+        let (edge_a, edge_b) = (edge(a, i), edge(b, j));
+        let cross = edge_a.x * edge_b.y - edge_a.y * edge_b.x;
+        if cross >= 0.0 && i < n {
+            i += 1;
+        }
+        if cross <= 0.0 && j < m {
+            j += 1;
+        }
+    }
+    while sum.len() > 1 && vertex(sum[0]) == vertex(sum[sum.len() - 1]) {
+        sum.pop();
+    }
+    sum
+}
+
+///The distance between two hulls returned by any of the algorithms,
+/// with the closest point of the first hull and of the second one
+///
+/// The differences of the points of the two hulls are their Minkowski
+/// sum with the second hull turned around the origin, like in GJK, and
+/// the distance is the one from the origin to that polygon. This takes
+/// O(n + m) time. Overlapping hulls have a distance of 0 and a point
+/// of both as the closest points.
+///
+/// # Panics
+/// Panics when a hull is empty, or a coordinate is NaN
+pub fn hull_distance(a: &[Point2D], b: &[Point2D]) -> (Point2D, Point2D, f64) {
+    assert!(!a.is_empty() && !b.is_empty(), "a hull has no vertices");
+    let first = from_lowest(a);
+    let turned = from_lowest(&b.iter().map(|point| Point2D::new(-point.x, -point.y)).collect::<Vec<Point2D>>());
+    let difference = merge_edges(&first, &turned);
+    let n = difference.len();
+    let vertex = |(i, j): (usize, usize)| Point2D::new(first[i].x + turned[j].x, first[i].y + turned[j].y);
+    let origin = Point2D::new(0.0, 0.0);
+    //the origin in a triangle of the fan from the first vertex is the same
+    //mix of the vertices of both hulls adding up to the triangle corners
+    for k in 1..n.saturating_sub(1) {
+        let corners = [difference[0], difference[k], difference[k + 1]];
+        let (p0, p1, p2) = (vertex(corners[0]), vertex(corners[1]), vertex(corners[2]));
+        let area = orietation(&p0, &p1, &p2);
+        let weights = [orietation(&origin, &p1, &p2), orietation(&p0, &origin, &p2), orietation(&p0, &p1, &origin)];
+        if area > 0.0 && weights.iter().all(|&weight| weight >= 0.0) {
+            let (mut x, mut y) = (0.0, 0.0);
+            for (&(i, _), weight) in corners.iter().zip(weights.iter()) {
+                x += weight / area * first[i].x;
+                y += weight / area * first[i].y;
+            }
+            let point = Point2D::new(x, y);
+            return (point, point, 0.0);
+        }
+    }
+    let mut closest = (first[0], first[0], f64::INFINITY);
+    for k in 0..n {
+        let ((i, j), (next_i, next_j)) = (difference[k], difference[(k + 1) % n]);
+        //along an edge of the difference both points move along their edges
+        let along = |from: &Point2D, to: &Point2D, t: f64| Point2D::new(from.x + t * (to.x - from.x), from.y + t * (to.y - from.y));
+        let (from, to) = (vertex((i, j)), vertex((next_i, next_j)));
+        let (dx, dy) = (to.x - from.x, to.y - from.y);
+        let length = dx * dx + dy * dy;
+        let t = if length > 0.0 { (-(from.x * dx + from.y * dy) / length).clamp(0.0, 1.0) } else { 0.0 };
+        let nearest = along(&from, &to, t);
+        let distance = (nearest.x * nearest.x + nearest.y * nearest.y).sqrt();
+        if distance < closest.2 {
+            let other = along(&turned[j], &turned[next_j], t);
+            closest = (along(&first[i], &first[next_i], t), Point2D::new(-other.x, -other.y), distance);
+        }
+    }
+    closest
+}
+
+///The Hausdorff distance between the areas of two hulls returned by
+/// any of the algorithms: how far a point of either one can be from
+/// the other one
+///
+/// The distance to a convex area is convex, so it is greatest at a
+/// vertex, and every vertex outside the other hull is a PointLocator
+/// query in O(log n), O((n + m) log(n + m)) in all. For a hull that
+/// approximates another one, this bounds the error of either.
+///
+/// # Panics
+/// Panics when a hull is empty, or a coordinate is NaN
+pub fn hausdorff_distance(a: &[Point2D], b: &[Point2D]) -> f64 {
+    let farthest = |from: &[Point2D], to: &[Point2D]| {
+        let locator = PointLocator::new(to);
+        from.iter()
+            .filter(|vertex| !locator.contains(vertex))
+            .map(|vertex| locator.closest_point(vertex).1)
+            .fold(0.0, f64::max)
+    };
+    farthest(a, b).max(farthest(b, a))
+}
+
+/// The hull vertices counter clockwise without repeats, from the one
+/// with the least y-coordinate (the leftmost one on a tie)
+fn from_lowest(hull: &[Point2D]) -> Vec<Point2D> {
+    let mut vertices = without_repeats(hull);
+    let lowest = (0..vertices.len()).fold(0, |lowest, i| {
+        if (vertices[i].y, vertices[i].x) < (vertices[lowest].y, vertices[lowest].x) {
+            i
+        } else {
+            lowest
+        }
+    });
+    vertices.rotate_left(lowest);
+    vertices
+}
+
+/// Walks two chains from left to right over the x-range they share
+///
+/// Returns the points where they cross and the vertices that
+/// `keep(from the first chain, own height, height of the other chain)`,
+/// in order. At both ends of the range the chains count as having a
+/// vertex.
+fn walk<F: Fn(bool, f64, f64) -> bool>(a: &[Point2D], b: &[Point2D], keep: F) -> Vec<Point2D> {
+    let mut points: Vec<Point2D> = Vec::new();
+    if a.is_empty() || b.is_empty() {
+        return points;
+    }
+    let (start, end) = (a[0].x.max(b[0].x), a[a.len() - 1].x.min(b[b.len() - 1].x));
+    if start > end {
+        return points;
+    }
+    //the next vertices of both chains and the edges under the walk
+    let (mut i, mut j) = (0, 0);
+    let (mut segment_a, mut segment_b) = (0, 0);
+    while i < a.len() && a[i].x < start {
+        i += 1;
+    }
+    while j < b.len() && b[j].x < start {
+        j += 1;
+    }
+    let mut previous: Option<(f64, f64, f64)> = None;
+    loop {
+        let next = |chain: &[Point2D], k: usize| if k < chain.len() { chain[k].x } else { f64::INFINITY };
+        let x = next(a, i).min(next(b, j)).max(start);
+        if x > end {
+            break;
+        }
+        let ends = x == start || x == end;
+        let (at_a, at_b) = (next(a, i) == x, next(b, j) == x);
+        let height_a = if at_a { a[i].y } else { height_at(a, &mut segment_a, x) };
+        let height_b = if at_b { b[j].y } else { height_at(b, &mut segment_b, x) };
+        if let Some((previous_x, previous_a, previous_b)) = previous {
+            let (d0, d1) = (previous_a - previous_b, height_a - height_b);
+            if (d0 < 0.0 && d1 > 0.0) || (d0 > 0.0 && d1 < 0.0) {
+                let t = d0 / (d0 - d1);
+                points.push(Point2D::new(previous_x + t * (x - previous_x), previous_a + t * (height_a - previous_a)));
+            }
+        }
+        let kept_a = (at_a || ends) && keep(true, height_a, height_b);
+        let kept_b = (at_b || ends) && keep(false, height_b, height_a);
+        //the chains meet at a vertex of one of them, which keep may drop
+        if height_a == height_b && !kept_a && !kept_b {
+            points.push(Point2D::new(x, height_a));
+        }
+        //the lower one first at the same x
+        if kept_b && (!kept_a || height_b < height_a) {
+            points.push(Point2D::new(x, height_b));
+        }
+        if kept_a {
+            points.push(Point2D::new(x, height_a));
+        }
+        if kept_b && kept_a && height_b >= height_a {
+            points.push(Point2D::new(x, height_b));
+        }
+        previous = Some((x, height_a, height_b));
+        if at_a {
+            i += 1;
+        }
+        if at_b {
+            j += 1;
+        }
+        if x == end {
+            break;
+        }
+    }
+    points
+}
+
+/// The lower and the upper chain of a hull from left to right, between
+/// the lowest and the highest of its leftmost and rightmost vertices,
+/// or None when the hull has no area
+fn chains(hull: &[Point2D]) -> Option<(Vec<Point2D>, Vec<Point2D>)> {
+    let vertices = without_repeats(hull);
+    if vertices.len() < 3 || twice_signed_area(&vertices) <= 0.0 {
+        return None;
+    }
+    let n = vertices.len();
+    let first = |key: &dyn Fn(&Point2D) -> (f64, f64)| {
+        (0..n).fold(0, |best, i| if key(&vertices[i]) < key(&vertices[best]) { i } else { best })
+    };
+    let left_low = first(&|point| (point.x, point.y));
+    let left_high = first(&|point| (point.x, -point.y));
+    let right_low = first(&|point| (-point.x, point.y));
+    let right_high = first(&|point| (-point.x, -point.y));
+    //counter clockwise the lower chain runs to the right, the upper one back
+    let lower = (0..=(right_low + n - left_low) % n).map(|k| vertices[(left_low + k) % n]).collect();
+    let upper = (0..=(left_high + n - right_high) % n).map(|k| vertices[(left_high + n - k) % n]).collect();
+    Some((lower, upper))
+}
+
+/// The hull vertices counter clockwise without repeated vertices
+fn without_repeats(hull: &[Point2D]) -> Vec<Point2D> {
+    let mut vertices = ConvexHull::new(hull.to_vec()).into_vertices();
+    vertices.dedup();
+    while vertices.len() > 1 && vertices.first() == vertices.last() {
+        vertices.pop();
+    }
+    vertices
+}
+
+/// The height of a chain from left to right at x, moving `segment`
+/// forward to the edge over x
+fn height_at(chain: &[Point2D], segment: &mut usize, x: f64) -> f64 {
+    while *segment + 2 < chain.len() && chain[*segment + 1].x <= x {
+        *segment += 1;
+    }
+    let (from, to) = (chain[*segment], chain[*segment + 1]);
+    if to.x == from.x {
+        return from.y.max(to.y);
+    }
+    from.y + (to.y - from.y) * (x - from.x) / (to.x - from.x)
+}
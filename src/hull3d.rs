@@ -0,0 +1,105 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Convex hulls of points in space
+//!
+//! The hull of a 3D input set is a polyhedron, returned as a list
+//! of triangular faces. Faces are index triples into the input set,
+//! so that meshes can share the vertex buffer with it.
+use points::Point3D;
+use std::collections::HashSet;
+
+///A triangle of the hull, as indices of its vertices in the input set
+///
+/// The vertices are in counter clockwise order seen from outside
+/// the hull, so the normal (b - a) × (c - a) points outwards.
+pub type Face = [usize; 3];
+
+/// Solves the 3D convexhull problem by inserting the points one at a time
+///
+/// The hull starts as a tetrahedron of the first four points that are
+/// not on a plane. Each further point outside of the hull removes the
+/// faces it sees, and the horizon, the edges between seen and unseen
+/// faces, is joined to it by new faces. This takes O(n h) time for h
+/// hull faces.
+///
+/// Points on the plane of a face are not outside of it, so the faces
+/// of flat sides are triangulated only by their corners. An input set
+/// on one plane has no hull faces.
+pub fn convex_hull_3d(input_set: &[Point3D]) -> Vec<Face> {
+    let points = input_set;
+    let tetrahedron = match first_tetrahedron(points) {
+        Some(tetrahedron) => tetrahedron,
+        None => return Vec::new(),
+    };
+    let [a, b, c, d] = tetrahedron;
+    // make every face turn away from the opposite corner
+    let mut faces: Vec<Face> = if orientation(&points[a], &points[b], &points[c], &points[d]) < 0.0 {
+        vec![[a, b, c], [a, d, b], [b, d, c], [c, d, a]]
+    } else {
+        vec![[a, c, b], [a, b, d], [b, c, d], [c, a, d]]
+    };
+
+    for point in 0..points.len() {
+        if tetrahedron.contains(&point) {
+            continue;
+        }
+        let (visible, hidden): (Vec<Face>, Vec<Face>) = faces
+            .into_iter()
+            .partition(|face| sees(points, face, point));
+        faces = hidden;
+        if visible.is_empty() {
+            continue;
+        }
+        // an edge of a seen face is on the horizon when the face
+        // across it is not seen
+        let edges: HashSet<(usize, usize)> = visible
+            .iter()
+            .flat_map(|face| vec![(face[0], face[1]), (face[1], face[2]), (face[2], face[0])])
+            .collect();
+        for &(start, end) in &edges {
+            if !edges.contains(&(end, start)) {
+                faces.push([start, end, point]);
+            }
+        }
+    }
+    faces
+}
+
+/// The first four points of the input set that are not on a plane
+fn first_tetrahedron(points: &[Point3D]) -> Option<[usize; 4]> {
+    let a = 0;
+    let b = (1..points.len()).find(|&i| points[i] != points[a])?;
+    let c = (b + 1..points.len()).find(|&i| {
+        let normal = cross(&difference(&points[a], &points[b]), &difference(&points[a], &points[i]));
+        normal != Point3D::new(0.0, 0.0, 0.0)
+    })?;
+    let d = (c + 1..points.len())
+        .find(|&i| orientation(&points[a], &points[b], &points[c], &points[i]) != 0.0)?;
+    Some([a, b, c, d])
+}
+
+/// Whether the point is strictly outside of the plane of the face
+fn sees(points: &[Point3D], face: &Face, point: usize) -> bool {
+    orientation(&points[face[0]], &points[face[1]], &points[face[2]], &points[point]) > 0.0
+}
+
+/// Six times the signed volume of the tetrahedron abcd, positive when
+/// d is on the side of the plane through a, b and c that the normal
+/// (b - a) × (c - a) points to
+fn orientation(a: &Point3D, b: &Point3D, c: &Point3D, d: &Point3D) -> f64 {
+    let normal = cross(&difference(a, b), &difference(a, c));
+    let offset = difference(a, d);
+    normal.x * offset.x + normal.y * offset.y + normal.z * offset.z
+}
+
+/// The vector from one point to another
+fn difference(from: &Point3D, to: &Point3D) -> Point3D {
+    Point3D::new(to.x - from.x, to.y - from.y, to.z - from.z)
+}
+
+fn cross(u: &Point3D, v: &Point3D) -> Point3D {
+    Point3D::new(u.y * v.z - u.z * v.y, u.z * v.x - u.x * v.z, u.x * v.y - u.y * v.x)
+}
@@ -6,7 +6,13 @@
 //! Collection of functions that operates and
 //! modifies the input set to conveniently
 //! determine the hull points
+use geometry::Orientation;
 use points::*;
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+#[cfg(feature = "allocator_api")]
+use std::alloc::Allocator;
 
 ///Types of shapes used for input sampling
 #[derive(Debug, Copy, Clone)]
@@ -14,6 +20,9 @@ pub enum Shape {
     Triangle,
     Rectangle,
     Circle,
+    /// A regular polygon with the given number of vertices,
+    /// used to control the size of the hull
+    Polygon(u64),
 }
 
 impl Shape {
@@ -23,6 +32,7 @@ impl Shape {
             Shape::Rectangle => 4,
             // we have chosen to have 18 vertex points for a triangle
             Shape::Circle => 18,
+            Shape::Polygon(vertices) => vertices,
         }
     }
 }
@@ -57,6 +67,75 @@ impl Number {
     }
 }
 
+///Orders in which an input set can be presented to an algorithm
+#[cfg(feature = "generators")]
+#[derive(Debug, Copy, Clone)]
+pub enum InputOrder {
+    Shuffled,
+    XSorted,
+    ReverseSorted,
+}
+
+#[cfg(feature = "generators")]
+impl InputOrder {
+    ///Rearranges the points of the input set in this order
+    pub fn arrange<T: CoordNum>(self, input_set: &mut [Point2D<T>]) {
+        match self {
+            InputOrder::Shuffled => thread_rng().shuffle(input_set),
+            InputOrder::XSorted => input_set.sort_by(cmp_xy),
+            InputOrder::ReverseSorted => input_set.sort_by(|a, b| cmp_xy(b, a)),
+        }
+    }
+}
+
+/// Lexicographic comparison on x-coordinate and then y-coordinate
+fn cmp_xy<T: CoordNum>(point_a: &Point2D<T>, point_b: &Point2D<T>) -> Ordering {
+    point_a
+        .x
+        .partial_cmp(&point_b.x)
+        .unwrap()
+        .then(point_a.y.partial_cmp(&point_b.y).unwrap())
+}
+
+///A point of an input set with a NaN or infinite coordinate
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct InvalidPoint {
+    /// position of the point in the input set
+    pub index: usize,
+    /// the offending point
+    pub point: Point2D,
+}
+
+impl fmt::Display for InvalidPoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "point {} of the input set is not finite: ({}, {})",
+            self.index, self.point.x, self.point.y
+        )
+    }
+}
+
+impl Error for InvalidPoint {}
+
+///Checks that every coordinate of the input set is finite
+///
+/// NaN and infinite coordinates make the hull algorithms return
+/// nonsense or panic while sorting. The error names the first
+/// offending point.
+pub fn validate<T: CoordNum>(input_set: &[Point2D<T>]) -> Result<(), InvalidPoint> {
+    match input_set
+        .iter()
+        .position(|point| !point.x.is_finite() || !point.y.is_finite())
+    {
+        Some(index) => Err(InvalidPoint {
+            index,
+            point: input_set[index].to_f64(),
+        }),
+        None => Ok(()),
+    }
+}
+
 ///Converts any raw point tuple to Point2D type
 /// and pushes it to the inputset
 pub fn push(inputset: &mut Vec<Point2D>, point: (f64, f64)) -> &Vec<Point2D> {
@@ -69,40 +148,124 @@ pub fn push(inputset: &mut Vec<Point2D>, point: (f64, f64)) -> &Vec<Point2D> {
 ///left most in case of a match
 /// Sets the pivot point of the inputset
 /// as the first element of the input set.
-pub fn set_pivot(input_set: &mut Vec<Point2D>) -> &Vec<Point2D> {
+pub fn set_pivot<T: CoordNum>(input_set: &mut Vec<Point2D<T>>) -> &Vec<Point2D<T>> {
     //panic if there are no elements in the input_set
-    assert!(input_set.len() > 0);
+    assert!(!input_set.is_empty());
     //set the vertex point to be the
     //first element of the inputset
     for i in 0..input_set.len() {
         if input_set[i].pick_left(&input_set[0]) == &input_set[i] {
-            &input_set.swap(0, i);
+            input_set.swap(0, i);
         }
     }
     input_set
 }
 
+#[cfg(feature = "parallel_sort")]
+extern crate rayon;
+
 ///Sorts the  elements of input set by polar
 ///angle in counter clockwise order around pivot point.
 ///(if more than one point has the same angle, remove all
 ///but the one that is farthest from pivot point)
-pub fn sort_polar_angle_ccw(input_set: &Vec<Point2D>) -> Vec<Point2D> {
-    let mut fat_pt_vec: Vec<Fatpoint2D> = Vec::new();
+///Copies of the pivot point are dropped.
+///
+/// With the `parallel_sort` feature the points are sorted on all
+/// cores with rayon's par_sort_unstable_by.
+pub fn sort_polar_angle_ccw<T: CoordNum>(input_set: &[Point2D<T>]) -> Vec<Point2D<T>> {
+    let mut fat_pt_vec: Vec<Fatpoint2D<T>> = Vec::new();
     //convert all the point2D as FatPoints
     if let Some((first, elements)) = input_set.split_first() {
         fat_pt_vec = elements
             .iter()
-            .map(|point| Fatpoint2D::new(point, &first))
+            .filter(|point| *point != first)
+            .map(|point| Fatpoint2D::new(point, first))
             .collect();
     };
     //sort the fatpoint vec
+    #[cfg(not(feature = "parallel_sort"))]
+    {
+        fat_pt_vec.sort_by(|a, b| b.partial_cmp_distance(a).unwrap());
+        fat_pt_vec.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    }
+    //an unstable sort needs the distance as the tie breaker
+    #[cfg(feature = "parallel_sort")]
+    {
+        use self::rayon::prelude::*;
+        fat_pt_vec.par_sort_unstable_by(|a, b| {
+            a.partial_cmp(b).unwrap().then(b.partial_cmp_distance(a).unwrap())
+        });
+    }
+    fat_pt_vec.dedup_by(|a, b| a.eq_polar_angle(b));
+    //convert back to point2d
+    let mut output = Vec::new();
+    if let Some(first) = input_set.first() {
+        output.push(Fatpoint2D::new(first, first).to_point())
+    }
+    for fat_point in fat_pt_vec.iter() {
+        output.push(fat_point.to_point());
+    }
+    output
+}
+
+///Sorts the elements of input set by polar angle in counter
+/// clockwise order around the pivot point like sort_polar_angle_ccw,
+/// comparing by cross products instead of computed angles
+///
+/// No angle is computed, so there are no calls to atan2 and points at
+/// the same angle are found exactly. Of these only the one farthest
+/// from the pivot point is kept, and copies of the pivot point are
+/// dropped.
+pub fn sort_polar_angle_cross<T: CoordNum>(input_set: &[Point2D<T>]) -> Vec<Point2D<T>> {
+    let mut output: Vec<Point2D<T>> = Vec::with_capacity(input_set.len());
+    let (pivot, elements) = match input_set.split_first() {
+        Some(split) => split,
+        None => return output,
+    };
+    let mut sorted: Vec<Point2D<T>> = elements.iter().filter(|point| *point != pivot).cloned().collect();
+    sorted.sort_unstable_by(|a, b| cmp_polar_angle(pivot, a, b));
+    sorted.dedup_by(|a, b| T::orientation(pivot, a, b) == Orientation::Collinear);
+    output.push(*pivot);
+    output.extend(sorted);
+    output
+}
+
+///Compares two points by their polar angle around a pivot point with
+/// no point below it, and by their distance from it on a tie, the
+/// farther point first
+///
+/// The points at counter clockwise smaller angles are less. Copies of
+/// the pivot point are less than all other points.
+pub fn cmp_polar_angle<T: CoordNum>(pivot: &Point2D<T>, a: &Point2D<T>, b: &Point2D<T>) -> Ordering {
+    if a == pivot || b == pivot {
+        return (b == pivot).cmp(&(a == pivot));
+    }
+    match T::orientation(pivot, a, b) {
+        Orientation::CounterClockwise => Ordering::Less,
+        Orientation::Clockwise => Ordering::Greater,
+        Orientation::Collinear => T::dot(pivot, b, b).partial_cmp(&T::dot(pivot, a, a)).unwrap(),
+    }
+}
+
+///Same as sort_polar_angle_ccw, with the temporary fat points and
+/// the output allocated by the given allocator
+///
+/// Requires the `allocator_api` feature, which needs a nightly compiler.
+#[cfg(feature = "allocator_api")]
+pub fn sort_polar_angle_ccw_in<T: CoordNum, A: Allocator + Clone>(input_set: &[Point2D<T>], alloc: A) -> Vec<Point2D<T>, A> {
+    let mut fat_pt_vec: Vec<Fatpoint2D<T>, A> = Vec::new_in(alloc.clone());
+    //convert all the point2D as FatPoints
+    if let Some((first, elements)) = input_set.split_first() {
+        fat_pt_vec.extend(elements.iter().filter(|point| *point != first).map(|point| Fatpoint2D::new(point, first)));
+    };
+    //sort the fatpoint vec
     fat_pt_vec.sort_by(|a, b| b.partial_cmp_distance(a).unwrap());
     fat_pt_vec.sort_by(|a, b| a.partial_cmp(b).unwrap());
     fat_pt_vec.dedup_by(|a, b| a.eq_polar_angle(b));
     //convert back to point2d
-    let mut output = Vec::new();
-    if let Some(first) = input_set.get(0) {
-        output.push(Fatpoint2D::new(first, &first).to_point())
+    let mut output = Vec::with_capacity_in(fat_pt_vec.len() + 1, alloc);
+    if let Some(first) = input_set.first() {
+        output.push(*first)
     }
     for fat_point in fat_pt_vec.iter() {
         output.push(fat_point.to_point());
@@ -110,6 +273,26 @@ pub fn sort_polar_angle_ccw(input_set: &Vec<Point2D>) -> Vec<Point2D> {
     output
 }
 
+///Same as sort_polar_angle_cross, with the sorted points allocated by
+/// the given allocator
+///
+/// Requires the `allocator_api` feature, which needs a nightly compiler.
+#[cfg(feature = "allocator_api")]
+pub fn sort_polar_angle_cross_in<T: CoordNum, A: Allocator + Clone>(input_set: &[Point2D<T>], alloc: A) -> Vec<Point2D<T>, A> {
+    let mut output: Vec<Point2D<T>, A> = Vec::with_capacity_in(input_set.len(), alloc.clone());
+    let (pivot, elements) = match input_set.split_first() {
+        Some(split) => split,
+        None => return output,
+    };
+    let mut sorted: Vec<Point2D<T>, A> = Vec::new_in(alloc);
+    sorted.extend(elements.iter().filter(|point| *point != pivot).cloned());
+    sorted.sort_unstable_by(|a, b| cmp_polar_angle(pivot, a, b));
+    sorted.dedup_by(|a, b| T::orientation(pivot, a, b) == Orientation::Collinear);
+    output.push(*pivot);
+    output.extend(sorted);
+    output
+}
+
 pub fn jarvis_binary_search(
     next_to_top: &Point2D,
     top: &Point2D,
@@ -121,7 +304,7 @@ pub fn jarvis_binary_search(
     // println!("top {:?}",top);
     // println!("sub_hull_set {:?}",sub_hull_set);
     for point in sub_hull_set {
-        let orientation = find_angle(&next_to_top, &top, &point);
+        let orientation = find_angle(next_to_top, top, point);
         // if next_to_top == &Point2D::new(2.,0.) {
         //     println!("next_to_top {:?}",next_to_top);
         //     println!("top {:?}",top);
@@ -138,10 +321,15 @@ pub fn jarvis_binary_search(
 
 /// Generates set of points with predetermined number
 /// of hull vertices and in the given shape
+#[cfg(feature = "generators")]
 use std::f64;
 // use plots::*;
+#[cfg(feature = "generators")]
 extern crate rand;
+#[cfg(feature = "generators")]
 use self::rand::prelude::*;
+#[cfg(feature = "generators")]
+use kdtree::KdTree;
 /// Generate the input set for convex hull
 ///
 /// Idea is derived from this link
@@ -150,6 +338,7 @@ use self::rand::prelude::*;
 /// # Panics
 /// Panics if the total points is less than the number of vertices
 // pub fn generate(total_points: u64, number_of_vertex: u64) -> Vec<Point2D> {
+#[cfg(feature = "generators")]
 pub fn generate(shape: Shape, sample_size: Number) -> Vec<Point2D> {
     let total_points = sample_size.val();
     let number_of_vertex = shape.num_of_vertices();
@@ -185,4 +374,165 @@ pub fn generate(shape: Shape, sample_size: Number) -> Vec<Point2D> {
     }
     // draw_plot(&output);
     output
-}
\ No newline at end of file
+}
+
+/// Generates a moving input set: the points of `generate` with
+/// random velocities of at most `max_speed` units per time step
+#[cfg(feature = "generators")]
+pub fn generate_moving(shape: Shape, sample_size: Number, max_speed: f64) -> Vec<MovingPoint> {
+    let mut rng = thread_rng();
+    generate(shape, sample_size)
+        .into_iter()
+        .map(|position| {
+            // random heading and speed for each point
+            let theta = 2.0 * f64::consts::PI * rng.gen::<f64>();
+            let speed = max_speed * rng.gen::<f64>();
+            MovingPoint::new(position, Point2D::new(speed * theta.cos(), speed * theta.sin()))
+        })
+        .collect()
+}
+
+/// Positions of the moving points at each of the given number of
+/// time steps, starting at time zero and dt apart
+pub fn time_steps(moving_points: &[MovingPoint], steps: usize, dt: f64) -> Vec<Vec<Point2D>> {
+    (0..steps)
+        .map(|step| {
+            moving_points
+                .iter()
+                .map(|point| point.at(step as f64 * dt))
+                .collect()
+        })
+        .collect()
+}
+
+/// Generates the trajectory of a 2D random walk
+///
+/// The walk starts at the origin and every step moves a fixed
+/// distance in a random direction, so consecutive points are
+/// highly correlated and the hull is small relative to the extent
+/// of the walk.
+#[cfg(feature = "generators")]
+pub fn random_walk(sample_size: Number, step: f64) -> Vec<Point2D> {
+    let mut rng = thread_rng();
+    let mut position = Point2D::new(0.0, 0.0);
+    let mut output: Vec<Point2D> = Vec::new();
+    for _ in 0..sample_size.val() {
+        output.push(position);
+        let theta = 2.0 * f64::consts::PI * rng.gen::<f64>();
+        position = Point2D::new(position.x + step * theta.cos(), position.y + step * theta.sin());
+    }
+    output
+}
+
+/// Generates blue noise points with Poisson-disk sampling
+///
+/// The points fill a width x height rectangle centered at the origin
+/// and no two of them are closer than `min_distance`. Unlike uniform
+/// random points they look like sensor placements or graphics sample
+/// patterns: evenly spread with no clumps.
+///
+/// The algorithm is from Robert Bridson's paper
+/// "Fast Poisson Disk Sampling in Arbitrary Dimensions" (2007)
+///
+/// # Panics
+/// Panics if the minimum distance is not positive
+#[cfg(feature = "generators")]
+pub fn poisson_disk(width: f64, height: f64, min_distance: f64) -> Vec<Point2D> {
+    assert!(min_distance > 0.0);
+    // number of candidates tried around a point before giving up on it
+    let attempts = 30;
+    let mut tree = KdTree::new();
+
+    let mut rng = thread_rng();
+    let mut output: Vec<Point2D> = Vec::new();
+    let mut active: Vec<usize> = Vec::new();
+    let first = Point2D::new(
+        width * (rng.gen::<f64>() - 0.5),
+        height * (rng.gen::<f64>() - 0.5),
+    );
+    tree.insert(first);
+    output.push(first);
+    active.push(0);
+
+    while !active.is_empty() {
+        let active_index = rng.gen_range(0, active.len());
+        let center = output[active[active_index]];
+        let mut found = false;
+        for _ in 0..attempts {
+            // candidate in the ring between r and 2r around the center
+            let theta = 2.0 * f64::consts::PI * rng.gen::<f64>();
+            let radius = min_distance * (1.0 + rng.gen::<f64>());
+            let candidate = Point2D::new(
+                center.x + radius * theta.cos(),
+                center.y + radius * theta.sin(),
+            );
+            if candidate.x.abs() > width / 2.0 || candidate.y.abs() > height / 2.0 {
+                continue;
+            }
+            let too_close = tree
+                .nearest(&candidate)
+                .is_some_and(|(_, point)| {
+                    (point.x - candidate.x).powi(2) + (point.y - candidate.y).powi(2) < min_distance.powi(2)
+                });
+            if !too_close {
+                tree.insert(candidate);
+                active.push(output.len());
+                output.push(candidate);
+                found = true;
+                break;
+            }
+        }
+        if !found {
+            active.swap_remove(active_index);
+        }
+    }
+    output
+}
+
+#[cfg(feature = "image")]
+extern crate png;
+
+/// Generates the input set of a binary mask stored as a PNG image
+///
+/// Every pixel whose brightness is at least `threshold` (0 - 255)
+/// becomes a point at its (column, row) pixel coordinate, the same
+/// coordinates OpenCV uses for the mask. Requires the `image` feature.
+#[cfg(feature = "image")]
+pub fn from_png<P: AsRef<::std::path::Path>>(
+    path: P,
+    threshold: u8,
+) -> Result<Vec<Point2D>, png::DecodingError> {
+    let file = ::std::fs::File::open(path)?;
+    let mut decoder = png::Decoder::new(file);
+    // palette and low bit depth images are expanded to 8 bit channels
+    decoder.set(png::Transformations::EXPAND);
+    let (info, mut reader) = decoder.read_info()?;
+    let mut buffer = vec![0; info.buffer_size()];
+    reader.next_frame(&mut buffer)?;
+
+    let channels = match info.color_type {
+        png::ColorType::Grayscale | png::ColorType::Indexed => 1,
+        png::ColorType::GrayscaleAlpha => 2,
+        png::ColorType::RGB => 3,
+        png::ColorType::RGBA => 4,
+    };
+    // the high byte of 16 bit samples is enough for a threshold
+    let bytes_per_sample = if info.bit_depth == png::BitDepth::Sixteen { 2 } else { 1 };
+    // alpha is ignored, only the colour channels give the brightness
+    let colour_channels = if channels == 2 || channels == 4 { channels - 1 } else { channels };
+
+    let mut output: Vec<Point2D> = Vec::new();
+    for row in 0..info.height as usize {
+        let line = &buffer[row * info.line_size..(row + 1) * info.line_size];
+        for column in 0..info.width as usize {
+            let pixel = &line[column * channels * bytes_per_sample..];
+            let brightness = (0..colour_channels)
+                .map(|channel| pixel[channel * bytes_per_sample] as u32)
+                .sum::<u32>() / colour_channels as u32;
+            if brightness >= threshold as u32 {
+                output.push(Point2D::new(column as f64, row as f64));
+            }
+        }
+    }
+    Ok(output)
+}
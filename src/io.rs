@@ -0,0 +1,267 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Readers for input sets stored in files
+//!
+//! Three formats are understood:
+//!
+//! * CSV: one `x,y` point per line. Empty lines, lines starting
+//!   with `#` and a header line are skipped.
+//! * Binary (`.bin`): the x and y coordinate of every point as
+//!   little endian f64 values, without any header.
+//! * GeoJSON (`.geojson`): the vertices of a Polygon (its outer ring),
+//!   LineString or MultiPoint geometry, which may be wrapped in a
+//!   Feature or be the first feature of a FeatureCollection.
+//!   Reading GeoJSON requires the `geojson` feature.
+//!
+//! Files ending with `.gz` are decompressed while they are read,
+//! so multi-gigabyte inputs don't have to be unpacked to disk first.
+//! Reading gzip files requires the `gzip` feature.
+//!
+//! Files too large to be read into memory have their hull found
+//! chunk by chunk with `hull_of_file`.
+use convexhull::convex_hull_of;
+use points::Point2D;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+
+#[cfg(feature = "gzip")]
+extern crate flate2;
+#[cfg(feature = "geojson")]
+extern crate serde_json;
+
+///File formats of input sets
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Format {
+    Csv,
+    Binary,
+    GeoJson,
+}
+
+impl Format {
+    ///Finds the format from the file extension, ignoring a `.gz` suffix
+    pub fn of(path: &Path) -> Option<Format> {
+        let path = if is_gzip(path) { Path::new(path.file_stem()?) } else { path };
+        match path.extension()?.to_str()? {
+            "csv" => Some(Format::Csv),
+            "bin" => Some(Format::Binary),
+            "geojson" => Some(Format::GeoJson),
+            _ => None,
+        }
+    }
+}
+
+/// True for paths ending with `.gz`
+fn is_gzip(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == "gz")
+}
+
+///Reads the input set stored in a file
+///
+/// The format is picked from the file extension (`.csv`, `.bin`,
+/// `.geojson`, optionally followed by `.gz`).
+pub fn read_points<P: AsRef<Path>>(path: P) -> io::Result<Vec<Point2D>> {
+    let path = path.as_ref();
+    let format = Format::of(path).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("unknown input set format: {}", path.display()),
+        )
+    })?;
+    let reader = open(path)?;
+    match format {
+        Format::Csv => read_csv(BufReader::new(reader)),
+        Format::Binary => read_binary(BufReader::new(reader)),
+        Format::GeoJson => read_geojson(BufReader::new(reader)),
+    }
+}
+
+/// Opens a file, decompressing it on the fly when it is gzipped
+pub fn open(path: &Path) -> io::Result<Box<dyn Read>> {
+    let file = File::open(path)?;
+    if is_gzip(path) {
+        open_gzip(file)
+    } else {
+        Ok(Box::new(file))
+    }
+}
+
+#[cfg(feature = "gzip")]
+fn open_gzip(file: File) -> io::Result<Box<dyn Read>> {
+    Ok(Box::new(flate2::read::MultiGzDecoder::new(file)))
+}
+
+#[cfg(not(feature = "gzip"))]
+fn open_gzip(_file: File) -> io::Result<Box<dyn Read>> {
+    Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "reading gzip files requires the gzip feature",
+    ))
+}
+
+///Finds the hull of the input set stored in a file without reading
+/// all of it into memory
+///
+/// CSV and binary files are read `chunk_size` points at a time, and
+/// the hull of every chunk together with the hull of the chunks before
+/// it is found with `convexhull::convex_hull_of`. Only one chunk and
+/// the current hull are in memory, so the file may be larger than the
+/// RAM. GeoJSON files hold a single geometry and are read whole.
+///
+/// The hull is in the order of `convexhull::hull_indices`.
+pub fn hull_of_file<P: AsRef<Path>>(path: P, chunk_size: usize) -> io::Result<Vec<Point2D>> {
+    let path = path.as_ref();
+    let format = Format::of(path).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("unknown input set format: {}", path.display()),
+        )
+    })?;
+    let chunk_size = chunk_size.max(1);
+    let mut hull_points: Vec<Point2D> = Vec::new();
+    let mut chunk: Vec<Point2D> = Vec::with_capacity(chunk_size);
+    let merge = |hull_points: &mut Vec<Point2D>, chunk: &mut Vec<Point2D>| {
+        chunk.append(hull_points);
+        *hull_points = convex_hull_of(chunk);
+        chunk.clear();
+    };
+    let mut reader = BufReader::new(open(path)?);
+    match format {
+        Format::Csv => for_each_csv_point(reader, |point| {
+            chunk.push(point);
+            if chunk.len() == chunk_size {
+                merge(&mut hull_points, &mut chunk);
+            }
+        })?,
+        Format::Binary => {
+            let mut buffer = [0u8; 16];
+            while let Some(point) = read_binary_point(&mut reader, &mut buffer)? {
+                chunk.push(point);
+                if chunk.len() == chunk_size {
+                    merge(&mut hull_points, &mut chunk);
+                }
+            }
+        }
+        Format::GeoJson => chunk = read_geojson(reader)?,
+    }
+    merge(&mut hull_points, &mut chunk);
+    Ok(hull_points)
+}
+
+///Reads `x,y` points, one per line
+pub fn read_csv<R: BufRead>(reader: R) -> io::Result<Vec<Point2D>> {
+    let mut output: Vec<Point2D> = Vec::new();
+    for_each_csv_point(reader, |point| output.push(point))?;
+    Ok(output)
+}
+
+/// Calls `f` with every point of a CSV input, in order
+fn for_each_csv_point<R: BufRead, F: FnMut(Point2D)>(reader: R, mut f: F) -> io::Result<()> {
+    for (number, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match parse_csv_point(line) {
+            Some(point) => f(point),
+            // the first line may be a header like "x,y"
+            None if number == 0 => continue,
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {} is not a point: {}", number + 1, line),
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parses one `x,y` line
+fn parse_csv_point(line: &str) -> Option<Point2D> {
+    let mut values = line.split(',').map(|value| value.trim().parse::<f64>());
+    match (values.next(), values.next(), values.next()) {
+        (Some(Ok(x)), Some(Ok(y)), None) => Some(Point2D::new(x, y)),
+        _ => None,
+    }
+}
+
+///Reads points stored as pairs of little endian f64 coordinates
+pub fn read_binary<R: Read>(mut reader: R) -> io::Result<Vec<Point2D>> {
+    let mut output: Vec<Point2D> = Vec::new();
+    let mut buffer = [0u8; 16];
+    loop {
+        match read_binary_point(&mut reader, &mut buffer)? {
+            Some(point) => output.push(point),
+            None => return Ok(output),
+        }
+    }
+}
+
+/// Reads the next point, or None at the end of the input
+fn read_binary_point<R: Read>(reader: &mut R, buffer: &mut [u8; 16]) -> io::Result<Option<Point2D>> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        let read = reader.read(&mut buffer[filled..])?;
+        if read == 0 {
+            break;
+        }
+        filled += read;
+    }
+    match filled {
+        0 => Ok(None),
+        16 => {
+            let mut x = [0u8; 8];
+            let mut y = [0u8; 8];
+            x.copy_from_slice(&buffer[..8]);
+            y.copy_from_slice(&buffer[8..]);
+            Ok(Some(Point2D::new(f64::from_le_bytes(x), f64::from_le_bytes(y))))
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "the input ends in the middle of a point",
+        )),
+    }
+}
+
+///Reads the vertices of the first geometry in a GeoJSON document
+///
+/// The closing vertex of a polygon ring is dropped.
+#[cfg(feature = "geojson")]
+pub fn read_geojson<R: Read>(reader: R) -> io::Result<Vec<Point2D>> {
+    let document: serde_json::Value = serde_json::from_reader(reader)?;
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+    let geometry = match document["type"].as_str() {
+        Some("FeatureCollection") => &document["features"][0]["geometry"],
+        Some("Feature") => &document["geometry"],
+        _ => &document,
+    };
+    let coordinates = match geometry["type"].as_str() {
+        Some("Polygon") => &geometry["coordinates"][0],
+        Some("LineString") | Some("MultiPoint") => &geometry["coordinates"],
+        _ => return Err(invalid("expected a Polygon, LineString or MultiPoint geometry")),
+    };
+    let mut output: Vec<Point2D> = Vec::new();
+    for position in coordinates.as_array().ok_or_else(|| invalid("the coordinates are not a list"))? {
+        match (position[0].as_f64(), position[1].as_f64()) {
+            (Some(x), Some(y)) => output.push(Point2D::new(x, y)),
+            _ => return Err(invalid("a position is not a pair of numbers")),
+        }
+    }
+    if geometry["type"].as_str() == Some("Polygon") && output.len() > 1 && output.first() == output.last() {
+        output.pop();
+    }
+    Ok(output)
+}
+
+#[cfg(not(feature = "geojson"))]
+fn read_geojson<R: Read>(_reader: R) -> io::Result<Vec<Point2D>> {
+    Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "reading GeoJSON files requires the geojson feature",
+    ))
+}
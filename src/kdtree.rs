@@ -0,0 +1,285 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! A kd-tree spatial index over points in the plane
+//!
+//! The tree answers nearest neighbour and range queries, and finds
+//! the points inside a hull, without looking at every point. Points can still be added after the
+//! tree is built, so generators that grow an input set one point
+//! at a time can query it as they go.
+//!
+//! Queries return the indices of the points in the order they
+//! were given to the tree.
+use hull::PointLocator;
+use points::{bounding_box, Point2D};
+
+#[derive(Debug, Clone)]
+struct Node {
+    point: Point2D,
+    index: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+///A kd-tree splitting on x and y in turns
+///
+/// # Panics
+/// Building panics when a coordinate is NaN
+#[derive(Debug, Clone, Default)]
+pub struct KdTree {
+    nodes: Vec<Node>,
+    root: Option<usize>,
+}
+
+/// x on even depths and y on odd depths
+fn coordinate(point: &Point2D, depth: usize) -> f64 {
+    if depth.is_multiple_of(2) {
+        point.x
+    } else {
+        point.y
+    }
+}
+
+fn distance_squared(a: &Point2D, b: &Point2D) -> f64 {
+    (a.x - b.x).powi(2) + (a.y - b.y).powi(2)
+}
+
+impl KdTree {
+    ///An empty tree
+    pub fn new() -> KdTree {
+        KdTree {
+            nodes: Vec::new(),
+            root: None,
+        }
+    }
+
+    ///Builds a balanced tree holding the points, which keep
+    /// their indices in the slice
+    pub fn build(points: &[Point2D]) -> KdTree {
+        let mut tree = KdTree {
+            nodes: Vec::with_capacity(points.len()),
+            root: None,
+        };
+        let mut indices: Vec<usize> = (0..points.len()).collect();
+        tree.root = tree.build_node(points, &mut indices, 0);
+        tree
+    }
+
+    /// Splits the indices at the median of the depth's coordinate
+    fn build_node(&mut self, points: &[Point2D], indices: &mut [usize], depth: usize) -> Option<usize> {
+        if indices.is_empty() {
+            return None;
+        }
+        let median = indices.len() / 2;
+        indices.select_nth_unstable_by(median, |&a, &b| {
+            coordinate(&points[a], depth)
+                .partial_cmp(&coordinate(&points[b], depth))
+                .unwrap()
+        });
+        let index = indices[median];
+        let node = self.nodes.len();
+        self.nodes.push(Node {
+            point: points[index],
+            index,
+            left: None,
+            right: None,
+        });
+        let (lower, upper) = indices.split_at_mut(median);
+        let left = self.build_node(points, lower, depth + 1);
+        let right = self.build_node(points, &mut upper[1..], depth + 1);
+        self.nodes[node].left = left;
+        self.nodes[node].right = right;
+        Some(node)
+    }
+
+    ///Number of points in the tree
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    ///True when the tree holds no point
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    ///Adds a point and returns its index
+    ///
+    /// The tree is not rebalanced, so points added in sorted
+    /// order make the queries slower.
+    pub fn insert(&mut self, point: Point2D) -> usize {
+        let index = self.nodes.len();
+        self.nodes.push(Node {
+            point,
+            index,
+            left: None,
+            right: None,
+        });
+        let mut current = match self.root {
+            Some(root) => root,
+            None => {
+                self.root = Some(index);
+                return index;
+            }
+        };
+        let mut depth = 0;
+        loop {
+            let goes_left = coordinate(&point, depth) < coordinate(&self.nodes[current].point, depth);
+            let child = if goes_left {
+                &mut self.nodes[current].left
+            } else {
+                &mut self.nodes[current].right
+            };
+            match *child {
+                Some(next) => current = next,
+                None => {
+                    *child = Some(index);
+                    return index;
+                }
+            }
+            depth += 1;
+        }
+    }
+
+    ///The index and position of the point closest to the target,
+    /// or None when the tree is empty
+    pub fn nearest(&self, target: &Point2D) -> Option<(usize, Point2D)> {
+        let mut best: Option<(usize, f64)> = None;
+        self.nearest_in(self.root, target, 0, &mut best);
+        best.map(|(node, _)| (self.nodes[node].index, self.nodes[node].point))
+    }
+
+    fn nearest_in(&self, node: Option<usize>, target: &Point2D, depth: usize, best: &mut Option<(usize, f64)>) {
+        let node = match node {
+            Some(node) => node,
+            None => return,
+        };
+        let Node {
+            ref point,
+            left,
+            right,
+            ..
+        } = self.nodes[node];
+        let distance = distance_squared(point, target);
+        if best.is_none_or(|(_, best_distance)| distance < best_distance) {
+            *best = Some((node, distance));
+        }
+        let offset = coordinate(target, depth) - coordinate(point, depth);
+        let (near, far) = if offset < 0.0 { (left, right) } else { (right, left) };
+        self.nearest_in(near, target, depth + 1, best);
+        // the far side can only be closer than the splitting line
+        if best.is_none_or(|(_, best_distance)| offset.powi(2) < best_distance) {
+            self.nearest_in(far, target, depth + 1, best);
+        }
+    }
+
+    ///Indices of the k points closest to the target, closest first
+    pub fn k_nearest(&self, target: &Point2D, k: usize) -> Vec<usize> {
+        self.k_nearest_where(target, k, |_| true)
+    }
+
+    ///Indices of the k points closest to the target among those whose
+    /// index passes the filter, closest first
+    pub fn k_nearest_where<F: Fn(usize) -> bool>(&self, target: &Point2D, k: usize, keep: F) -> Vec<usize> {
+        let mut best: Vec<(f64, usize)> = Vec::new();
+        if k > 0 {
+            self.k_nearest_in(self.root, target, 0, k, &keep, &mut best);
+        }
+        best.into_iter().map(|(_, node)| self.nodes[node].index).collect()
+    }
+
+    /// Keeps the k closest nodes in `best`, sorted by distance
+    fn k_nearest_in<F: Fn(usize) -> bool>(
+        &self,
+        node: Option<usize>,
+        target: &Point2D,
+        depth: usize,
+        k: usize,
+        keep: &F,
+        best: &mut Vec<(f64, usize)>,
+    ) {
+        let node = match node {
+            Some(node) => node,
+            None => return,
+        };
+        let Node {
+            ref point,
+            index,
+            left,
+            right,
+        } = self.nodes[node];
+        let distance = distance_squared(point, target);
+        if keep(index) && (best.len() < k || distance < best[k - 1].0) {
+            let position = best.iter().position(|&(other, _)| other > distance).unwrap_or(best.len());
+            best.insert(position, (distance, node));
+            best.truncate(k);
+        }
+        let offset = coordinate(target, depth) - coordinate(point, depth);
+        let (near, far) = if offset < 0.0 { (left, right) } else { (right, left) };
+        self.k_nearest_in(near, target, depth + 1, k, keep, best);
+        if best.len() < k || offset.powi(2) < best[k - 1].0 {
+            self.k_nearest_in(far, target, depth + 1, k, keep, best);
+        }
+    }
+
+    ///Indices of the points closer to the center than the radius
+    pub fn within_radius(&self, center: &Point2D, radius: f64) -> Vec<usize> {
+        let mut output: Vec<usize> = Vec::new();
+        let min = Point2D::new(center.x - radius, center.y - radius);
+        let max = Point2D::new(center.x + radius, center.y + radius);
+        self.range_in(self.root, &min, &max, 0, &mut |node: &Node| {
+            if distance_squared(&node.point, center) < radius.powi(2) {
+                output.push(node.index);
+            }
+        });
+        output
+    }
+
+    ///Indices of the points inside the axis aligned rectangle
+    /// from `min` to `max`, borders included
+    pub fn range(&self, min: &Point2D, max: &Point2D) -> Vec<usize> {
+        let mut output: Vec<usize> = Vec::new();
+        self.range_in(self.root, min, max, 0, &mut |node: &Node| output.push(node.index));
+        output
+    }
+
+    ///Indices of the points inside the hull or on its boundary, to
+    /// filter the points by a hull in either orientation
+    ///
+    /// Only the points in the bounding box of the hull are tested
+    /// against it, in O(log h) each.
+    pub fn inside_hull(&self, hull: &[Point2D]) -> Vec<usize> {
+        if hull.is_empty() {
+            return Vec::new();
+        }
+        let (min, max) = bounding_box(hull);
+        let locator = PointLocator::new(hull);
+        let mut output: Vec<usize> = Vec::new();
+        self.range_in(self.root, &min, &max, 0, &mut |node: &Node| {
+            if locator.contains(&node.point) {
+                output.push(node.index);
+            }
+        });
+        output
+    }
+
+    /// Visits every node inside the rectangle
+    fn range_in<F: FnMut(&Node)>(&self, node: Option<usize>, min: &Point2D, max: &Point2D, depth: usize, visit: &mut F) {
+        let node = match node {
+            Some(node) => &self.nodes[node],
+            None => return,
+        };
+        let point = &node.point;
+        if point.x >= min.x && point.x <= max.x && point.y >= min.y && point.y <= max.y {
+            visit(node);
+        }
+        let split = coordinate(point, depth);
+        if coordinate(min, depth) <= split {
+            self.range_in(node.left, min, max, depth + 1, visit);
+        }
+        if coordinate(max, depth) >= split {
+            self.range_in(node.right, min, max, depth + 1, visit);
+        }
+    }
+}
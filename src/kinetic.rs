@@ -0,0 +1,201 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Hulls of moving points
+//!
+//! A kinetic data structure keeps the hull of points moving with
+//! constant velocities up to date as time advances. Instead of being
+//! recomputed at every time step, the hull is only repaired when it
+//! changes, which is when a point crosses the line of a hull edge.
+use convexhull::hull_indices;
+use points::*;
+
+///The hull of moving points at the current time
+///
+/// Every hull edge has a certificate: no point is right of the edge
+/// until the first time one of the points crosses its line, which is
+/// a root of a quadratic polynomial in time. Advancing the time
+/// processes the failing certificates in order. A point crossing an
+/// edge becomes a vertex between its ends, and a vertex whose
+/// neighbour crosses the edge on its other side leaves the hull.
+/// Only the certificates of the new edges are computed again, in
+/// O(n) time each, so an event takes O(n + h) time.
+///
+/// Degenerate events, like a triangle hull collapsing onto a line,
+/// and hulls with fewer than three vertices are recomputed.
+#[derive(Debug, Clone)]
+pub struct KineticHull {
+    points: Vec<MovingPoint>,
+    time: f64,
+    // the vertices as indices of the points, counter clockwise
+    hull: Vec<usize>,
+    // when the certificate of each edge (hull[i], hull[i + 1]) fails,
+    // and the point crossing it
+    failures: Vec<Option<(f64, usize)>>,
+    events: usize,
+}
+
+impl KineticHull {
+    ///The hull of the points at the given time
+    pub fn new(points: Vec<MovingPoint>, time: f64) -> KineticHull {
+        let mut kinetic_hull = KineticHull {
+            points,
+            time,
+            hull: Vec::new(),
+            failures: Vec::new(),
+            events: 0,
+        };
+        kinetic_hull.rebuild(time);
+        kinetic_hull
+    }
+
+    ///Moves the points forward to the given time, repairing the hull
+    /// at every event on the way
+    pub fn advance(&mut self, time: f64) {
+        loop {
+            if self.hull.len() < 3 {
+                self.rebuild(time);
+                break;
+            }
+            let next = self
+                .failures
+                .iter()
+                .enumerate()
+                .filter_map(|(edge, failure)| failure.map(|(at, point)| (at, edge, point)))
+                .filter(|&(at, _, _)| at <= time)
+                .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+            match next {
+                Some((at, edge, point)) => self.process_event(at, edge, point),
+                None => break,
+            }
+        }
+        self.time = time;
+    }
+
+    ///The current time
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    ///The number of events processed so far
+    pub fn events(&self) -> usize {
+        self.events
+    }
+
+    ///The hull at the current time, in counter clockwise order
+    pub fn hull(&self) -> Vec<Point2D> {
+        self.hull.iter().map(|&index| self.points[index].at(self.time)).collect()
+    }
+
+    /// Repairs the hull when `point` crosses the line of an edge
+    fn process_event(&mut self, time: f64, edge: usize, point: usize) {
+        self.time = time;
+        self.events += 1;
+        let count = self.hull.len();
+        let (next, previous) = ((edge + 2) % count, (edge + count - 1) % count);
+        if count > 3 && point == self.hull[next] {
+            // the end of the edge is no longer a vertex
+            self.remove_vertex((edge + 1) % count);
+        } else if count > 3 && point == self.hull[previous] {
+            self.remove_vertex(edge);
+        } else if !self.hull.contains(&point) && self.between(edge, point) {
+            self.hull.insert(edge + 1, point);
+            self.failures.insert(edge + 1, None);
+            self.failures[edge] = self.failure(edge);
+            self.failures[edge + 1] = self.failure(edge + 1);
+        } else {
+            // slightly after the event, so that the crossing counts
+            // and the certificates fail later
+            self.time = time + 1e-9 * (1.0 + time.abs());
+            let later = self.time;
+            self.rebuild(later);
+        }
+    }
+
+    /// Whether the point is between the ends of an edge, along it
+    fn between(&self, edge: usize, point: usize) -> bool {
+        let start = self.points[self.hull[edge]].at(self.time);
+        let end = self.points[self.hull[(edge + 1) % self.hull.len()]].at(self.time);
+        let point = self.points[point].at(self.time);
+        let along = (end.x - start.x) * (point.x - start.x) + (end.y - start.y) * (point.y - start.y);
+        along > 0.0 && along < (end.x - start.x).powi(2) + (end.y - start.y).powi(2)
+    }
+
+    /// Removes a vertex and finds the certificate of the edge replacing
+    /// the two edges at it
+    fn remove_vertex(&mut self, vertex: usize) {
+        self.hull.remove(vertex);
+        self.failures.remove(vertex);
+        let edge = (vertex + self.hull.len() - 1) % self.hull.len();
+        self.failures[edge] = self.failure(edge);
+    }
+
+    /// Computes the hull from scratch at the given time
+    fn rebuild(&mut self, time: f64) {
+        let positions: Vec<Point2D> = self.points.iter().map(|point| point.at(time)).collect();
+        self.hull = hull_indices(&positions);
+        self.failures = (0..self.hull.len()).map(|edge| self.failure(edge)).collect();
+    }
+
+    /// The first point to cross the line of an edge and when
+    fn failure(&self, edge: usize) -> Option<(f64, usize)> {
+        let (start, end) = (self.hull[edge], self.hull[(edge + 1) % self.hull.len()]);
+        (0..self.points.len())
+            .filter(|&point| point != start && point != end)
+            .filter_map(|point| {
+                crossing(&self.points[start], &self.points[end], &self.points[point], self.time)
+                    .map(|at| (at, point))
+            })
+            .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap())
+    }
+}
+
+/// The first time from `now` on at which the point goes from the left
+/// to the right of the line through `start` and `end`
+///
+/// The orientation of three linearly moving points is a quadratic
+/// polynomial in time, and the crossings are its roots where it decreases.
+fn crossing(start: &MovingPoint, end: &MovingPoint, point: &MovingPoint, now: f64) -> Option<f64> {
+    let cross = |a: Point2D, b: Point2D| a.x * b.y - a.y * b.x;
+    let difference = |to: Point2D, from: Point2D| Point2D::new(to.x - from.x, to.y - from.y);
+    let (edge_at_zero, edge_speed) = (
+        difference(end.position, start.position),
+        difference(end.velocity, start.velocity),
+    );
+    let (point_at_zero, point_speed) = (
+        difference(point.position, start.position),
+        difference(point.velocity, start.velocity),
+    );
+    let c0 = cross(edge_at_zero, point_at_zero);
+    let c1 = cross(edge_at_zero, point_speed) + cross(edge_speed, point_at_zero);
+    let c2 = cross(edge_speed, point_speed);
+    let root = if c2 == 0.0 {
+        if c1 < 0.0 {
+            -c0 / c1
+        } else {
+            return None;
+        }
+    } else {
+        let discriminant = c1 * c1 - 4.0 * c2 * c0;
+        if discriminant <= 0.0 {
+            return None;
+        }
+        // the roots without cancellation, which matters when the
+        // parabola is nearly flat; the decreasing one has the slope
+        // 2 c2 t + c1 = -sqrt(discriminant)
+        let q = -0.5 * (c1 + c1.signum() * discriminant.sqrt());
+        let (first, second) = (q / c2, c0 / q);
+        if 2.0 * c2 * first + c1 < 0.0 {
+            first
+        } else {
+            second
+        }
+    };
+    if root >= now {
+        Some(root)
+    } else {
+        None
+    }
+}
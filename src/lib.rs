@@ -4,8 +4,35 @@
 // distribution of this software for license terms.
 
 #![allow(dead_code)]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 pub mod points;
+pub mod geometry;
+pub mod robust;
+#[cfg(feature = "rational")]
+pub mod rational;
 pub mod inputset;
 pub mod convexhull;
+pub mod hull3d;
+pub mod spherical;
+pub mod dynamic;
+pub mod kinetic;
+#[cfg(feature = "plotting")]
 pub mod plots;
+#[cfg(feature = "benchmark")]
 pub mod benchmark;
+pub mod hull;
+#[cfg(feature = "generators")]
+pub mod distribution;
+pub mod io;
+pub mod samples;
+pub mod cache;
+pub mod kdtree;
+pub mod prefilter;
+pub mod polygon;
+pub mod concave;
+pub mod envelope;
+pub mod calipers;
+pub mod halfplane;
+pub mod delaunay;
+pub mod circle;
+pub mod perf;
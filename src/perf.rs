@@ -0,0 +1,191 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Hardware performance counters of the running thread
+//!
+//! The algorithms differ more in how they use the memory than
+//! in the number of operations, which the cache misses and
+//! branch mispredictions of a run show. The counters are read
+//! with the Linux perf_event_open system call and need the
+//! `perf_counters` feature. Elsewhere PerfCounters::open fails.
+use std::io;
+
+///Events counted in user space while the counters are running
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Counters {
+    /// retired instructions
+    pub instructions: u64,
+    /// last level cache misses
+    pub cache_misses: u64,
+    /// mispredicted branches
+    pub branch_misses: u64,
+}
+
+///Open counters for the instructions, cache misses and branch
+/// mispredictions of the calling thread
+pub struct PerfCounters {
+    events: Vec<sys::Event>,
+}
+
+impl PerfCounters {
+    ///Opens the counters, stopped
+    ///
+    /// This fails when the kernel forbids it, which is controlled
+    /// by /proc/sys/kernel/perf_event_paranoid, or in virtual
+    /// machines without a performance monitoring unit.
+    pub fn open() -> io::Result<PerfCounters> {
+        Ok(PerfCounters {
+            events: vec![
+                sys::Event::open(sys::PERF_COUNT_HW_INSTRUCTIONS)?,
+                sys::Event::open(sys::PERF_COUNT_HW_CACHE_MISSES)?,
+                sys::Event::open(sys::PERF_COUNT_HW_BRANCH_MISSES)?,
+            ],
+        })
+    }
+
+    ///Resets the counters to zero and starts them
+    pub fn start(&mut self) -> io::Result<()> {
+        for event in &self.events {
+            event.start()?;
+        }
+        Ok(())
+    }
+
+    ///Stops the counters and reads the events counted since start
+    pub fn stop(&mut self) -> io::Result<Counters> {
+        let mut counts: Vec<u64> = Vec::new();
+        for event in &mut self.events {
+            counts.push(event.stop()?);
+        }
+        Ok(Counters {
+            instructions: counts[0],
+            cache_misses: counts[1],
+            branch_misses: counts[2],
+        })
+    }
+}
+
+#[cfg(not(all(feature = "perf_counters", target_os = "linux")))]
+mod sys {
+    use std::io;
+
+    pub const PERF_COUNT_HW_INSTRUCTIONS: u64 = 1;
+    pub const PERF_COUNT_HW_CACHE_MISSES: u64 = 3;
+    pub const PERF_COUNT_HW_BRANCH_MISSES: u64 = 5;
+
+    /// Never opened
+    pub struct Event;
+
+    impl Event {
+        pub fn open(_config: u64) -> io::Result<Event> {
+            Err(io::Error::other("performance counters require the perf_counters feature on Linux"))
+        }
+
+        pub fn start(&self) -> io::Result<()> {
+            Ok(())
+        }
+
+        pub fn stop(&mut self) -> io::Result<u64> {
+            Ok(0)
+        }
+    }
+}
+
+#[cfg(all(feature = "perf_counters", target_os = "linux"))]
+mod sys {
+    extern crate libc;
+    use std::fs::File;
+    use std::io::{self, Read};
+    use std::os::unix::io::{AsRawFd, FromRawFd};
+
+    const PERF_TYPE_HARDWARE: u32 = 0;
+    pub const PERF_COUNT_HW_INSTRUCTIONS: u64 = 1;
+    pub const PERF_COUNT_HW_CACHE_MISSES: u64 = 3;
+    pub const PERF_COUNT_HW_BRANCH_MISSES: u64 = 5;
+
+    // flag bits of perf_event_attr
+    const DISABLED: u64 = 1;
+    const EXCLUDE_KERNEL: u64 = 1 << 5;
+    const EXCLUDE_HV: u64 = 1 << 6;
+
+    // _IO('$', n) requests of linux/perf_event.h
+    const PERF_EVENT_IOC_ENABLE: u64 = 0x2400;
+    const PERF_EVENT_IOC_DISABLE: u64 = 0x2401;
+    const PERF_EVENT_IOC_RESET: u64 = 0x2403;
+
+    /// The first version (PERF_ATTR_SIZE_VER0) of struct perf_event_attr,
+    /// which every kernel accepts
+    #[repr(C)]
+    struct Attributes {
+        kind: u32,
+        size: u32,
+        config: u64,
+        sample_period: u64,
+        sample_type: u64,
+        read_format: u64,
+        flags: u64,
+        wakeup_events: u32,
+        bp_type: u32,
+        config1: u64,
+    }
+
+    /// One counter, closed when the file is dropped
+    pub struct Event {
+        file: File,
+    }
+
+    impl Event {
+        pub fn open(config: u64) -> io::Result<Event> {
+            let attributes = Attributes {
+                kind: PERF_TYPE_HARDWARE,
+                size: ::std::mem::size_of::<Attributes>() as u32,
+                config,
+                sample_period: 0,
+                sample_type: 0,
+                read_format: 0,
+                flags: DISABLED | EXCLUDE_KERNEL | EXCLUDE_HV,
+                wakeup_events: 0,
+                bp_type: 0,
+                config1: 0,
+            };
+            // this thread, any cpu, no group, no flags
+            let fd = unsafe {
+                libc::syscall(
+                    libc::SYS_perf_event_open,
+                    &attributes as *const Attributes,
+                    0 as libc::pid_t,
+                    -1 as libc::c_int,
+                    -1 as libc::c_int,
+                    0 as libc::c_ulong,
+                )
+            };
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(Event {
+                file: unsafe { File::from_raw_fd(fd as i32) },
+            })
+        }
+
+        fn ioctl(&self, request: u64) -> io::Result<()> {
+            if unsafe { libc::ioctl(self.file.as_raw_fd(), request as _, 0) } < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        }
+
+        pub fn start(&self) -> io::Result<()> {
+            self.ioctl(PERF_EVENT_IOC_RESET)?;
+            self.ioctl(PERF_EVENT_IOC_ENABLE)
+        }
+
+        pub fn stop(&mut self) -> io::Result<u64> {
+            self.ioctl(PERF_EVENT_IOC_DISABLE)?;
+            let mut count = [0u8; 8];
+            self.file.read_exact(&mut count)?;
+            Ok(u64::from_ne_bytes(count))
+        }
+    }
+}
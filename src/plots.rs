@@ -6,7 +6,7 @@
 use points::Point2D;
 
 extern crate plotlib;
-use plots::plotlib::style::Point;
+use plots::plotlib::style::{Line, Point};
 
 // This code is reffered from plotlib crate's example
 pub fn draw_plot(points: &Vec<Point2D>) {
@@ -28,3 +28,23 @@ pub fn draw_plot(points: &Vec<Point2D>) {
         .y_label("Y-axis");
     plotlib::page::Page::single(&v).save("scatter.svg");
 }
+
+/// Draws benchmark results as a line of runtime (ms)
+/// against the quantity they are indexed by, e.g. the
+/// sample size n or the hull size h
+pub fn draw_benchmark_plot(results: &[(u64, f64)], x_label: &str, file_name: &str) {
+    let data: Vec<(f64, f64)> = results.iter().map(|&(x, y)| (x as f64, y)).collect();
+    let x_max = data.iter().fold(1.0, |max: f64, point| max.max(point.0));
+    let y_max = data.iter().fold(1.0, |max: f64, point| max.max(point.1));
+    let l1 = plotlib::line::Line::new(&data).style(
+        plotlib::line::Style::new()
+            .colour("black"),
+    );
+    let v = plotlib::view::ContinuousView::new()
+        .add(&l1)
+        .x_range(0.0, x_max)
+        .y_range(0.0, y_max)
+        .x_label(x_label)
+        .y_label("Runtime (ms)");
+    plotlib::page::Page::single(&v).save(file_name);
+}
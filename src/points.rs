@@ -4,67 +4,330 @@
 // distribution of this software for license terms.
 
 //! Points types for finding canvex hull
+use geometry::Orientation;
+use std::fmt::Debug;
+use std::ops::{Add, Mul, Neg, Sub};
+
+///A number type for the coordinates of a Point2D
+///
+/// Implemented for f32, f64, i32 and i64, and the hull algorithms of
+/// `convexhull` are generic over it, so every turn is taken in the
+/// coordinate type itself: f32 points in f32 arithmetic, integer points
+/// exactly. The functions taking any `HasXY` work on the positions as
+/// f64, which rounds i64 coordinates beyond 2^53.
+pub trait CoordNum:
+    Copy
+    + PartialOrd
+    + Debug
+    + Default
+    + Send
+    + Sync
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+    ///The type of products of coordinate differences, like the cross
+    /// product, which is i128 for the integers so that it can be exact
+    type Product: Copy
+        + PartialOrd
+        + Debug
+        + Default
+        + Add<Output = Self::Product>
+        + Sub<Output = Self::Product>
+        + Mul<Output = Self::Product>
+        + Neg<Output = Self::Product>;
+
+    ///Whether `orientation` is exact, so that sorting by turns tells
+    /// apart points that computed angles would round together
+    const EXACT_TURNS: bool = false;
+
+    /// the coordinate as an f64
+    fn to_f64(self) -> f64;
+
+    /// the coordinate as a Product
+    fn widen(self) -> Self::Product;
+
+    ///Bits that are the same exactly when the coordinates are, with 0.0
+    /// and -0.0 the same, to hash points by
+    fn to_bits(self) -> u64;
+
+    /// whether the coordinate is a finite number, which integers are
+    fn is_finite(self) -> bool {
+        true
+    }
+
+    ///A bound of the rounding error of sums and differences of
+    /// products that add up to the given magnitude, zero for integers
+    fn rounding_error(_magnitude: Self::Product) -> Self::Product {
+        Self::Product::default()
+    }
+
+    ///The cross product of b - a and c - a, positive when the path from
+    /// a over b to c turns counter clockwise
+    fn cross(a: &Point2D<Self>, b: &Point2D<Self>, c: &Point2D<Self>) -> Self::Product {
+        let difference = |to: Self, from: Self| to.widen() - from.widen();
+        difference(b.x, a.x) * difference(c.y, a.y) - difference(b.y, a.y) * difference(c.x, a.x)
+    }
+
+    ///The dot product of b - a and c - a
+    fn dot(a: &Point2D<Self>, b: &Point2D<Self>, c: &Point2D<Self>) -> Self::Product {
+        let difference = |to: Self, from: Self| to.widen() - from.widen();
+        difference(b.x, a.x) * difference(c.x, a.x) + difference(b.y, a.y) * difference(c.y, a.y)
+    }
+
+    ///Which way the path from a over b to c turns
+    ///
+    /// Floating point coordinates use the sign of the cross product,
+    /// which can be wrong for points very close to a line. Integer
+    /// coordinates are exact.
+    fn orientation(a: &Point2D<Self>, b: &Point2D<Self>, c: &Point2D<Self>) -> Orientation {
+        let turn = Self::cross(a, b, c);
+        let zero = Self::Product::default();
+        if turn > zero {
+            Orientation::CounterClockwise
+        } else if turn < zero {
+            Orientation::Clockwise
+        } else {
+            Orientation::Collinear
+        }
+    }
+
+    ///Which way the path from the centroid of the triangle over b to c
+    /// turns
+    ///
+    /// The centroid of integer points has no integer coordinates, so
+    /// the turn is the sign of nine times its cross product, taken
+    /// relative to the first corner of the triangle. Floating point
+    /// coordinates round the centroid and take its orientation.
+    fn centroid_orientation(triangle: &[Point2D<Self>; 3], b: &Point2D<Self>, c: &Point2D<Self>) -> Orientation {
+        let difference = |to: &Point2D<Self>| (to.x.widen() - triangle[0].x.widen(), to.y.widen() - triangle[0].y.widen());
+        let cross = |u: (Self::Product, Self::Product), v: (Self::Product, Self::Product)| u.0 * v.1 - u.1 * v.0;
+        let (first, second) = (difference(&triangle[1]), difference(&triangle[2]));
+        //three times the centroid, relative to the first corner
+        let centroid = (first.0 + second.0, first.1 + second.1);
+        let (u, v) = (difference(b), difference(c));
+        let three = |value: Self::Product| value + value + value;
+        let turn = three(cross(u, v)) - cross(u, centroid) - cross(centroid, v);
+        let zero = Self::Product::default();
+        if turn > zero {
+            Orientation::CounterClockwise
+        } else if turn < zero {
+            Orientation::Clockwise
+        } else {
+            Orientation::Collinear
+        }
+    }
+}
+
+/// The cross product is `orietation`, exact in sign with the `robust`
+/// feature.
+impl CoordNum for f64 {
+    type Product = f64;
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn widen(self) -> f64 {
+        self
+    }
+
+    fn to_bits(self) -> u64 {
+        (self + 0.0).to_bits()
+    }
+
+    fn is_finite(self) -> bool {
+        f64::is_finite(self)
+    }
+
+    fn rounding_error(magnitude: f64) -> f64 {
+        16.0 * f64::EPSILON * magnitude
+    }
+
+    fn cross(a: &Point2D, b: &Point2D, c: &Point2D) -> f64 {
+        orietation(a, b, c)
+    }
+
+    fn centroid_orientation(triangle: &[Point2D; 3], b: &Point2D, c: &Point2D) -> Orientation {
+        let centroid = Point2D::new(
+            (triangle[0].x + triangle[1].x + triangle[2].x) / 3.0,
+            (triangle[0].y + triangle[1].y + triangle[2].y) / 3.0,
+        );
+        f64::orientation(&centroid, b, c)
+    }
+}
+
+/// The cross product is taken in f32, which rounds far sooner than
+/// the one of f64 points.
+impl CoordNum for f32 {
+    type Product = f32;
+
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+
+    fn widen(self) -> f32 {
+        self
+    }
+
+    fn to_bits(self) -> u64 {
+        u64::from((self + 0.0).to_bits())
+    }
+
+    fn is_finite(self) -> bool {
+        f32::is_finite(self)
+    }
+
+    fn rounding_error(magnitude: f32) -> f32 {
+        16.0 * f32::EPSILON * magnitude
+    }
+
+    fn centroid_orientation(triangle: &[Point2D<f32>; 3], b: &Point2D<f32>, c: &Point2D<f32>) -> Orientation {
+        let centroid = Point2D::new(
+            (triangle[0].x + triangle[1].x + triangle[2].x) / 3.0,
+            (triangle[0].y + triangle[1].y + triangle[2].y) / 3.0,
+        );
+        f32::orientation(&centroid, b, c)
+    }
+}
+
+/// The products are i128, where they are exact for any i32 coordinates.
+impl CoordNum for i32 {
+    type Product = i128;
+    const EXACT_TURNS: bool = true;
+
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+
+    fn widen(self) -> i128 {
+        i128::from(self)
+    }
+
+    fn to_bits(self) -> u64 {
+        u64::from(self as u32)
+    }
+}
+
+/// The products are i128, where they are exact for coordinates within
+/// ±2^60, which the hull algorithms need beyond their turns. The turns
+/// themselves are exact for any coordinates, see `exact_orientation`.
+impl CoordNum for i64 {
+    type Product = i128;
+    const EXACT_TURNS: bool = true;
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn widen(self) -> i128 {
+        i128::from(self)
+    }
+
+    fn to_bits(self) -> u64 {
+        self as u64
+    }
+
+    fn orientation(a: &Point2D<i64>, b: &Point2D<i64>, c: &Point2D<i64>) -> Orientation {
+        exact_orientation(a, b, c)
+    }
+}
+
+///Which way the path from a over b to c turns, exactly for any
+/// integer coordinates
+///
+/// The differences of the coordinates are widened to i128, where they
+/// have at most 65 bits. Their products could overflow even there, so
+/// the two products of the cross product are compared by sign and
+/// then by magnitude, each the product of two u64 values in a u128.
+pub fn exact_orientation(a: &Point2D<i64>, b: &Point2D<i64>, c: &Point2D<i64>) -> Orientation {
+    let difference = |to: i64, from: i64| i128::from(to) - i128::from(from);
+    //(sign, magnitude) of a product
+    let product = |u: i128, v: i128| ((u.signum() * v.signum()) as i8, u.unsigned_abs() * v.unsigned_abs());
+    let left = product(difference(b.x, a.x), difference(c.y, a.y));
+    let right = product(difference(b.y, a.y), difference(c.x, a.x));
+    let ordering = left.0.cmp(&right.0).then_with(|| match left.0 {
+        1 => left.1.cmp(&right.1),
+        -1 => right.1.cmp(&left.1),
+        _ => Ordering::Equal,
+    });
+    match ordering {
+        Ordering::Greater => Orientation::CounterClockwise,
+        Ordering::Less => Orientation::Clockwise,
+        Ordering::Equal => Orientation::Collinear,
+    }
+}
 
 ///A basic representation of a point
 ///
 ///With x and y coordinate, a point2D
-///is a point in 2D euclidean space
+///is a point in 2D euclidean space. The coordinates are f64 unless
+///another CoordNum is given, like `Point2D<i32>` for pixels.
 ///
 /// #Example
 ///
 /// ```
-/// let point = points::Point2D::new(1.0,2.0);
-/// assert_eq(Point2D {x: 1.0, y: 2.0}, point);
+/// use rustalgo::points::Point2D;
+/// const POINT: Point2D = Point2D::new(1.0, 2.0);
+/// assert_eq!(Point2D {x: 1.0, y: 2.0}, POINT);
+/// let pixel: Point2D<i32> = Point2D::new(3, 4);
+/// assert_eq!(POINT.x + 2.0, pixel.to_f64().x);
 /// ```
 #[derive(Debug, Copy, Clone)]
-pub struct Point2D {
+pub struct Point2D<T = f64> {
     /// x-coordinate value
-    pub x: f64,
+    pub x: T,
     /// y-coordinate value
-    pub y: f64,
+    pub y: T,
 }
 
-impl PartialEq for Point2D {
+impl<T: PartialEq> PartialEq for Point2D<T> {
     /// compare 2 points using = sign
     /// and return true when both x and y
     /// coordinate are same
-    fn eq(&self, other: &Point2D) -> bool {
+    fn eq(&self, other: &Point2D<T>) -> bool {
         self.x == other.x && self.y == other.y
     }
 }
 
-///Implementation methods for Point2D type
-///
-/// Some handy methods find the convex hull
-/// using the point
-impl Point2D {
+impl<T> Point2D<T> {
     ///Constructor for Point2D
-    pub fn new(x: f64, y: f64) -> Point2D {
+    ///
+    /// Being a const fn, it can build constant and static points.
+    pub const fn new(x: T, y: T) -> Point2D<T> {
         Point2D { x, y }
     }
+}
+
+impl<T: CoordNum> Point2D<T> {
+    ///The same point with f64 coordinates
+    pub fn to_f64(&self) -> Point2D {
+        Point2D::new(self.x.to_f64(), self.y.to_f64())
+    }
 
     ///Comparision of point position relative to another point
     ///
     ///Given two points we need to left most point.
     /// This is used to find the pivot point of the vertex point
     /// of the hull.
-    pub fn pick_left<'a>(&'a self, other: &'a Point2D) -> &'a Point2D {
+    pub fn pick_left<'a>(&'a self, other: &'a Point2D<T>) -> &'a Point2D<T> {
         //when both the points are same, return the other point
         if self == other {
-            return other;
+            other
         } else if self.y != other.y {
             // else return the point with min y-coordinate
             if self.y < other.y {
-                return self;
+                self
             } else {
-                return other;
+                other
             }
         } else {
             // when y-coordinates are same return the point with min x-coordinate
             if self.x < other.x {
-                return self;
+                self
             } else {
-                return other;
+                other
             }
         }
     }
@@ -75,19 +338,37 @@ impl Point2D {
     /// Return true for counterclockwise turn
     /// and false for colinearity or clockwise turns.
     ///
-    pub fn ccw(&self, point_b: &Point2D, point_c: &Point2D) -> bool {
-        (point_b.x - self.x) * (point_c.y - self.y) - (point_b.y - self.y) * (point_c.x - self.x)
-            > 0.0
+    /// With the `robust` feature the turn of f64 points is exact, see
+    /// `robust::orient2d`.
+    pub fn ccw(&self, point_b: &Point2D<T>, point_c: &Point2D<T>) -> bool {
+        T::orientation(self, point_b, point_c) == Orientation::CounterClockwise
     }
 
-    ///Determine the distance between 2 points
-    fn compute_distance(&self, point2: &Point2D) -> f64 {
-        ((self.x - point2.x).powi(2) + (self.y - point2.y).powi(2)).sqrt()
+    ///Determine the distance between 2 points, in f64
+    ///
+    /// The coordinates are converted before they are subtracted, since
+    /// the difference of two integers can overflow their type.
+    fn compute_distance(&self, point2: &Point2D<T>) -> f64 {
+        ((self.x.to_f64() - point2.x.to_f64()).powi(2) + (self.y.to_f64() - point2.y.to_f64()).powi(2)).sqrt()
     }
 
-    ///Determine the polarangle between 2 points
-    fn compute_angle(&self, point2: &Point2D) -> f64 {
-        (point2.y - self.y).atan2(point2.x - self.x)
+    ///Determine the polarangle between 2 points, in f64
+    fn compute_angle(&self, point2: &Point2D<T>) -> f64 {
+        (point2.y.to_f64() - self.y.to_f64()).atan2(point2.x.to_f64() - self.x.to_f64())
+    }
+}
+
+///Implementation methods for Point2D type
+///
+/// Some handy methods find the convex hull
+/// using the point
+impl Point2D {
+
+    ///The same point with f32 coordinates, rounded to the nearest f32
+    ///
+    /// Points that came from f32 coordinates are returned exactly.
+    pub fn to_f32(&self) -> Point2D<f32> {
+        Point2D::new(self.x as f32, self.y as f32)
     }
 
     ///Converts a Point2D back to a tuple
@@ -96,6 +377,74 @@ impl Point2D {
     }
 }
 
+///Anything that has a position in the plane
+///
+/// Lets the hull functions work on user types, so that metadata
+/// like ids, weights or timestamps travels with each point.
+pub trait HasXY {
+    /// the position of the item
+    fn xy(&self) -> Point2D;
+}
+
+impl<T: CoordNum> HasXY for Point2D<T> {
+    fn xy(&self) -> Point2D {
+        self.to_f64()
+    }
+}
+
+/// A point with a payload of any type
+impl<T> HasXY for (Point2D, T) {
+    fn xy(&self) -> Point2D {
+        self.0
+    }
+}
+
+///A point moving with a constant velocity
+///
+/// Used to describe input sets that change over time.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MovingPoint {
+    /// position at time zero
+    pub position: Point2D,
+    /// distance travelled along each axis per unit of time
+    pub velocity: Point2D,
+}
+
+impl MovingPoint {
+    ///Constructor for MovingPoint
+    pub fn new(position: Point2D, velocity: Point2D) -> MovingPoint {
+        MovingPoint { position, velocity }
+    }
+
+    ///Position of the point at the given time
+    pub fn at(&self, time: f64) -> Point2D {
+        Point2D::new(
+            self.position.x + self.velocity.x * time,
+            self.position.y + self.velocity.y * time,
+        )
+    }
+}
+
+///A point in 3D euclidean space
+///
+/// Used by the hull3d module.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Point3D {
+    /// x-coordinate value
+    pub x: f64,
+    /// y-coordinate value
+    pub y: f64,
+    /// z-coordinate value
+    pub z: f64,
+}
+
+impl Point3D {
+    ///Constructor for Point3D
+    pub const fn new(x: f64, y: f64, z: f64) -> Point3D {
+        Point3D { x, y, z }
+    }
+}
+
 ///An extended representation of a point
 ///
 /// We store additional information to a
@@ -104,43 +453,51 @@ impl Point2D {
 /// in the input set.
 ///
 #[derive(Debug, PartialEq)]
-pub struct Fatpoint2D {
-    x: f64,
-    y: f64,
+pub struct Fatpoint2D<T = f64> {
+    x: T,
+    y: T,
     distance: f64,
     angle: f64,
 }
 
 use std::cmp::Ordering;
-impl PartialOrd for Fatpoint2D {
+use std::collections::{HashMap, HashSet};
+impl<T: CoordNum> PartialOrd for Fatpoint2D<T> {
     /// A handy method to sort points based on their angle
     /// and distance from the vertex point
-    fn partial_cmp(&self, other: &Fatpoint2D) -> Option<Ordering> {
+    fn partial_cmp(&self, other: &Fatpoint2D<T>) -> Option<Ordering> {
         self.angle.partial_cmp(&other.angle)
     }
 }
 
-impl Fatpoint2D {
+impl<T: CoordNum> Fatpoint2D<T> {
     /// create properties for a point from another point usually the vertex
-    pub fn new(point: &Point2D, vertex: &Point2D) -> Fatpoint2D {
+    ///
+    /// The angle is that of the direction from the point to the vertex,
+    /// in [-π, π). Points level with the vertex and right of it get -π,
+    /// which atan2 would give as π, so that around a lowest vertex they
+    /// come first like the counter clockwise order has them. Distance
+    /// and angle are f64 for every coordinate type.
+    pub fn new(point: &Point2D<T>, vertex: &Point2D<T>) -> Fatpoint2D<T> {
+        let angle = point.compute_angle(vertex);
         Fatpoint2D {
             x: point.x,
             y: point.y,
-            distance: point.compute_distance(&vertex),
-            angle: point.compute_angle(&vertex),
+            distance: point.compute_distance(vertex),
+            angle: if angle == ::std::f64::consts::PI { -angle } else { angle },
         }
     }
 
     /// canverts a fatpoint back to point2d
-    pub fn to_point(&self) -> Point2D {
+    pub fn to_point(&self) -> Point2D<T> {
         Point2D::new(self.x, self.y)
     }
 
-    pub fn partial_cmp_distance(&self, other: &Fatpoint2D) -> Option<Ordering> {
+    pub fn partial_cmp_distance(&self, other: &Fatpoint2D<T>) -> Option<Ordering> {
         self.distance.partial_cmp(&other.distance)
     }
 
-    pub fn eq_polar_angle(&self, other: &Fatpoint2D) -> bool {
+    pub fn eq_polar_angle(&self, other: &Fatpoint2D<T>) -> bool {
         self.angle == other.angle
     }
 }
@@ -160,7 +517,7 @@ fn test_new_fat_point() {
             x: 1.0,
             y: 2.0,
             distance: 1.0,
-            angle: 1.5707963267948966,
+            angle: ::std::f64::consts::FRAC_PI_2,
         },
         Fatpoint2D::new(&point_a, &point_b)
     );
@@ -185,11 +542,66 @@ fn test_fat_point_cmp() {
     assert_eq!(vec![&fat_point_b, &fat_point_a], set);
 }
 
+///The corners of the axis aligned box holding the points, the one
+/// with the least coordinates first
+///
+/// # Panics
+/// Panics when there are no points
+pub fn bounding_box<P: HasXY>(points: &[P]) -> (Point2D, Point2D) {
+    assert!(!points.is_empty(), "there are no points");
+    let first = points[0].xy();
+    points.iter().fold((first, first), |(low, high), item| {
+        let point = item.xy();
+        (Point2D::new(low.x.min(point.x), low.y.min(point.y)), Point2D::new(high.x.max(point.x), high.y.max(point.y)))
+    })
+}
+
+///Removes the points within the tolerance of a point kept before
+/// them, keeping the first of every group in its place
+///
+/// The points are snapped to a grid of cells as wide as the tolerance,
+/// and a point is only compared to the points kept in its cell and the
+/// eight around it, in O(n) expected time. A tolerance of 0 removes the
+/// exact copies only, with 0.0 and -0.0 the same coordinate.
+///
+/// # Panics
+/// Panics when a coordinate is NaN
+pub fn dedup_points(points: &mut Vec<Point2D>, tolerance: f64) {
+    assert!(points.iter().all(|point| !point.x.is_nan() && !point.y.is_nan()), "a coordinate is NaN");
+    if tolerance <= 0.0 {
+        let mut seen: HashSet<(u64, u64)> = HashSet::new();
+        points.retain(|point| seen.insert(((point.x + 0.0).to_bits(), (point.y + 0.0).to_bits())));
+        return;
+    }
+    let cell = |point: &Point2D| ((point.x / tolerance).floor() as i64, (point.y / tolerance).floor() as i64);
+    let mut kept: HashMap<(i64, i64), Vec<Point2D>> = HashMap::new();
+    points.retain(|point| {
+        let (column, row) = cell(point);
+        let close = |other: &Point2D| (other.x - point.x).powi(2) + (other.y - point.y).powi(2) <= tolerance * tolerance;
+        for neighbour in [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 0), (0, 1), (1, -1), (1, 0), (1, 1)].iter() {
+            let key = (column.saturating_add(neighbour.0), row.saturating_add(neighbour.1));
+            if kept.get(&key).is_some_and(|others| others.iter().any(close)) {
+                return false;
+            }
+        }
+        kept.entry((column, row)).or_default().push(*point);
+        true
+    });
+}
+
+#[cfg(not(feature = "robust"))]
 pub fn orietation(point_a: &Point2D, point_b: &Point2D, point_c: &Point2D) -> f64 {
     (point_b.x - point_a.x) * (point_c.y - point_a.y)
         - (point_b.y - point_a.y) * (point_c.x - point_a.x)
 }
 
+///The cross product of b - a and c - a, with the sign of the exact
+/// value, see `robust::orient2d`
+#[cfg(feature = "robust")]
+pub fn orietation(point_a: &Point2D, point_b: &Point2D, point_c: &Point2D) -> f64 {
+    ::robust::orient2d(point_a, point_b, point_c)
+}
+
 ///
 /// For formula to find the angle when we know the sides of a triangle is
 /// reffered from https://en.wikipedia.org/wiki/Law_of_cosines
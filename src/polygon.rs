@@ -0,0 +1,229 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Polygons as input sets
+//!
+//! GIS data usually comes as polygons rather than loose points.
+//! A Ring is the outer boundary of such a polygon, read from WKT,
+//! converted from a `geo_types::Polygon` (with the `geo` feature)
+//! or given as a closed list of vertices. The hull of a simple
+//! polygon is found in linear time with Melkman's algorithm.
+//!
+//! Holes never touch the hull, so they are dropped.
+//!
+//! The bounding geometry of a scene of several segments or polygons
+//! is found with `hull_of_segments` and `hull_of_polygons`, which tell
+//! which input primitive each hull vertex came from.
+use convexhull::{convex_hull_of, melkman};
+use points::{orietation, Point2D};
+use std::error::Error;
+use std::f64::consts::PI;
+use std::fmt;
+
+#[cfg(feature = "geo")]
+extern crate geo_types;
+
+///The vertices of a polygon boundary, in their order along it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ring {
+    vertices: Vec<Point2D>,
+}
+
+impl Ring {
+    ///A ring through the vertices. The closing vertex may be
+    /// repeated at the end or left out.
+    pub fn new(mut vertices: Vec<Point2D>) -> Ring {
+        if vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+        Ring { vertices }
+    }
+
+    ///Reads the outer ring of a WKT polygon like
+    /// `POLYGON ((0 0, 4 0, 4 4, 0 4, 0 0))`
+    ///
+    /// Z and M values after the x and y coordinates are ignored.
+    pub fn from_wkt(wkt: &str) -> Result<Ring, ParseWktError> {
+        let wkt = wkt.trim();
+        if wkt.len() < 7 || !wkt[..7].eq_ignore_ascii_case("POLYGON") {
+            return Err(ParseWktError::new("expected a POLYGON"));
+        }
+        // skip dimension tags like Z or M up to the opening parentheses
+        let body = match wkt.find('(') {
+            Some(start) => wkt[start + 1..].trim_start(),
+            None => return Err(ParseWktError::new("expected '(' after POLYGON")),
+        };
+        if !body.starts_with('(') {
+            return Err(ParseWktError::new("expected '(' before the outer ring"));
+        }
+        let ring = match body.find(')') {
+            Some(end) => &body[1..end],
+            None => return Err(ParseWktError::new("the outer ring is not closed by ')'")),
+        };
+        let mut vertices: Vec<Point2D> = Vec::new();
+        for vertex in ring.split(',') {
+            let mut values = vertex.split_whitespace().map(|value| value.parse::<f64>());
+            match (values.next(), values.next()) {
+                (Some(Ok(x)), Some(Ok(y))) => vertices.push(Point2D::new(x, y)),
+                _ => return Err(ParseWktError::new(&format!("not a vertex: '{}'", vertex.trim()))),
+            }
+        }
+        Ok(Ring::new(vertices))
+    }
+
+    ///The vertices of the ring, without the closing vertex
+    pub fn vertices(&self) -> &[Point2D] {
+        &self.vertices
+    }
+
+    ///The hull of the polygon in the order of `convexhull::hull_indices`
+    ///
+    /// The ring must be simple (not cross itself), which holds for
+    /// valid GIS polygons. Use `convexhull::convex_hull_of` on
+    /// the vertices of other rings.
+    pub fn hull(&self) -> Vec<Point2D> {
+        melkman(&self.vertices)
+    }
+
+    ///Whether the ring is a convex polygon, see is_convex
+    pub fn is_convex(&self) -> bool {
+        is_convex(&self.vertices)
+    }
+}
+
+///Whether the polygon is convex, in either orientation
+///
+/// Every turn along the boundary has to go the same way, or straight on
+/// for collinear vertices, and the boundary has to go around once, which
+/// rules out stars. Repeated vertices, the closing one included, are
+/// skipped. Polygons without area, with all their vertices on a line,
+/// are not convex.
+pub fn is_convex(polygon: &[Point2D]) -> bool {
+    let mut vertices = polygon.to_vec();
+    vertices.dedup();
+    if vertices.len() > 1 && vertices.first() == vertices.last() {
+        vertices.pop();
+    }
+    let n = vertices.len();
+    if n < 3 {
+        return false;
+    }
+    let (mut side, mut turning) = (0.0, 0.0);
+    for i in 0..n {
+        let (a, b, c) = (&vertices[i], &vertices[(i + 1) % n], &vertices[(i + 2) % n]);
+        let turn = orietation(a, b, c);
+        let ahead = (b.x - a.x) * (c.x - b.x) + (b.y - a.y) * (c.y - b.y);
+        //turning back along the edge is a spike
+        if turn * side < 0.0 || (turn == 0.0 && ahead < 0.0) {
+            return false;
+        }
+        if turn != 0.0 {
+            side = turn;
+        }
+        turning += turn.atan2(ahead);
+    }
+    //a full turn, and not two or more
+    turning.abs() < 3.0 * PI
+}
+
+impl AsRef<[Point2D]> for Ring {
+    fn as_ref(&self) -> &[Point2D] {
+        &self.vertices
+    }
+}
+
+///Where a hull vertex of several segments or polygons came from
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Source {
+    /// index of the segment or polygon in the input
+    pub primitive: usize,
+    /// index of the vertex in it, 0 for the start of a segment and 1 for its end
+    pub vertex: usize,
+}
+
+///The hull of line segments, with the source of every vertex
+///
+/// The vertices are in the order of `convexhull::hull_indices`. A point
+/// shared by several segments comes from the first of them.
+///
+/// # Panics
+/// Panics when a coordinate is NaN
+pub fn hull_of_segments(segments: &[(Point2D, Point2D)]) -> Vec<(Point2D, Source)> {
+    let ends: Vec<(Point2D, Source)> = segments
+        .iter()
+        .enumerate()
+        .flat_map(|(primitive, &(start, end))| {
+            vec![(start, Source { primitive, vertex: 0 }), (end, Source { primitive, vertex: 1 })]
+        })
+        .collect();
+    convex_hull_of(&ends)
+}
+
+///The hull of polygons, like rings or vertex lists, with the source of
+/// every vertex
+///
+/// The polygons don't need to be simple or apart from each other. The
+/// vertices are in the order of `convexhull::hull_indices`, and a
+/// point shared by several polygons comes from the first of them.
+///
+/// # Panics
+/// Panics when a coordinate is NaN
+pub fn hull_of_polygons<P: AsRef<[Point2D]>>(polygons: &[P]) -> Vec<(Point2D, Source)> {
+    let vertices: Vec<(Point2D, Source)> = polygons
+        .iter()
+        .enumerate()
+        .flat_map(|(primitive, polygon)| {
+            polygon
+                .as_ref()
+                .iter()
+                .enumerate()
+                .map(move |(vertex, point)| (*point, Source { primitive, vertex }))
+        })
+        .collect();
+    convex_hull_of(&vertices)
+}
+
+#[cfg(feature = "geo")]
+impl<'a> From<&'a geo_types::LineString<f64>> for Ring {
+    fn from(line_string: &'a geo_types::LineString<f64>) -> Ring {
+        Ring::new(
+            line_string
+                .0
+                .iter()
+                .map(|coordinate| Point2D::new(coordinate.x, coordinate.y))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(feature = "geo")]
+impl<'a> From<&'a geo_types::Polygon<f64>> for Ring {
+    fn from(polygon: &'a geo_types::Polygon<f64>) -> Ring {
+        Ring::from(polygon.exterior())
+    }
+}
+
+///Why a WKT text could not be read as a polygon
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseWktError {
+    /// what was wrong with the text
+    pub reason: String,
+}
+
+impl ParseWktError {
+    fn new(reason: &str) -> ParseWktError {
+        ParseWktError {
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ParseWktError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid WKT polygon: {}", self.reason)
+    }
+}
+
+impl Error for ParseWktError {}
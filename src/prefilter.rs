@@ -0,0 +1,158 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Passes that throw away input points which can't be on the hull
+//!
+//! Running a filter before an algorithm leaves less work for it.
+//! A filter only drops points that are strictly inside the hull,
+//! so the hull of the filtered input set is the hull of the input set.
+use geometry::Orientation;
+use points::{bounding_box, CoordNum, Point2D};
+
+///The available filters
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Prefilter {
+    /// uniform grid bucketing, see grid_filter
+    Grid,
+    /// the quadrilateral of the extreme points, see prune_akl_toussaint
+    AklToussaint,
+}
+
+impl Prefilter {
+    ///Runs the filter on an input set
+    pub fn apply(self, input_set: &[Point2D]) -> Vec<Point2D> {
+        match self {
+            Prefilter::Grid => grid_filter(input_set),
+            Prefilter::AklToussaint => prune_akl_toussaint(input_set),
+        }
+    }
+}
+
+///Drops the points of an input set using a uniform grid
+///
+/// The bounding box is cut into about √n by √n cells and every
+/// column remembers the lowest and highest row holding a point.
+/// A point is dropped when some column to its left and some column
+/// to its right both reach rows below and above the point's row:
+/// it is then inside the quadrilateral of four points from those
+/// columns. The pass is linear, and on large uniform input sets it
+/// keeps only the points in the cells along the border.
+///
+/// The order of the kept points is unchanged.
+pub fn grid_filter(input_set: &[Point2D]) -> Vec<Point2D> {
+    if input_set.is_empty() {
+        return Vec::new();
+    }
+    let (low, high) = bounding_box(input_set);
+    let (min_x, max_x, min_y, max_y) = (low.x, high.x, low.y, high.y);
+    let size = ((input_set.len() as f64).sqrt().ceil() as usize).max(1);
+    let bucket = |value: f64, min: f64, max: f64| {
+        if max > min {
+            (((value - min) / (max - min) * size as f64) as usize).min(size - 1)
+        } else {
+            0
+        }
+    };
+    let cell = |point: &Point2D| (bucket(point.x, min_x, max_x), bucket(point.y, min_y, max_y));
+
+    // lowest and highest occupied row of every column
+    let mut lowest: Vec<usize> = vec![usize::MAX; size];
+    let mut highest: Vec<usize> = vec![0; size];
+    for point in input_set {
+        let (column, row) = cell(point);
+        lowest[column] = lowest[column].min(row);
+        highest[column] = highest[column].max(row);
+    }
+
+    // the same extremes over all the columns left of a column ...
+    let mut lowest_left: Vec<usize> = vec![usize::MAX; size];
+    let mut highest_left: Vec<usize> = vec![0; size];
+    for column in 1..size {
+        lowest_left[column] = lowest_left[column - 1].min(lowest[column - 1]);
+        highest_left[column] = highest_left[column - 1].max(highest[column - 1]);
+    }
+    // ... and right of a column
+    let mut lowest_right: Vec<usize> = vec![usize::MAX; size];
+    let mut highest_right: Vec<usize> = vec![0; size];
+    for column in (0..size - 1).rev() {
+        lowest_right[column] = lowest_right[column + 1].min(lowest[column + 1]);
+        highest_right[column] = highest_right[column + 1].max(highest[column + 1]);
+    }
+
+    input_set
+        .iter()
+        .filter(|point| {
+            let (column, row) = cell(point);
+            let inside = lowest_left[column] < row
+                && highest_left[column] > row
+                && lowest_right[column] < row
+                && highest_right[column] > row;
+            !inside
+        })
+        .cloned()
+        .collect()
+}
+
+///Drops the points of an input set inside the quadrilateral of its
+/// leftmost, lowest, rightmost and highest points
+///
+/// These four points are on the hull, so everything strictly inside
+/// the quadrilateral they make is inside the hull too. The pass is
+/// linear and on uniform input sets drops about half of the points
+/// in a square and more in a circle.
+///
+/// The order of the kept points is unchanged.
+///
+/// S. Akl and G. Toussaint, "A fast convex hull algorithm",
+/// Information Processing Letters, 1978
+pub fn prune_akl_toussaint<T: CoordNum>(input_set: &[Point2D<T>]) -> Vec<Point2D<T>> {
+    let corners = quadrilateral(input_set);
+    input_set
+        .iter()
+        .filter(|point| !inside(&corners, point))
+        .cloned()
+        .collect()
+}
+
+///The leftmost, lowest, rightmost and highest point of an input set,
+/// in this counter clockwise order and without repeated corners
+///
+/// These are the corners of the quadrilateral of prune_akl_toussaint.
+pub fn quadrilateral<T: CoordNum>(input_set: &[Point2D<T>]) -> Vec<Point2D<T>> {
+    if input_set.is_empty() {
+        return Vec::new();
+    }
+    let (mut left, mut bottom, mut right, mut top) = (input_set[0], input_set[0], input_set[0], input_set[0]);
+    for point in input_set {
+        if point.x < left.x {
+            left = *point;
+        }
+        if point.y < bottom.y {
+            bottom = *point;
+        }
+        if point.x > right.x {
+            right = *point;
+        }
+        if point.y > top.y {
+            top = *point;
+        }
+    }
+    let mut corners: Vec<Point2D<T>> = Vec::new();
+    for &corner in &[left, bottom, right, top] {
+        if !corners.contains(&corner) {
+            corners.push(corner);
+        }
+    }
+    corners
+}
+
+///Whether a point is strictly inside a convex polygon given by its
+/// corners in counter clockwise order
+pub fn inside<T: CoordNum>(corners: &[Point2D<T>], point: &Point2D<T>) -> bool {
+    corners.len() > 2
+        && (0..corners.len()).all(|i| {
+            T::orientation(&corners[i], &corners[(i + 1) % corners.len()], point) == Orientation::CounterClockwise
+        })
+}
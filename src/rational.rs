@@ -0,0 +1,72 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Ground truth hulls in exact rational arithmetic
+//!
+//! Every finite f64 is a fraction, so the hull of f64 points can be
+//! found with no rounding at all by taking their coordinates as
+//! arbitrary precision rationals. That is far too slow to be one of the
+//! benchmarked algorithms, but it is the hull the float algorithms
+//! should find, and the correctness mode of the benchmark compares them
+//! with it. Needs the `rational` feature.
+extern crate num_rational;
+use self::num_rational::BigRational;
+use convexhull::monotone_chain_by;
+use geometry::Orientation;
+use points::*;
+use std::cmp::Ordering;
+
+///A point with rational coordinates
+///
+/// The coordinates are not Copy, so this is not a `Point2D<T>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RationalPoint {
+    pub x: BigRational,
+    pub y: BigRational,
+}
+
+impl RationalPoint {
+    ///The exact value of a point, or None when a coordinate is NaN
+    /// or infinite
+    pub fn from_point(point: &Point2D) -> Option<RationalPoint> {
+        Some(RationalPoint { x: BigRational::from_float(point.x)?, y: BigRational::from_float(point.y)? })
+    }
+}
+
+///Which way the path from a over b to c turns, with no rounding
+pub fn rational_orientation(a: &RationalPoint, b: &RationalPoint, c: &RationalPoint) -> Orientation {
+    let left = (&b.x - &a.x) * (&c.y - &a.y);
+    let right = (&b.y - &a.y) * (&c.x - &a.x);
+    match left.cmp(&right) {
+        Ordering::Greater => Orientation::CounterClockwise,
+        Ordering::Less => Orientation::Clockwise,
+        Ordering::Equal => Orientation::Collinear,
+    }
+}
+
+///The hull of the input set with every turn taken in rational
+/// arithmetic, in the order of convex_hull_of
+///
+/// The monotone chain of `convex_hull_exact`. Sorting f64 values is
+/// exact already, only the turns need the rationals. Points on the hull
+/// edges and copies of vertices are left out.
+///
+/// # Panics
+/// Panics when a coordinate is NaN or infinite
+pub fn exact_hull(input_set: &[Point2D]) -> Vec<Point2D> {
+    let mut points = input_set.to_vec();
+    points.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap()));
+    points.dedup();
+    let exact: Vec<RationalPoint> = points
+        .iter()
+        .map(|point| RationalPoint::from_point(point).expect("coordinates must be finite"))
+        .collect();
+    monotone_chain_by(points.len(), |a, b, c| {
+        rational_orientation(&exact[a], &exact[b], &exact[c]) == Orientation::CounterClockwise
+    })
+    .into_iter()
+    .map(|position| points[position])
+    .collect()
+}
@@ -0,0 +1,146 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Adaptive precision orientation
+//!
+//! The cross product in f64 rounds, and for points very close to a line
+//! the rounding can be larger than the product itself, so the turn
+//! comes out with the wrong sign and a scan keeps a point it should
+//! drop. `orient2d` gets the sign right for any finite coordinates: it
+//! takes the f64 product when an error bound shows its sign is certain,
+//! and only otherwise redoes it with more and more of the rounding
+//! errors kept as floating point expansions. With the `robust` feature
+//! `points::orietation` and `Point2D::ccw`, the turns of Graham scan,
+//! Jarvis march and the other algorithms on f64 points, go through it.
+//!
+//! J. R. Shewchuk, "Adaptive Precision Floating-Point Arithmetic and
+//! Fast Robust Geometric Predicates", Discrete & Computational
+//! Geometry, 1997
+use points::*;
+
+/// Half of the distance from 1.0 to the next f64
+const EPSILON: f64 = f64::EPSILON / 2.0;
+/// 2^27 + 1, splits an f64 into two halves of 26 bits
+const SPLITTER: f64 = 134_217_729.0;
+const RESULT_ERROR_BOUND: f64 = (3.0 + 8.0 * EPSILON) * EPSILON;
+const CCW_ERROR_BOUND_A: f64 = (3.0 + 16.0 * EPSILON) * EPSILON;
+const CCW_ERROR_BOUND_B: f64 = (2.0 + 12.0 * EPSILON) * EPSILON;
+const CCW_ERROR_BOUND_C: f64 = (9.0 + 64.0 * EPSILON) * EPSILON * EPSILON;
+
+/// The rounded sum and its rounding error
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    (x, (a - a_virtual) + (b - b_virtual))
+}
+
+/// The rounding error of x = a - b
+fn two_diff_tail(a: f64, b: f64, x: f64) -> f64 {
+    let b_virtual = a - x;
+    let a_virtual = x + b_virtual;
+    (a - a_virtual) + (b_virtual - b)
+}
+
+fn split(a: f64) -> (f64, f64) {
+    let c = SPLITTER * a;
+    let high = c - (c - a);
+    (high, a - high)
+}
+
+/// The rounded product and its rounding error
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    let (a_high, a_low) = split(a);
+    let (b_high, b_low) = split(b);
+    let error = x - a_high * b_high - a_low * b_high - a_high * b_low;
+    (x, a_low * b_low - error)
+}
+
+/// Adds a number to an expansion, whose components are in increasing
+/// magnitude and do not overlap, leaving out zero components
+fn grow_expansion(expansion: &[f64], b: f64) -> Vec<f64> {
+    let mut sum = Vec::with_capacity(expansion.len() + 1);
+    let mut q = b;
+    for &component in expansion {
+        let (x, error) = two_sum(q, component);
+        if error != 0.0 {
+            sum.push(error);
+        }
+        q = x;
+    }
+    if q != 0.0 || sum.is_empty() {
+        sum.push(q);
+    }
+    sum
+}
+
+/// The exact value of ab - cd, with both products already split into
+/// their rounded values and errors
+fn products_difference(ab: (f64, f64), cd: (f64, f64)) -> Vec<f64> {
+    let expansion = grow_expansion(&[ab.1], ab.0);
+    grow_expansion(&grow_expansion(&expansion, -cd.1), -cd.0)
+}
+
+fn add_expansions(a: &[f64], b: &[f64]) -> Vec<f64> {
+    b.iter().fold(a.to_vec(), |sum, &component| grow_expansion(&sum, component))
+}
+
+///Which way the path from a over b to c turns, positive for a counter
+/// clockwise turn, negative for a clockwise one and zero only when the
+/// points are exactly on a line
+///
+/// The value has the sign of the exact cross product, but only its
+/// magnitude when that is far from the rounding errors. Most calls
+/// cost no more than `points::orietation` and a comparison.
+pub fn orient2d(a: &Point2D, b: &Point2D, c: &Point2D) -> f64 {
+    let left = (a.x - c.x) * (b.y - c.y);
+    let right = (a.y - c.y) * (b.x - c.x);
+    let det = left - right;
+    let sum = if left > 0.0 && right > 0.0 {
+        left + right
+    } else if left < 0.0 && right < 0.0 {
+        -left - right
+    } else {
+        //the products have different signs or one is zero, and their
+        //difference has the right sign
+        return det;
+    };
+    if det.abs() >= CCW_ERROR_BOUND_A * sum {
+        return det;
+    }
+    orient2d_adapt(a, b, c, sum)
+}
+
+/// The later stages of orient2d, each adding rounding errors the
+/// previous one left out, until the sign is certain
+fn orient2d_adapt(a: &Point2D, b: &Point2D, c: &Point2D, sum: f64) -> f64 {
+    let (acx, bcx, acy, bcy) = (a.x - c.x, b.x - c.x, a.y - c.y, b.y - c.y);
+    //the exact products of the rounded differences
+    let products = products_difference(two_product(acx, bcy), two_product(acy, bcx));
+    let mut det: f64 = products.iter().sum();
+    if det.abs() >= CCW_ERROR_BOUND_B * sum {
+        return det;
+    }
+    let acx_tail = two_diff_tail(a.x, c.x, acx);
+    let bcx_tail = two_diff_tail(b.x, c.x, bcx);
+    let acy_tail = two_diff_tail(a.y, c.y, acy);
+    let bcy_tail = two_diff_tail(b.y, c.y, bcy);
+    if acx_tail == 0.0 && acy_tail == 0.0 && bcx_tail == 0.0 && bcy_tail == 0.0 {
+        return det;
+    }
+    let bound = CCW_ERROR_BOUND_C * sum + RESULT_ERROR_BOUND * det.abs();
+    det += (acx * bcy_tail + bcy * acx_tail) - (acy * bcx_tail + bcx * acy_tail);
+    if det.abs() >= bound {
+        return det;
+    }
+    //the exact cross product of the differences with their tails
+    let first = products_difference(two_product(acx_tail, bcy), two_product(acy_tail, bcx));
+    let second = products_difference(two_product(acx, bcy_tail), two_product(acy, bcx_tail));
+    let third = products_difference(two_product(acx_tail, bcy_tail), two_product(acy_tail, bcx_tail));
+    let exact = add_expansions(&add_expansions(&add_expansions(&products, &first), &second), &third);
+    //the largest component has the sign of the whole expansion
+    exact[exact.len() - 1]
+}
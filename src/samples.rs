@@ -0,0 +1,54 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Small input sets built at compile time
+//!
+//! These are ready to use Point2D values, handy for examples,
+//! tests and quick checks of the hull algorithms without
+//! generating or converting an input set first.
+use points::Point2D;
+
+/// The corners of a 2 x 2 square and its center
+pub const SQUARE_WITH_CENTER: [Point2D; 5] = [
+    Point2D::new(0.0, 0.0),
+    Point2D::new(2.0, 0.0),
+    Point2D::new(1.0, 1.0),
+    Point2D::new(2.0, 2.0),
+    Point2D::new(0.0, 2.0),
+];
+
+/// Hull of SQUARE_WITH_CENTER in counter clockwise order
+pub const SQUARE_HULL: [Point2D; 4] = [
+    Point2D::new(0.0, 0.0),
+    Point2D::new(2.0, 0.0),
+    Point2D::new(2.0, 2.0),
+    Point2D::new(0.0, 2.0),
+];
+
+/// A triangle with points on its edges and inside it
+pub const TRIANGLE_WITH_EDGE_POINTS: [Point2D; 7] = [
+    Point2D::new(0.0, 0.0),
+    Point2D::new(2.0, 0.0),
+    Point2D::new(4.0, 0.0),
+    Point2D::new(3.0, 1.0),
+    Point2D::new(2.0, 2.0),
+    Point2D::new(1.0, 1.0),
+    Point2D::new(2.0, 0.5),
+];
+
+/// Hull of TRIANGLE_WITH_EDGE_POINTS in counter clockwise order
+pub const TRIANGLE_HULL: [Point2D; 3] = [
+    Point2D::new(0.0, 0.0),
+    Point2D::new(4.0, 0.0),
+    Point2D::new(2.0, 2.0),
+];
+
+/// Points that all lie on one line, they have no hull with an area
+pub const COLLINEAR: [Point2D; 4] = [
+    Point2D::new(0.0, 0.0),
+    Point2D::new(1.0, 1.0),
+    Point2D::new(2.0, 2.0),
+    Point2D::new(3.0, 3.0),
+];
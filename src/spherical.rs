@@ -0,0 +1,112 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Convex hulls on the sphere
+//!
+//! Longitude and latitude are not a plane: the planar algorithms go
+//! wrong near the poles and across the antimeridian, where points
+//! close on the globe are far apart on the map. Lifted to unit vectors
+//! in space the great circles are the planes through the centre of the
+//! sphere, and the hull is found in a plane touching the sphere
+//! instead, where those circles are straight lines.
+use convexhull::convex_hull_of;
+use hull3d::convex_hull_3d;
+use points::*;
+
+/// The unit vector of a longitude and latitude in degrees
+fn lift(point: &Point2D) -> Point3D {
+    let (lon, lat) = (point.x.to_radians(), point.y.to_radians());
+    Point3D::new(lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin())
+}
+
+fn dot(u: &Point3D, v: &Point3D) -> f64 {
+    u.x * v.x + u.y * v.y + u.z * v.z
+}
+
+fn cross(u: &Point3D, v: &Point3D) -> Point3D {
+    Point3D::new(u.y * v.z - u.z * v.y, u.z * v.x - u.x * v.z, u.x * v.y - u.y * v.x)
+}
+
+fn unit(u: &Point3D) -> Point3D {
+    let length = dot(u, u).sqrt();
+    Point3D::new(u.x / length, u.y / length, u.z / length)
+}
+
+fn sum<I: Iterator<Item = Point3D>>(vectors: I) -> Point3D {
+    vectors.fold(Point3D::new(0.0, 0.0, 0.0), |sum, u| Point3D::new(sum.x + u.x, sum.y + u.y, sum.z + u.z))
+}
+
+///The geodesic hull of points given as longitude (x) and latitude (y)
+/// in degrees, or None when no open hemisphere holds them
+///
+/// The hull vertices are returned counter clockwise as seen from
+/// outside the sphere, the same way as the planar hulls on a map, and
+/// its edges are the shorter great circle arcs between them. A hull
+/// only exists when the points are on one side of a great circle.
+///
+/// The points are projected from the centre of the sphere onto the
+/// plane touching it at their mean direction, which maps great circles
+/// to lines, and their planar hull is found in O(n log n). When some
+/// point is a quarter turn or more from that direction, the 3D hull of
+/// the unit vectors and the centre of the sphere decides: the centre is
+/// one of its vertices only when the points are in a hemisphere, and
+/// the normals of the faces around it point away from them. Points all
+/// on one great circle have no such faces and are only found in a
+/// hemisphere from their mean.
+///
+/// # Panics
+/// Panics when a coordinate is NaN
+pub fn spherical_hull(points: &[Point2D]) -> Option<Vec<Point2D>> {
+    if points.is_empty() {
+        return Some(Vec::new());
+    }
+    let lifted: Vec<Point3D> = points.iter().map(lift).collect();
+    let mean = sum(lifted.iter().cloned());
+    let mut center = unit(&mean);
+    if !lifted.iter().all(|point| dot(point, &center) > 0.0) {
+        center = hemisphere(&lifted)?;
+    }
+    //east and north at the center, seen from outside
+    let axis = if center.z.abs() < 0.9 { Point3D::new(0.0, 0.0, 1.0) } else { Point3D::new(1.0, 0.0, 0.0) };
+    let east = unit(&cross(&axis, &center));
+    let north = cross(&center, &east);
+    let projected: Vec<(Point2D, Point2D)> = lifted
+        .iter()
+        .zip(points)
+        .map(|(point, &original)| {
+            let height = dot(point, &center);
+            (Point2D::new(dot(point, &east) / height, dot(point, &north) / height), original)
+        })
+        .collect();
+    Some(convex_hull_of(&projected).into_iter().map(|vertex| vertex.1).collect())
+}
+
+/// A direction all of the unit vectors are less than a quarter turn
+/// from, if there is one
+fn hemisphere(lifted: &[Point3D]) -> Option<Point3D> {
+    let origin = Point3D::new(0.0, 0.0, 0.0);
+    let mut points = vec![origin];
+    points.extend_from_slice(lifted);
+    let normals: Vec<Point3D> = convex_hull_3d(&points)
+        .into_iter()
+        .filter(|face| face.contains(&0))
+        .map(|face| {
+            let (a, b, c) = (points[face[0]], points[face[1]], points[face[2]]);
+            let ab = Point3D::new(b.x - a.x, b.y - a.y, b.z - a.z);
+            let ac = Point3D::new(c.x - a.x, c.y - a.y, c.z - a.z);
+            unit(&cross(&ab, &ac))
+        })
+        .collect();
+    if normals.is_empty() {
+        return None;
+    }
+    let inwards = sum(normals.into_iter());
+    let center = unit(&Point3D::new(-inwards.x, -inwards.y, -inwards.z));
+    if lifted.iter().all(|point| dot(point, &center) > 0.0) {
+        Some(center)
+    } else {
+        None
+    }
+}
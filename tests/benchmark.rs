@@ -0,0 +1,153 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+#![cfg(feature = "benchmark")]
+
+extern crate rustalgo;
+use rustalgo::benchmark::*;
+use std::fs;
+use std::time::Duration;
+
+#[test]
+fn test_reject_outliers() {
+    let samples = vec![10.0, 10.2, 9.9, 10.1, 10.0, 50.0];
+    let measurement = Measurement::new(samples.clone(), true);
+    assert_eq!(1, measurement.rejected());
+    assert_eq!(&[10.0, 10.2, 9.9, 10.1, 10.0], measurement.samples());
+    assert_eq!(&samples[..], measurement.raw_samples());
+    assert_eq!(10.0, measurement.median());
+    // without rejection the outlier counts
+    let measurement = Measurement::new(samples.clone(), false);
+    assert_eq!(0, measurement.rejected());
+    assert_eq!(&samples[..], measurement.samples());
+}
+
+#[test]
+fn test_reject_outliers_zero_deviation() {
+    // more than half the samples are equal, so the MAD is 0 and no
+    // sample can be scaled by it
+    let samples = vec![5.0, 5.0, 5.0, 5.0, 9.0];
+    let measurement = Measurement::new(samples.clone(), true);
+    assert_eq!(0, measurement.rejected());
+    assert_eq!(&samples[..], measurement.samples());
+    // too few samples to tell an outlier
+    let measurement = Measurement::new(vec![1.0, 100.0], true);
+    assert_eq!(0, measurement.rejected());
+}
+
+#[test]
+fn test_report_rejected() {
+    let mut options = Options::new(false);
+    options.reject_outliers = true;
+    let output = vec![
+        (100, Measurement::new(vec![1.0, 1.0, 1.1, 0.9, 8.0], true)),
+        (1000, Measurement::new(vec![2.0, 2.0, 2.0], true)),
+    ];
+    let mut report: Vec<u8> = Vec::new();
+    write_report(&mut report, "Graham_Circle", &output, &options).unwrap();
+    let report = String::from_utf8(report).unwrap();
+    let lines: Vec<&str> = report.lines().collect();
+    assert_eq!(vec!["Graham_Circle : [(100, 1.0), (1000, 2.0)]", "Graham_Circle_rejected : [(100, 1), (1000, 0)]"], lines);
+    // the rejected counts are left out when outliers are kept
+    options.reject_outliers = false;
+    let mut report: Vec<u8> = Vec::new();
+    write_report(&mut report, "Graham_Circle", &output, &options).unwrap();
+    assert_eq!("Graham_Circle : [(100, 1.0), (1000, 2.0)]\n", String::from_utf8(report).unwrap());
+}
+
+#[test]
+fn test_dump_samples() {
+    let dir = std::env::temp_dir().join(format!("rustalgo_dump_samples_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let mut options = Options::new(false);
+    options.reject_outliers = true;
+    options.dump_samples = Some(dir.clone());
+    let graham = vec![
+        (100, Measurement::new(vec![1.5, 1.25, 1.5, 1.75, 9.0], true)),
+        (1000, Measurement::new(vec![2.5, 2.75], true)),
+    ];
+    assert_eq!(1, graham[0].1.rejected());
+    let jarvis = vec![(100, Measurement::new(vec![0.5], true))];
+    let mut report: Vec<u8> = Vec::new();
+    write_report(&mut report, "Graham_Circle", &graham, &options).unwrap();
+    write_report(&mut report, "Jarvis_Circle", &jarvis, &options).unwrap();
+    let mut files: Vec<String> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    files.sort();
+    assert_eq!(vec!["Graham_Circle_100.txt", "Graham_Circle_1000.txt", "Jarvis_Circle_100.txt"], files);
+    // every sample in the order it was taken, the rejected outlier too
+    assert_eq!("1.5\n1.25\n1.5\n1.75\n9\n", fs::read_to_string(dir.join("Graham_Circle_100.txt")).unwrap());
+    assert_eq!("2.5\n2.75\n", fs::read_to_string(dir.join("Graham_Circle_1000.txt")).unwrap());
+    assert_eq!("0.5\n", fs::read_to_string(dir.join("Jarvis_Circle_100.txt")).unwrap());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_is_throttled() {
+    // a frequency drop of more than 10% below the highest reading
+    assert!(is_throttled(&[1.0, 1.0], &[3000.0, 2600.0, 3000.0]));
+    assert!(!is_throttled(&[1.0, 1.0], &[3000.0, 2800.0, 3000.0]));
+    assert!(!is_throttled(&[1.0, 1.0], &[]));
+    // runs getting slower and slower
+    assert!(is_throttled(&[1.0, 1.05, 1.1, 1.15, 1.2], &[]));
+    // not slowing down enough, or not every time
+    assert!(!is_throttled(&[1.0, 1.01, 1.02, 1.03, 1.04], &[]));
+    assert!(!is_throttled(&[1.0, 1.05, 1.1, 1.08, 1.2], &[]));
+    // too few runs to tell a trend from noise
+    assert!(!is_throttled(&[1.0, 1.2, 1.5], &[]));
+    assert!(!is_throttled(&[1.0, 1.2, 1.4, 1.6], &[]));
+}
+
+#[test]
+fn test_cpu_frequency() {
+    assert_eq!(Some(2400.0), parse_frequency("2400000\n"));
+    assert_eq!(None, parse_frequency(""));
+    assert_eq!(None, parse_frequency("unknown"));
+    // readable only where cpufreq is in sysfs
+    if let Some(frequency) = cpu_frequency() {
+        assert!(frequency > 0.0);
+    }
+}
+
+#[test]
+fn test_cooldown() {
+    let mut options = Options::new(false);
+    options.cooldown_ms = 250;
+    assert_eq!(Duration::from_millis(250), cooldown(true, &options));
+    assert_eq!(Duration::from_millis(0), cooldown(false, &options));
+    options.cooldown_ms = 0;
+    assert_eq!(Duration::from_millis(0), cooldown(true, &options));
+}
+
+#[test]
+fn test_speedup_table() {
+    let columns = vec!["ParallelQuickHull/QuickHull".to_string(), "DivideAndConquer(2)/DivideAndConquer(1)".to_string()];
+    let rows = vec![(100, vec![0.5, 0.25]), (1000000, vec![3.5, 1.875])];
+    let mut table: Vec<u8> = Vec::new();
+    write_speedup_table(&mut table, "Speedup_Circle", &columns, &rows).unwrap();
+    let table = String::from_utf8(table).unwrap();
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(
+        vec![
+            "Speedup_Circle ParallelQuickHull/QuickHull DivideAndConquer(2)/DivideAndConquer(1)",
+            "100                               0.50                                    0.25",
+            "1000000                           3.50                                    1.88",
+        ],
+        lines
+    );
+}
+
+#[test]
+fn test_to_fixed_point() {
+    use rustalgo::points::Point2D;
+    let unit = 1i64 << FIXED_POINT_BITS;
+    let points = vec![Point2D::new(1.0, -2.0), Point2D::new(0.5, 0.0)];
+    assert_eq!(vec![Point2D::new(unit, -2 * unit), Point2D::new(unit / 2, 0)], to_fixed_point(&points));
+    //rounded to the nearest step
+    let step = 1.0 / unit as f64;
+    assert_eq!(vec![Point2D::new(1, 0)], to_fixed_point(&[Point2D::new(0.75 * step, 0.25 * step)]));
+}
@@ -0,0 +1,55 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+extern crate rustalgo;
+use rustalgo::cache::*;
+use rustalgo::convexhull::Algorithm::*;
+use rustalgo::points::*;
+
+fn diamond() -> Vec<Point2D> {
+    vec![
+        Point2D::new(0.0, -2.0),
+        Point2D::new(0.0, 0.0),
+        Point2D::new(2.0, 0.0),
+        Point2D::new(0.0, 2.0),
+        Point2D::new(-2.0, 0.0),
+    ]
+}
+
+#[test]
+fn test_fingerprint() {
+    let mut reordered = diamond();
+    reordered.swap(0, 1);
+    assert_eq!(fingerprint(&diamond()), fingerprint(&diamond()));
+    assert!(fingerprint(&diamond()) != fingerprint(&reordered));
+}
+
+#[test]
+fn test_hull_cache() {
+    let mut cache = HullCache::new(Graham, 1);
+    let hull_should_be = vec![
+        Point2D::new(-2.0, 0.0),
+        Point2D::new(0.0, -2.0),
+        Point2D::new(2.0, 0.0),
+        Point2D::new(0.0, 2.0),
+    ];
+    assert_eq!(&hull_should_be, cache.hull(&diamond()));
+    assert_eq!((0, 1), (cache.hits(), cache.misses()));
+    let first: *const Vec<Point2D> = cache.hull(&diamond());
+    assert_eq!(&hull_should_be, cache.hull(&diamond()));
+    assert!(std::ptr::eq(first, cache.hull(&diamond())));
+    assert_eq!((3, 1), (cache.hits(), cache.misses()));
+    assert_eq!(1, cache.len());
+    // the capacity of one drops the diamond for the next input set
+    let mut shifted = diamond();
+    shifted[1] = Point2D::new(0.5, 0.0);
+    cache.hull(&shifted);
+    assert_eq!((3, 2), (cache.hits(), cache.misses()));
+    assert_eq!(1, cache.len());
+    cache.hull(&diamond());
+    assert_eq!((3, 3), (cache.hits(), cache.misses()));
+    cache.clear();
+    assert!(cache.is_empty());
+}
@@ -0,0 +1,255 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+extern crate rustalgo;
+mod common;
+use common::scattered;
+use rustalgo::calipers::*;
+use rustalgo::convexhull::*;
+use rustalgo::points::*;
+use rustalgo::samples::*;
+
+fn distance(a: &Point2D, b: &Point2D) -> f64 {
+    ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt()
+}
+
+// the hull of points scattered over an ellipse
+fn scattered_hull() -> Vec<Point2D> {
+    convex_hull_of(&scattered(500, 0.0))
+}
+
+#[test]
+fn test_hull_diameter() {
+    let hull = scattered_hull();
+    let mut longest = 0.0;
+    for a in &hull {
+        for b in &hull {
+            longest = distance(a, b).max(longest);
+        }
+    }
+    let (a, b, length) = hull_diameter(&hull);
+    assert_eq!(longest, length);
+    assert_eq!(length, distance(&a, &b));
+    // clockwise, as from other libraries
+    let mut clockwise = hull.clone();
+    clockwise.reverse();
+    assert_eq!(longest, hull_diameter(&clockwise).2);
+    assert_eq!(8.0_f64.sqrt(), hull_diameter(&SQUARE_HULL).2);
+    assert_eq!(4.0, hull_diameter(&TRIANGLE_HULL).2);
+    assert_eq!(0.0, hull_diameter(&[Point2D::new(1.0, 1.0)]).2);
+    assert_eq!(5.0, hull_diameter(&[Point2D::new(0.0, 0.0), Point2D::new(3.0, 4.0)]).2);
+}
+
+// the pair sorted, for comparing pairs in any order
+fn sorted(a: Point2D, b: Point2D) -> (Point2D, Point2D) {
+    if (a.x, a.y) < (b.x, b.y) {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+#[test]
+fn test_antipodal_pairs() {
+    for hull in [scattered_hull(), SQUARE_HULL.to_vec(), TRIANGLE_HULL.to_vec()] {
+        let n = hull.len();
+        // two vertices are antipodal when one of them is farthest along
+        // the outer normal of an edge at either of them and the other one
+        // farthest against it
+        let normal = |edge: usize| {
+            let (a, b) = (hull[edge % n], hull[(edge + 1) % n]);
+            (b.y - a.y, a.x - b.x)
+        };
+        let farthest_along = |vertex: usize, (x, y): (f64, f64)| hull.iter().all(|point| x * point.x + y * point.y <= x * hull[vertex].x + y * hull[vertex].y);
+        let mut expected = Vec::new();
+        for a in 0..n {
+            for b in a + 1..n {
+                let antipodal = [a + n - 1, a, b + n - 1, b].iter().any(|&edge| {
+                    let (x, y) = normal(edge);
+                    (farthest_along(a, (x, y)) && farthest_along(b, (-x, -y))) || (farthest_along(b, (x, y)) && farthest_along(a, (-x, -y)))
+                });
+                if antipodal {
+                    expected.push(sorted(hull[a], hull[b]));
+                }
+            }
+        }
+        let mut pairs: Vec<(Point2D, Point2D)> = antipodal_pairs(&hull).map(|(a, b)| sorted(a, b)).collect();
+        let mut clockwise = hull.clone();
+        clockwise.reverse();
+        let mut reversed: Vec<(Point2D, Point2D)> = antipodal_pairs(&clockwise).map(|(a, b)| sorted(a, b)).collect();
+        for pairs in [&mut expected, &mut pairs, &mut reversed] {
+            pairs.sort_by(|p, q| (p.0.x, p.0.y, p.1.x, p.1.y).partial_cmp(&(q.0.x, q.0.y, q.1.x, q.1.y)).unwrap());
+        }
+        assert_eq!(expected, pairs);
+        assert_eq!(expected, reversed);
+    }
+    // every pair of the square's vertices is on two parallel sides
+    assert_eq!(6, antipodal_pairs(&SQUARE_HULL).count());
+    assert_eq!(1, antipodal_pairs(&[Point2D::new(0.0, 0.0), Point2D::new(3.0, 4.0)]).count());
+    assert_eq!(0, antipodal_pairs(&[Point2D::new(1.0, 1.0)]).count());
+    assert_eq!(0, antipodal_pairs(&[]).count());
+}
+
+#[test]
+fn test_hull_width() {
+    let hull = scattered_hull();
+    // the width is the least extent over all edge directions
+    let least = (0..hull.len())
+        .map(|i| {
+            let (a, b) = (hull[i], hull[(i + 1) % hull.len()]);
+            hull.iter().map(|point| orietation(&a, &b, point)).fold(0.0, f64::max) / distance(&a, &b)
+        })
+        .fold(f64::INFINITY, f64::min);
+    let slab = hull_width(&hull);
+    assert!((least - slab.width).abs() < 1e-12);
+    assert!(slab.width < 2.0);
+    assert!(hull.contains(&slab.vertex) && hull.contains(&slab.edge.0) && hull.contains(&slab.edge.1));
+    assert_eq!(2.0, hull_width(&SQUARE_HULL).width);
+    // the height onto the longest edge
+    assert_eq!(2.0, hull_width(&TRIANGLE_HULL).width);
+    assert_eq!(Point2D::new(2.0, 2.0), hull_width(&TRIANGLE_HULL).vertex);
+    assert_eq!(0.0, hull_width(&[Point2D::new(0.0, 0.0), Point2D::new(3.0, 4.0)]).width);
+}
+
+#[test]
+fn test_largest_inscribed_triangle() {
+    let hull = scattered_hull();
+    let n = hull.len();
+    let mut largest: f64 = 0.0;
+    for i in 0..n {
+        for j in i + 1..n {
+            for k in j + 1..n {
+                largest = largest.max(orietation(&hull[i], &hull[j], &hull[k]).abs() / 2.0);
+            }
+        }
+    }
+    let (corners, area) = largest_inscribed_triangle(&hull);
+    assert!((largest - area).abs() < 1e-12);
+    assert!((orietation(&corners[0], &corners[1], &corners[2]) / 2.0 - area).abs() < 1e-12);
+    assert!(corners.iter().all(|corner| hull.contains(corner)));
+    // clockwise, as from other libraries
+    let mut clockwise = hull.clone();
+    clockwise.reverse();
+    assert!((largest - largest_inscribed_triangle(&clockwise).1).abs() < 1e-12);
+    assert_eq!(2.0, largest_inscribed_triangle(&SQUARE_HULL).1);
+    assert_eq!(4.0, largest_inscribed_triangle(&TRIANGLE_HULL).1);
+    assert_eq!(0.0, largest_inscribed_triangle(&[Point2D::new(0.0, 0.0), Point2D::new(3.0, 4.0)]).1);
+}
+
+#[test]
+fn test_min_area_rect() {
+    let hull = scattered_hull();
+    let rect = min_area_rect(&hull);
+    // the ellipse is 6 wide and 2 high, along the axes
+    assert!(rect.area > 11.0 && rect.area <= 12.0);
+    let (side, other) = (distance(&rect.corners[0], &rect.corners[1]), distance(&rect.corners[1], &rect.corners[2]));
+    assert!((side * other - rect.area).abs() < 1e-9);
+    for point in &hull {
+        for i in 0..4 {
+            assert!(orietation(&rect.corners[i], &rect.corners[(i + 1) % 4], point) > -1e-9);
+        }
+    }
+    // no other rotation does better
+    for step in 0..360 {
+        let angle = step as f64 * std::f64::consts::PI / 360.0;
+        let (u, v) = (Point2D::new(angle.cos(), angle.sin()), Point2D::new(-angle.sin(), angle.cos()));
+        let extent = |axis: &Point2D| {
+            let values: Vec<f64> = hull.iter().map(|point| axis.x * point.x + axis.y * point.y).collect();
+            values.iter().cloned().fold(f64::NEG_INFINITY, f64::max) - values.iter().cloned().fold(f64::INFINITY, f64::min)
+        };
+        assert!(extent(&u) * extent(&v) >= rect.area - 1e-9);
+    }
+    // a square turned by 30 degrees
+    let turned: Vec<Point2D> = SQUARE_WITH_CENTER
+        .iter()
+        .map(|point| {
+            let (sin, cos) = (0.5_f64, 0.75_f64.sqrt());
+            Point2D::new(cos * point.x - sin * point.y, sin * point.x + cos * point.y)
+        })
+        .collect();
+    let rect = min_area_rect(&turned);
+    assert!((rect.area - 4.0).abs() < 1e-12);
+    assert!((rect.angle.rem_euclid(std::f64::consts::FRAC_PI_2) - std::f64::consts::FRAC_PI_6).abs() < 1e-12);
+    assert_eq!(0.0, min_area_rect(&COLLINEAR).area);
+    assert_eq!([Point2D::new(1.0, 1.0); 4], min_area_rect(&[Point2D::new(1.0, 1.0); 2]).corners);
+}
+
+#[test]
+fn test_min_perimeter_rect() {
+    // the rectangles along two of the hull edges have the same least
+    // area, but the second one has the lesser perimeter
+    let points = vec![
+        Point2D::new(7.0, 1.0),
+        Point2D::new(3.0, 5.0),
+        Point2D::new(9.0, 10.0),
+        Point2D::new(6.0, 4.0),
+        Point2D::new(2.0, 8.0),
+    ];
+    let by_area = min_area_rect(&points);
+    let by_perimeter = min_perimeter_rect(&points);
+    assert!(by_perimeter.perimeter() < by_area.perimeter() - 0.1);
+    assert!((by_area.area - by_perimeter.area).abs() < 1e-9);
+    assert_eq!(8.0, min_perimeter_rect(&SQUARE_WITH_CENTER).perimeter());
+    let hull = scattered_hull();
+    for step in 0..360 {
+        let angle = step as f64 * std::f64::consts::PI / 360.0;
+        let (u, v) = (Point2D::new(angle.cos(), angle.sin()), Point2D::new(-angle.sin(), angle.cos()));
+        let extent = |axis: &Point2D| {
+            let values: Vec<f64> = hull.iter().map(|point| axis.x * point.x + axis.y * point.y).collect();
+            values.iter().cloned().fold(f64::NEG_INFINITY, f64::max) - values.iter().cloned().fold(f64::INFINITY, f64::min)
+        };
+        assert!(2.0 * (extent(&u) + extent(&v)) >= min_perimeter_rect(&hull).perimeter() - 1e-9);
+    }
+}
+
+#[test]
+fn test_farthest_point_voronoi() {
+    let hull = scattered_hull();
+    let voronoi = farthest_point_voronoi(&hull);
+    let n = voronoi.sites.len();
+    assert_eq!(hull, voronoi.sites);
+    assert_eq!(n - 2, voronoi.vertices.len());
+    // a ray for every hull edge and a segment between the others
+    assert_eq!(2 * n - 3, voronoi.edges.len());
+    assert_eq!(n, voronoi.edges.iter().filter(|edge| edge.to.is_none()).count());
+    for (vertex, triangle) in voronoi.vertices.iter().zip(&voronoi.triangles) {
+        let radius = distance(vertex, &hull[triangle[0]]);
+        assert!((distance(vertex, &hull[triangle[1]]) - radius).abs() < 1e-9);
+        assert!((distance(vertex, &hull[triangle[2]]) - radius).abs() < 1e-9);
+        assert!(hull.iter().all(|site| distance(vertex, site) <= radius + 1e-9));
+    }
+    // the sites of an edge are the farthest along it
+    for edge in &voronoi.edges {
+        let start = voronoi.vertices[edge.from];
+        let step = edge.to.map_or(1.0, |to| distance(&start, &voronoi.vertices[to]) / 2.0);
+        let point = Point2D::new(start.x + step * edge.direction.x, start.y + step * edge.direction.y);
+        let farthest = hull.iter().map(|site| distance(&point, site)).fold(0.0, f64::max);
+        assert!((distance(&point, &hull[edge.sites.0]) - farthest).abs() < 1e-9);
+        assert!((distance(&point, &hull[edge.sites.1]) - farthest).abs() < 1e-9);
+    }
+    let (center, radius) = voronoi.min_enclosing_circle();
+    let (expected_center, expected_radius) = rustalgo::circle::min_enclosing_circle_in_order(&hull);
+    assert!((radius - expected_radius).abs() < 1e-9);
+    assert!(distance(&center, &expected_center) < 1e-6);
+}
+
+#[test]
+fn test_farthest_point_voronoi_of_few_sites() {
+    let triangle = farthest_point_voronoi(&TRIANGLE_WITH_EDGE_POINTS);
+    assert_eq!(vec![Point2D::new(2.0, 0.0)], triangle.vertices);
+    assert_eq!(vec![[2, 0, 1]], triangle.triangles);
+    let bottom = triangle.edges.iter().find(|edge| edge.sites == (0, 1)).unwrap();
+    assert_eq!((None, Point2D::new(0.0, 1.0)), (bottom.to, bottom.direction));
+    // the centre of the right angle is halfway along the long side
+    assert_eq!((Point2D::new(2.0, 0.0), 2.0), triangle.min_enclosing_circle());
+    // four sites on a circle have both vertices at its centre
+    let square = farthest_point_voronoi(&SQUARE_WITH_CENTER);
+    assert_eq!(vec![Point2D::new(1.0, 1.0); 2], square.vertices);
+    assert_eq!(5, square.edges.len());
+    assert_eq!(1.0, square.min_enclosing_circle().0.x);
+    let pair = farthest_point_voronoi(&COLLINEAR);
+    assert!(pair.vertices.is_empty() && pair.edges.is_empty());
+    assert_eq!((Point2D::new(1.5, 1.5), distance(&COLLINEAR[0], &COLLINEAR[3]) / 2.0), pair.min_enclosing_circle());
+}
@@ -0,0 +1,107 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+extern crate rustalgo;
+mod common;
+use common::scattered;
+use rustalgo::circle::*;
+use rustalgo::points::*;
+
+fn distance(a: &Point2D, b: &Point2D) -> f64 {
+    ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt()
+}
+
+// the least radius of the circles through two or three of the points
+// that hold all of them
+fn brute_force_radius(points: &[Point2D]) -> f64 {
+    let holds = |center: Point2D, radius: f64| points.iter().all(|point| distance(&center, point) <= radius * (1.0 + 1e-9));
+    let mut least = f64::INFINITY;
+    for a in points {
+        for b in points {
+            let center = Point2D::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0);
+            let radius = distance(a, b) / 2.0;
+            if radius < least && holds(center, radius) {
+                least = radius;
+            }
+            for c in points {
+                let d = 2.0 * ((b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x));
+                if d == 0.0 {
+                    continue;
+                }
+                let (b2, c2) = ((b.x - a.x).powi(2) + (b.y - a.y).powi(2), (c.x - a.x).powi(2) + (c.y - a.y).powi(2));
+                let center = Point2D::new(a.x + ((c.y - a.y) * b2 - (b.y - a.y) * c2) / d, a.y + ((b.x - a.x) * c2 - (c.x - a.x) * b2) / d);
+                let radius = distance(&center, a);
+                if radius < least && holds(center, radius) {
+                    least = radius;
+                }
+            }
+        }
+    }
+    least
+}
+
+#[test]
+fn test_min_enclosing_circle_in_order() {
+    assert_eq!((Point2D::new(1.0, 2.0), 0.0), min_enclosing_circle_in_order(&[Point2D::new(1.0, 2.0); 3]));
+    let pair = [Point2D::new(0.0, 0.0), Point2D::new(4.0, 0.0)];
+    assert_eq!((Point2D::new(2.0, 0.0), 2.0), min_enclosing_circle_in_order(&pair));
+    // on a line, with the ends in the middle of the input
+    let line: Vec<Point2D> = [1.0, 3.0, 0.0, 2.0, 4.0].iter().map(|&x| Point2D::new(x, x)).collect();
+    let (center, radius) = min_enclosing_circle_in_order(&line);
+    assert!(distance(&center, &Point2D::new(2.0, 2.0)) < 1e-12);
+    assert!((radius - 8f64.sqrt()).abs() < 1e-12);
+    // an obtuse triangle is held by the circle over its longest side
+    let triangle = [Point2D::new(0.0, 0.0), Point2D::new(4.0, 0.0), Point2D::new(2.0, 1.0)];
+    assert_eq!((Point2D::new(2.0, 0.0), 2.0), min_enclosing_circle_in_order(&triangle));
+    for k in 0..20 {
+        let points = scattered(3 + k * 3, k as f64 * 0.1);
+        let (center, radius) = min_enclosing_circle_in_order(&points);
+        assert!(points.iter().all(|point| distance(&center, point) <= radius * (1.0 + 1e-9)));
+        assert!((radius - brute_force_radius(&points)).abs() < 1e-9, "{}", k);
+    }
+}
+
+#[cfg(feature = "generators")]
+#[test]
+fn test_min_enclosing_circle() {
+    let points = scattered(2000, 0.3);
+    let (center, radius) = min_enclosing_circle_in_order(&points);
+    for circle in &[min_enclosing_circle(&points), min_enclosing_circle_of_hull(&points)] {
+        assert!(distance(&center, &circle.0) < 1e-9);
+        assert!((radius - circle.1).abs() < 1e-9);
+    }
+    // sorted along more than half of the unit circle, the worst order
+    // for the points as they are
+    let around: Vec<Point2D> = (0..500).map(|i| Point2D::new((i as f64 * 0.01).cos(), (i as f64 * 0.01).sin())).collect();
+    let (center, radius) = min_enclosing_circle(&around);
+    assert!(distance(&center, &Point2D::new(0.0, 0.0)) < 1e-9);
+    assert!((radius - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_max_inscribed_circle() {
+    use rustalgo::samples::*;
+    let (center, radius) = max_inscribed_circle(&SQUARE_HULL);
+    assert!(distance(&center, &Point2D::new(1.0, 1.0)) < 1e-12);
+    assert!((radius - 2f64.sqrt()).abs() < 1e-12);
+    // the center point leaves room for circles of radius 1
+    assert!((max_inscribed_circle(&SQUARE_WITH_CENTER).1 - 1.0).abs() < 1e-12);
+    assert_eq!((Point2D::new(0.5, 0.5), distance(&COLLINEAR[1], &COLLINEAR[2]) / 2.0), max_inscribed_circle(&COLLINEAR));
+    assert_eq!((Point2D::new(1.0, 2.0), 0.0), max_inscribed_circle(&[Point2D::new(1.0, 2.0); 3]));
+    // no point of a fine grid over the hull has more room
+    let points = scattered(60, 0.2);
+    let (center, radius) = max_inscribed_circle(&points);
+    let clearance = |at: &Point2D| points.iter().map(|point| distance(at, point)).fold(f64::INFINITY, f64::min);
+    assert!((clearance(&center) - radius).abs() < 1e-9);
+    let locator = rustalgo::hull::PointLocator::new(&rustalgo::convexhull::convex_hull_of(&points));
+    for i in 0..200 {
+        for j in 0..100 {
+            let at = Point2D::new(-2.0 + i as f64 * 0.03, -1.0 + j as f64 * 0.02);
+            if locator.contains(&at) {
+                assert!(clearance(&at) <= radius + 1e-9);
+            }
+        }
+    }
+}
@@ -0,0 +1,19 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Input sets shared by the integration tests
+use rustalgo::points::*;
+
+// points scattered over an ellipse, the angles and radii spread by
+// the fractional parts of multiples of the golden and silver ratios
+pub fn scattered(n: usize, seed: f64) -> Vec<Point2D> {
+    let fraction = |value: f64| value - value.floor();
+    (0..n)
+        .map(|i| {
+            let (angle, radius) = (fraction(i as f64 * 0.618_034 + seed) * std::f64::consts::TAU, fraction(i as f64 * 0.414_214 + seed));
+            Point2D::new(3.0 * radius * angle.cos() + 1.0, radius * angle.sin())
+        })
+        .collect()
+}
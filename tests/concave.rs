@@ -0,0 +1,32 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+extern crate rustalgo;
+use rustalgo::concave::*;
+use rustalgo::convexhull::*;
+use rustalgo::hull::twice_signed_area;
+use rustalgo::points::*;
+
+#[test]
+fn test_concave_hull() {
+    // a 10x10 lattice with a notch open to the right
+    let input_set: Vec<Point2D> = (0..100)
+        .map(|i| Point2D::new((i % 10) as f64, (i / 10) as f64))
+        .filter(|point| !(point.x >= 3.0 && point.y >= 3.0 && point.y <= 6.0))
+        .collect();
+    let hull = concave_hull(&input_set, 3);
+    assert_eq!(Point2D::new(0.0, 0.0), hull[0]);
+    assert!(hull.iter().all(|vertex| input_set.contains(vertex)));
+    // the notch from x = 2 to 9 and y = 2 to 7 is left out, but for
+    // the triangles cut off at its corners (3, 2) and (3, 7)
+    assert_eq!(2.0 * 47.0, twice_signed_area(&hull));
+    assert!(hull.contains(&Point2D::new(2.0, 4.0)));
+    let convex = convex_hull_of(&input_set);
+    assert_eq!(2.0 * 81.0, twice_signed_area(&convex));
+    // enough neighbours see across the notch
+    assert_eq!(2.0 * 81.0, twice_signed_area(&concave_hull(&input_set, 20)));
+    assert_eq!(convex, concave_hull(&input_set, input_set.len()));
+    assert_eq!(3, concave_hull(&[Point2D::new(0.0, 0.0), Point2D::new(1.0, 0.0), Point2D::new(0.0, 1.0)], 3).len());
+}
@@ -3,12 +3,18 @@
 // Please see the file LICENSE in the source
 // distribution of this software for license terms.
 
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+
 extern crate rustalgo;
 use rustalgo::points::*;
 use rustalgo::convexhull::*;
+#[cfg(feature = "generators")]
 use rustalgo::convexhull::Algorithm::*;
+#[cfg(feature = "generators")]
 use rustalgo::inputset::*;
+#[cfg(feature = "generators")]
 use rustalgo::inputset::Number::*;
+#[cfg(feature = "generators")]
 use rustalgo::inputset::Shape::*;
 
 #[test]
@@ -18,6 +24,68 @@ fn test_sort_input_with_smallinput() {
     sort_input(&mut vec![point]);
 }
 
+#[test]
+fn test_graham_scan_into_reused_buffer() {
+    let mut buffer: Vec<Point2D> = Vec::new();
+    let mut triangle = sort_input(&mut vec![
+        Point2D::new(0.0, -2.0),
+        Point2D::new(2.0, 1.0),
+        Point2D::new(0.0, 0.0),
+        Point2D::new(-2.0, 1.0),
+    ]);
+    graham_scan_into(&triangle, &mut buffer);
+    assert_eq!(graham_scan(&mut triangle), buffer);
+    let mut diamond = sort_input(&mut vec![
+        Point2D::new(0.0, -2.0),
+        Point2D::new(2.0, 0.0),
+        Point2D::new(0.0, 2.0),
+        Point2D::new(-2.0, 0.0),
+    ]);
+    graham_scan_into(&diamond, &mut buffer);
+    assert_eq!(graham_scan(&mut diamond), buffer);
+    assert_eq!(4, buffer.len());
+}
+
+#[cfg(feature = "allocator_api")]
+#[test]
+fn test_graham_scan_in() {
+    use std::alloc::Global;
+    let mut input_set = vec![
+        Point2D::new(0.0, -2.0),
+        Point2D::new(2.0, 0.0),
+        Point2D::new(0.0, 0.5),
+        Point2D::new(0.0, 2.0),
+        Point2D::new(-2.0, 0.0),
+    ];
+    let sorted_input_set = sort_input_in(&mut input_set.clone(), Global);
+    let hull = graham_scan_in(&sorted_input_set, Global);
+    assert_eq!(graham_scan(&mut sort_input(&mut input_set)), hull.to_vec());
+    //angles too close for f64 but not the same, which only exact turns tell apart
+    let max = i32::MAX;
+    let mut pixels = vec![Point2D::new(0, 0), Point2D::new(max, max - 1), Point2D::new(max - 1, max - 2), Point2D::new(0, 5)];
+    let sorted_pixels = sort_input_in(&mut pixels.clone(), Global);
+    assert_eq!(4, sorted_pixels.len());
+    assert_eq!(sort_input(&mut pixels), sorted_pixels.to_vec());
+}
+
+#[cfg(feature = "allocator_api")]
+#[test]
+fn test_jarvis_march_and_quick_hull_in() {
+    use std::alloc::Global;
+    let mut input_set = vec![
+        Point2D::new(0.0, -2.0),
+        Point2D::new(2.0, 0.0),
+        Point2D::new(0.0, 0.5),
+        Point2D::new(1.0, 1.0),
+        Point2D::new(0.0, 2.0),
+        Point2D::new(-2.0, 0.0),
+    ];
+    assert_eq!(jarvis_march(&mut input_set), jarvis_march_in(&input_set, Global).to_vec());
+    assert_eq!(quick_hull(&input_set), quick_hull_in(&input_set, Global).to_vec());
+    assert!(quick_hull_in::<f64, _>(&[], Global).is_empty());
+}
+
+#[cfg(feature = "generators")]
 fn test_algorithms(algorithm: Algorithm, shape: Shape, hull_should_be: &Vec<Point2D>) {
     let sample_sizes: Vec<Number> = vec![
         Hundred,
@@ -38,18 +106,19 @@ fn test_algorithms(algorithm: Algorithm, shape: Shape, hull_should_be: &Vec<Poin
     }
 }
 
+#[cfg(feature = "generators")]
 #[test]
 fn test_triangle() {
     let vertex_1 = Point2D::new(-1.0000000000000009, -1.7320508075688767);
     let vertex_2 = Point2D::new(2.0, 0.0);
     let vertex_3 = Point2D::new(-0.9999999999999996, 1.7320508075688776);
     let hull_should_be = vec![vertex_1, vertex_2, vertex_3];
-    let hull_should_be_jarvis = vec![vertex_1, vertex_3, vertex_2];
     test_algorithms(Graham, Triangle, &hull_should_be);
-    test_algorithms(Jarvis, Triangle, &hull_should_be_jarvis);
+    test_algorithms(Jarvis, Triangle, &hull_should_be);
     test_algorithms(Chan, Triangle, &hull_should_be);
 }
 
+#[cfg(feature = "generators")]
 #[test]
 fn test_rectangle() {
     
@@ -58,13 +127,14 @@ fn test_rectangle() {
     let vertex_3 = Point2D::new(0.00000000000000012246467991473532, 2.0);
     let vertex_4 = Point2D::new(-2.0, 0.00000000000000024492935982947064);
 
-    let hull_should_be = vec![vertex_1, vertex_2, vertex_3, vertex_4];
-    let hull_should_be_jarvis = vec![vertex_1, vertex_4, vertex_3, vertex_2];
+    // counter clockwise from the leftmost vertex
+    let hull_should_be = vec![vertex_4, vertex_1, vertex_2, vertex_3];
     test_algorithms(Graham, Rectangle, &hull_should_be);
-    test_algorithms(Jarvis, Rectangle, &hull_should_be_jarvis);
+    test_algorithms(Jarvis, Rectangle, &hull_should_be);
     test_algorithms(Chan, Rectangle, &hull_should_be);
 }
 
+#[cfg(feature = "generators")]
 #[test]
 fn test_circle() {
     
@@ -88,16 +158,620 @@ fn test_circle() {
     let vertex_18 = Point2D::new(-0.34729635533386244, -1.9696155060244158);
 
     let hull_should_be = vec![
-        vertex_1, vertex_2, vertex_3, vertex_4, vertex_5, vertex_6, vertex_7, vertex_8, vertex_9,
-        vertex_10, vertex_11, vertex_12, vertex_13, vertex_14, vertex_15, vertex_16, vertex_17,
-        vertex_18,
-    ];
-    let hull_should_be_jarvis = vec![
-        vertex_1, vertex_18, vertex_17, vertex_16, vertex_15, vertex_14, vertex_13, vertex_12,
-        vertex_11, vertex_10, vertex_9, vertex_8, vertex_7, vertex_6, vertex_5, vertex_4, vertex_3,
-        vertex_2,
+        vertex_14, vertex_15, vertex_16, vertex_17, vertex_18, vertex_1, vertex_2, vertex_3,
+        vertex_4, vertex_5, vertex_6, vertex_7, vertex_8, vertex_9, vertex_10, vertex_11,
+        vertex_12, vertex_13,
     ];
     test_algorithms(Graham, Circle, &hull_should_be);
-    test_algorithms(Jarvis, Circle, &hull_should_be_jarvis);
+    test_algorithms(Jarvis, Circle, &hull_should_be);
     test_algorithms(Chan, Circle, &hull_should_be);
 }
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "point 1 of the input set is not finite")]
+fn test_sort_input_with_nan() {
+    let point = Point2D::new(1.0, 2.0);
+    sort_input(&mut vec![point, Point2D::new(f64::NAN, 0.0), point]);
+}
+
+#[test]
+fn test_hull_indices() {
+    use rustalgo::samples::*;
+    let hull: Vec<Point2D> = hull_indices(&SQUARE_WITH_CENTER)
+        .into_iter()
+        .map(|index| SQUARE_WITH_CENTER[index])
+        .collect();
+    assert_eq!(SQUARE_HULL.to_vec(), hull);
+    assert_eq!(vec![0, 2, 4], hull_indices(&TRIANGLE_WITH_EDGE_POINTS));
+    assert_eq!(vec![0, 3], hull_indices(&COLLINEAR));
+}
+
+#[test]
+fn test_execute_on_payload() {
+    let input_set = vec![
+        (Point2D::new(1.0, 0.5), "inside"),
+        (Point2D::new(2.0, 0.0), "b"),
+        (Point2D::new(0.0, 0.0), "a"),
+        (Point2D::new(1.0, 2.0), "c"),
+        (Point2D::new(1.0, 2.0), "copy of c"),
+    ];
+    for &algorithm in &[Algorithm::Graham, Algorithm::Jarvis, Algorithm::Chan, Algorithm::QuickHull, Algorithm::Incremental, Algorithm::Delaunay] {
+        let labels: Vec<&str> = execute_on(algorithm, &input_set).into_iter().map(|vertex| vertex.1).collect();
+        assert_eq!(vec!["a", "b", "c"], labels, "{:?}", algorithm);
+    }
+}
+
+#[test]
+fn test_convex_hull_of_with_payload() {
+    let input_set = vec![
+        (Point2D::new(0.0, 0.0), "a"),
+        (Point2D::new(1.0, 0.5), "inside"),
+        (Point2D::new(2.0, 0.0), "b"),
+        (Point2D::new(1.0, 2.0), "c"),
+    ];
+    let labels: Vec<&str> = convex_hull_of(&input_set).into_iter().map(|vertex| vertex.1).collect();
+    assert_eq!(vec!["a", "b", "c"], labels);
+}
+
+#[test]
+fn test_convex_hull_of_sorted() {
+    use rustalgo::samples::*;
+    let mut input_set: Vec<Point2D> = (0..2000)
+        .map(|i| Point2D::new((i * 37 % 101) as f64, (i * 59 % 103) as f64))
+        .collect();
+    let expected = convex_hull_of(&input_set);
+    // out of order, sorted the usual way
+    assert_eq!(expected, convex_hull_of_sorted(&input_set));
+    input_set.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap()));
+    assert_eq!(expected, convex_hull_of_sorted(&input_set));
+    assert_eq!(2, convex_hull_of_sorted(&COLLINEAR).len());
+    assert_eq!(1, convex_hull_of_sorted(&[Point2D::new(1.0, 1.0); 4]).len());
+    assert!(convex_hull_of_sorted::<Point2D>(&[]).is_empty());
+}
+
+#[cfg(feature = "generators")]
+#[test]
+fn test_canonical_hulls() {
+    // a lattice with vertices level with the lowest one, and a skewed one
+    let lattice: Vec<Point2D> = (0..2000).map(|i| Point2D::new((i * 37 % 101) as f64, (i * 59 % 103) as f64)).collect();
+    let skewed: Vec<Point2D> = lattice.iter().map(|point| Point2D::new(point.x + 0.25 * point.y, point.y + 0.5 * point.x)).collect();
+    // and small sets on a 5 x 5 grid, with copies of the lowest point
+    let mut input_sets = vec![lattice, skewed];
+    let mut seed: u64 = 1;
+    let mut next = || {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((seed >> 33) % 5) as f64
+    };
+    for k in 0..400 {
+        input_sets.push((0..3 + k % 12).map(|_| Point2D::new(next(), next())).collect());
+    }
+    for input_set in &input_sets {
+        let expected = convex_hull_of(input_set);
+        for &algorithm in &[Graham, GrahamAngleFree, Jarvis, Chan, QuickHull, KirkpatrickSeidel, Incremental, DivideAndConquer(2), Auto, Delaunay] {
+            let hull = execute(algorithm, &mut prepare(algorithm, &mut input_set.clone()));
+            assert_eq!(expected, hull, "{:?} {:?}", algorithm, input_set);
+        }
+        let mut in_place = input_set.clone();
+        let size = graham_scan_in_place(&mut in_place);
+        assert_eq!(expected, in_place[..size].to_vec(), "{:?}", input_set);
+    }
+}
+
+#[test]
+fn test_merge_hulls() {
+    use rustalgo::samples::*;
+    let ellipse = |cx: f64, cy: f64| -> Vec<Point2D> {
+        (0..300)
+            .map(|i| {
+                let angle = i as f64 * 0.7;
+                Point2D::new(cx + 3.0 * angle.cos(), cy + 2.0 * angle.sin())
+            })
+            .collect()
+    };
+    for &(cx, cy) in &[(10.0, 0.0), (2.0, 1.0), (0.0, 0.0), (0.0, -20.0), (0.5, 0.25)] {
+        let (a, b) = (ellipse(0.0, 0.0), ellipse(cx, cy));
+        let both: Vec<Point2D> = a.iter().chain(b.iter()).cloned().collect();
+        let (hull_a, hull_b) = (convex_hull_of(&a), convex_hull_of(&b));
+        assert_eq!(convex_hull_of(&both), merge_hulls(&hull_a, &hull_b));
+        // clockwise and starting elsewhere
+        let mut clockwise: Vec<Point2D> = hull_b.iter().rev().cloned().collect();
+        clockwise.rotate_left(5);
+        assert_eq!(convex_hull_of(&both), merge_hulls(&hull_a, &clockwise));
+    }
+    // a hull inside the other one, with vertical edges at both ends
+    assert_eq!(SQUARE_HULL.to_vec(), merge_hulls(&SQUARE_HULL, &[Point2D::new(1.0, 1.0), Point2D::new(1.5, 1.0), Point2D::new(1.0, 1.5)]));
+    assert_eq!(SQUARE_HULL.to_vec(), merge_hulls(&SQUARE_HULL, &SQUARE_HULL));
+    assert_eq!(SQUARE_HULL.to_vec(), merge_hulls(&SQUARE_HULL, &[]));
+    assert_eq!(vec![Point2D::new(0.0, 0.0), Point2D::new(3.0, 3.0)], merge_hulls(&[Point2D::new(0.0, 0.0)], &[Point2D::new(3.0, 3.0), Point2D::new(1.0, 1.0)]));
+    assert!(merge_hulls(&[], &[]).is_empty());
+}
+
+#[test]
+fn test_partition_hull() {
+    use rustalgo::samples::*;
+    let partition = partition_hull(&TRIANGLE_WITH_EDGE_POINTS);
+    assert_eq!(vec![0, 2, 4], partition.hull);
+    assert_eq!(vec![1, 3, 5], partition.boundary);
+    assert_eq!(vec![6], partition.interior);
+}
+
+#[test]
+fn test_hull_mask() {
+    use rustalgo::samples::*;
+    let mask_should_be = vec![true, true, false, true, true];
+    assert_eq!(mask_should_be, hull_mask(&SQUARE_WITH_CENTER));
+}
+
+#[test]
+fn test_convex_hulls_by_key() {
+    let mut input_set = Vec::new();
+    for &(id, dx) in &[(1, 0.0), (2, 10.0)] {
+        input_set.push((Point2D::new(dx, 0.0), id));
+        input_set.push((Point2D::new(dx + 2.0, 0.0), id));
+        input_set.push((Point2D::new(dx + 1.0, 0.5), id));
+        input_set.push((Point2D::new(dx + 1.0, 2.0), id));
+    }
+    let hulls = convex_hulls_by_key(&input_set, |point| point.1);
+    assert_eq!(2, hulls.len());
+    assert_eq!(3, hulls[&1].len());
+    assert!(hulls[&2].iter().all(|vertex| vertex.1 == 2 && vertex.0.x >= 10.0));
+}
+
+#[test]
+fn test_convex_hulls_grouped() {
+    use rustalgo::samples::*;
+    let mut groups: Vec<Vec<Point2D>> = vec![
+        SQUARE_WITH_CENTER.to_vec(),
+        TRIANGLE_WITH_EDGE_POINTS.to_vec(),
+        COLLINEAR.to_vec(),
+        vec![Point2D::new(1.0, 1.0); 3],
+        Vec::new(),
+    ];
+    // more groups than fit in one batch
+    groups.extend((0..1000).map(|group| {
+        (0..group % 50)
+            .map(|i| Point2D::new(((i * 37 + group) % 101) as f64, ((i * 59) % 103) as f64))
+            .collect()
+    }));
+    let hulls = convex_hulls_grouped(&groups);
+    assert_eq!(groups.len(), hulls.len());
+    for (group, hull) in groups.iter().zip(&hulls) {
+        assert_eq!(&convex_hull_of(group), hull);
+    }
+    assert_eq!(TRIANGLE_HULL.to_vec(), hulls[1]);
+    assert!(hulls[4].is_empty());
+}
+
+#[test]
+fn test_chans_algorithm() {
+    use rustalgo::samples::*;
+    assert_eq!(SQUARE_HULL.to_vec(), chans_algorithm(&mut SQUARE_WITH_CENTER.to_vec()));
+    assert_eq!(TRIANGLE_HULL.to_vec(), chans_algorithm(&mut TRIANGLE_WITH_EDGE_POINTS.to_vec()));
+    // enough points on a lattice for several rounds of guesses, with duplicates
+    let mut input_set: Vec<Point2D> = (0..5000)
+        .map(|i| Point2D::new((i * 37 % 101) as f64, (i * 59 % 103) as f64))
+        .collect();
+    input_set.extend(input_set.clone());
+    let mut expected = convex_hull_of(&input_set);
+    let mut hull = chans_algorithm(&mut input_set);
+    expected.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap()));
+    hull.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap()));
+    assert_eq!(expected, hull);
+}
+
+#[test]
+fn test_chans_algorithm_with() {
+    let guesses = |schedule: GuessSchedule| (0..6).map(|t| schedule.guess(t, 100_000)).collect::<Vec<usize>>();
+    assert_eq!(vec![4, 16, 256, 65536, 100_000, 100_000], guesses(GuessSchedule::Squaring));
+    assert_eq!(vec![4, 8, 16, 32, 64, 128], guesses(GuessSchedule::Doubling));
+    let geometric = GuessSchedule::Geometric { start: 50, factor: 3 };
+    assert_eq!(vec![50, 150, 450, 1350, 4050, 12150], guesses(geometric));
+    assert_eq!(100_000, GuessSchedule::Squaring.guess(40, 100_000));
+    let input_set: Vec<Point2D> = (0..5000)
+        .map(|i| Point2D::new((i * 37 % 101) as f64, (i * 59 % 103) as f64))
+        .collect();
+    let expected = chans_algorithm(&mut input_set.clone());
+    for &schedule in &[
+        GuessSchedule::Doubling,
+        geometric,
+        GuessSchedule::Geometric { start: 0, factor: 0 },
+    ] {
+        assert_eq!(expected, chans_algorithm_with(&input_set, schedule));
+    }
+}
+
+#[test]
+fn test_jarvis_march_with() {
+    use rustalgo::samples::*;
+    let mut hull = jarvis_march_with(&TRIANGLE_WITH_EDGE_POINTS, JarvisOptions::default());
+    assert_eq!(TRIANGLE_HULL.to_vec(), hull);
+    let collinear = JarvisOptions { collinear: true };
+    hull = jarvis_march_with(&TRIANGLE_WITH_EDGE_POINTS, collinear);
+    let with_edge_points = vec![
+        Point2D::new(0.0, 0.0),
+        Point2D::new(2.0, 0.0),
+        Point2D::new(4.0, 0.0),
+        Point2D::new(3.0, 1.0),
+        Point2D::new(2.0, 2.0),
+        Point2D::new(1.0, 1.0),
+    ];
+    assert_eq!(with_edge_points, hull);
+    // copies of the first point and of other vertices
+    let mut input_set = TRIANGLE_WITH_EDGE_POINTS.to_vec();
+    input_set.extend(TRIANGLE_WITH_EDGE_POINTS.iter().cloned());
+    assert_eq!(3, jarvis_march_with(&input_set, JarvisOptions::default()).len());
+    assert_eq!(with_edge_points, jarvis_march_with(&input_set, collinear));
+    assert_eq!(2, jarvis_march_with(&COLLINEAR, JarvisOptions::default()).len());
+    assert_eq!(vec![Point2D::new(1.0, 1.0)], jarvis_march_with(&[Point2D::new(1.0, 1.0); 3], collinear));
+    // a lattice with many points on every edge
+    let grid: Vec<Point2D> = (0..400).map(|i| Point2D::new((i % 20) as f64, (i / 20) as f64)).collect();
+    let mut lattice = grid.clone();
+    lattice.extend(grid.clone());
+    let mut expected = convex_hull_of(&lattice);
+    hull = jarvis_march(&mut sort_input(&mut lattice));
+    expected.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap()));
+    hull.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap()));
+    assert_eq!(expected, hull);
+    // sort_input keeps one point per angle, the lattice starts at its pivot
+    assert_eq!(76, jarvis_march_with(&grid, collinear).len());
+}
+
+#[test]
+fn test_quick_hull() {
+    use rustalgo::samples::*;
+    let diamond = vec![
+        Point2D::new(0.0, -2.0),
+        Point2D::new(2.0, 0.0),
+        Point2D::new(0.5, 0.5),
+        Point2D::new(0.0, 2.0),
+        Point2D::new(1.0, 1.0),
+        Point2D::new(-2.0, 0.0),
+    ];
+    let hull = graham_scan(&mut sort_input(&mut diamond.clone()));
+    assert_eq!(hull, quick_hull(&diamond));
+    assert_eq!(SQUARE_HULL.to_vec(), quick_hull(&SQUARE_WITH_CENTER));
+    assert_eq!(TRIANGLE_HULL.to_vec(), quick_hull(&TRIANGLE_WITH_EDGE_POINTS));
+    assert_eq!(2, quick_hull(&COLLINEAR).len());
+    // (1, 0), (8, 0) and (9, 0) are equally far from the line through
+    // the first and the fifth point, only the outer two are vertices
+    let collinear_farthest = [
+        Point2D::new(0.0, 1.0),
+        Point2D::new(8.0, 0.0),
+        Point2D::new(1.0, 0.0),
+        Point2D::new(9.0, 0.0),
+        Point2D::new(11.0, 1.0),
+        Point2D::new(12.0, 12.0),
+    ];
+    assert_eq!(convex_hull_of(&collinear_farthest), quick_hull(&collinear_farthest));
+}
+
+#[test]
+fn test_graham_angle_free() {
+    use rustalgo::samples::*;
+    let mut square = prepare(Algorithm::GrahamAngleFree, &mut SQUARE_WITH_CENTER.to_vec());
+    assert_eq!(SQUARE_HULL.to_vec(), execute(Algorithm::GrahamAngleFree, &mut square));
+    let diamond = vec![
+        Point2D::new(0.0, -2.0),
+        Point2D::new(2.0, 0.0),
+        Point2D::new(0.0, 0.5),
+        Point2D::new(0.0, 2.0),
+        Point2D::new(-2.0, 0.0),
+    ];
+    assert_eq!(
+        graham_scan(&mut sort_input(&mut diamond.clone())),
+        graham_scan(&mut sort_input_angle_free(&mut diamond.clone()))
+    );
+}
+
+#[test]
+fn test_graham_scan_in_place() {
+    use rustalgo::samples::*;
+    let mut square = SQUARE_WITH_CENTER.to_vec();
+    let count = graham_scan_in_place(&mut square);
+    assert_eq!(SQUARE_HULL.to_vec(), square[..count].to_vec());
+    let diamond = vec![
+        Point2D::new(0.0, -2.0),
+        Point2D::new(2.0, 0.0),
+        Point2D::new(0.0, 0.5),
+        Point2D::new(0.0, 2.0),
+        Point2D::new(-2.0, 0.0),
+    ];
+    let mut in_place = diamond.clone();
+    let count = graham_scan_in_place(&mut in_place);
+    assert_eq!(graham_scan(&mut sort_input(&mut diamond.clone())), in_place[..count].to_vec());
+    let mut triangle = TRIANGLE_WITH_EDGE_POINTS.to_vec();
+    assert_eq!(3, graham_scan_in_place(&mut triangle));
+    let mut collinear = COLLINEAR.to_vec();
+    assert_eq!(2, graham_scan_in_place(&mut collinear));
+    // a lattice with collinear and repeated points, the pivot repeated too
+    let mut lattice: Vec<Point2D> = (0..3000)
+        .map(|i| Point2D::new((i * 37 % 41) as f64, (i * 59 % 43) as f64))
+        .collect();
+    let mut hull = quick_hull(&lattice);
+    let count = graham_scan_in_place(&mut lattice);
+    let mut in_place = lattice[..count].to_vec();
+    let compare = |a: &Point2D, b: &Point2D| a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap());
+    hull.sort_by(compare);
+    in_place.sort_by(compare);
+    assert_eq!(hull, in_place);
+    assert_eq!(3000, lattice.len());
+    assert_eq!(0, graham_scan_in_place::<f64>(&mut []));
+    assert_eq!(1, graham_scan_in_place(&mut [Point2D::new(1.0, 1.0); 3]));
+}
+
+#[test]
+fn test_convex_hull_auto() {
+    use rustalgo::samples::*;
+    assert_eq!(SQUARE_HULL.to_vec(), convex_hull_auto(&SQUARE_WITH_CENTER));
+    assert!(convex_hull_auto::<f64>(&[]).is_empty());
+    // mostly inside the quadrilateral of the extremes, so pruned first
+    let disc: Vec<Point2D> = (0..5000)
+        .map(|i| {
+            let (angle, radius) = (i as f64 * 2.4, (i as f64 / 5000.0).sqrt());
+            Point2D::new(radius * angle.cos(), radius * angle.sin())
+        })
+        .collect();
+    assert_eq!(quick_hull(&disc), convex_hull_auto(&disc));
+    // all on the hull
+    let ring: Vec<Point2D> = (0..5000)
+        .map(|i| Point2D::new((i as f64 * 2.4).cos(), (i as f64 * 2.4).sin()))
+        .collect();
+    assert_eq!(quick_hull(&ring), convex_hull_auto(&ring));
+}
+
+#[test]
+fn test_divide_and_conquer_hull() {
+    use rustalgo::samples::*;
+    // a lattice with collinear and repeated points across the chunks
+    let lattice: Vec<Point2D> = (0..5000)
+        .map(|i| Point2D::new((i * 37 % 41) as f64, (i * 59 % 43) as f64))
+        .collect();
+    for threads in 1..9 {
+        assert_eq!(quick_hull(&lattice), divide_and_conquer_hull(&lattice, threads));
+        assert_eq!(SQUARE_HULL.to_vec(), divide_and_conquer_hull(&SQUARE_WITH_CENTER, threads));
+        assert_eq!(2, divide_and_conquer_hull(&COLLINEAR, threads).len());
+    }
+    assert!(divide_and_conquer_hull::<f64>(&[], 4).is_empty());
+    // a column at x = 4 split between two chunks
+    let column: Vec<Point2D> = vec![(1, 0), (1, 3), (1, 4), (4, 4), (4, 2), (4, 0), (3, 0), (0, 3), (0, 0), (3, 3), (3, 1), (4, 3), (1, 1), (4, 3), (4, 2)]
+        .into_iter()
+        .map(|(x, y)| Point2D::new(x as f64, y as f64))
+        .collect();
+    assert_eq!(convex_hull_of(&column), divide_and_conquer_hull(&column, 4));
+    // small sets on a 5 x 5 grid, where most chunks share columns
+    for k in 0..400 {
+        let mut seed = k as u64 + 1;
+        let mut next = || {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) % 5
+        };
+        let grid: Vec<Point2D> = (0..6 + k % 11).map(|_| Point2D::new(next() as f64, next() as f64)).collect();
+        for threads in 2..6 {
+            assert_eq!(convex_hull_of(&grid), divide_and_conquer_hull(&grid, threads), "{:?} {}", grid, threads);
+        }
+    }
+}
+
+#[test]
+#[cfg(all(feature = "parallel", feature = "generators"))]
+fn test_parallel_quick_hull() {
+    use rustalgo::samples::*;
+    assert_eq!(SQUARE_HULL.to_vec(), parallel_quick_hull(&SQUARE_WITH_CENTER));
+    assert!(parallel_quick_hull::<f64>(&[]).is_empty());
+    // large enough to be split on several threads
+    let mut input_set = generate(Shape::Circle, Number::HundredThousand);
+    assert_eq!(quick_hull(&input_set), execute(Algorithm::ParallelQuickHull, &mut input_set));
+}
+
+#[test]
+fn test_kirkpatrick_seidel() {
+    use rustalgo::samples::*;
+    assert_eq!(SQUARE_HULL.to_vec(), kirkpatrick_seidel(&SQUARE_WITH_CENTER));
+    assert_eq!(TRIANGLE_HULL.to_vec(), kirkpatrick_seidel(&TRIANGLE_WITH_EDGE_POINTS));
+    assert_eq!(2, kirkpatrick_seidel(&COLLINEAR).len());
+    // a lattice has many collinear and duplicate points
+    let input_set: Vec<Point2D> = (0..3000)
+        .map(|i| Point2D::new((i * 37 % 41) as f64, (i * 59 % 43) as f64))
+        .collect();
+    assert_eq!(convex_hull_of(&input_set), kirkpatrick_seidel(&input_set));
+}
+
+#[test]
+fn test_incremental_hull() {
+    use rustalgo::samples::*;
+    assert_eq!(SQUARE_HULL.to_vec(), incremental_hull(&SQUARE_WITH_CENTER));
+    assert_eq!(TRIANGLE_HULL.to_vec(), incremental_hull(&TRIANGLE_WITH_EDGE_POINTS));
+    assert_eq!(2, incremental_hull(&COLLINEAR).len());
+    let mut input_set: Vec<Point2D> = (0..3000)
+        .map(|i| Point2D::new((i * 37 % 41) as f64, (i * 59 % 43) as f64))
+        .collect();
+    assert_eq!(convex_hull_of(&input_set), incremental_hull(&input_set));
+    // sorted input sets make every point a hull vertex for a while
+    input_set.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap()));
+    assert_eq!(convex_hull_of(&input_set), incremental_hull(&input_set));
+}
+
+#[test]
+#[cfg(feature = "generators")]
+fn test_randomized_incremental_hull() {
+    let input_set = generate(Shape::Circle, Number::Thousand);
+    assert_eq!(convex_hull_of(&input_set), randomized_incremental_hull(&input_set));
+}
+
+#[test]
+fn test_approximate_hull() {
+    use rustalgo::samples::*;
+    assert_eq!(convex_hull_of(&SQUARE_WITH_CENTER), approximate_hull(&SQUARE_WITH_CENTER, 4));
+    // one strip per distinct x-coordinate gives the exact hull
+    let input_set: Vec<Point2D> = (0..3000)
+        .map(|i| Point2D::new((i * 37 % 41) as f64, (i * 59 % 43) as f64))
+        .collect();
+    assert_eq!(convex_hull_of(&input_set), approximate_hull(&input_set, 41));
+    // with fewer strips no point is farther outside than a strip width
+    let strips = 5;
+    let width = 40.0 / strips as f64;
+    let hull = approximate_hull(&input_set, strips);
+    assert!(hull.iter().all(|vertex| input_set.contains(vertex)));
+    for point in &input_set {
+        for i in 0..hull.len() {
+            let (start, end) = (&hull[i], &hull[(i + 1) % hull.len()]);
+            let length = ((end.x - start.x).powi(2) + (end.y - start.y).powi(2)).sqrt();
+            assert!(-orietation(start, end, point) / length <= width);
+        }
+    }
+    assert!(approximate_hull::<f64>(&[], 8).is_empty());
+}
+
+#[test]
+fn test_convex_layers() {
+    use rustalgo::samples::*;
+    let layers = convex_layers(&SQUARE_WITH_CENTER);
+    assert_eq!(2, layers.len());
+    assert_eq!(SQUARE_HULL.len(), layers[0].len());
+    assert_eq!(1, layers[1].len());
+    // nested squares
+    let input_set: Vec<Point2D> = (1..4)
+        .flat_map(|size| {
+            let size = size as f64;
+            vec![
+                Point2D::new(-size, -size),
+                Point2D::new(size, -size),
+                Point2D::new(size, size),
+                Point2D::new(-size, size),
+            ]
+        })
+        .collect();
+    let layers = convex_layers(&input_set);
+    assert_eq!(3, layers.len());
+    assert_eq!(input_set.len(), layers.iter().map(|layer| layer.len()).sum::<usize>());
+    assert!(layers[2].contains(&Point2D::new(1.0, 1.0)));
+    assert!(convex_layers::<Point2D>(&[]).is_empty());
+}
+
+#[test]
+fn test_peeling_depth() {
+    use rustalgo::samples::*;
+    assert_eq!(1, peeling_depth(&SQUARE_WITH_CENTER, &Point2D::new(2.0, 0.0)));
+    assert_eq!(1, peeling_depth(&SQUARE_WITH_CENTER, &Point2D::new(1.0, 2.0)));
+    assert_eq!(1, peeling_depth(&SQUARE_WITH_CENTER, &Point2D::new(0.5, 1.5)));
+    assert_eq!(2, peeling_depth(&SQUARE_WITH_CENTER, &Point2D::new(1.0, 1.0)));
+    assert_eq!(0, peeling_depth(&SQUARE_WITH_CENTER, &Point2D::new(3.0, 1.0)));
+    // nested squares with a point on an edge of the middle one, which
+    // makes the inner square a pentagon
+    let mut input_set: Vec<Point2D> = (1..4)
+        .flat_map(|size| {
+            let size = size as f64;
+            vec![
+                Point2D::new(-size, -size),
+                Point2D::new(size, -size),
+                Point2D::new(size, size),
+                Point2D::new(-size, size),
+            ]
+        })
+        .collect();
+    input_set.push(Point2D::new(0.0, 2.0));
+    let queries = vec![
+        Point2D::new(0.0, 0.0),
+        Point2D::new(1.5, 0.0),
+        Point2D::new(-2.5, 2.5),
+        Point2D::new(0.0, 2.0),
+        Point2D::new(0.0, 3.5),
+    ];
+    assert_eq!(vec![3, 2, 1, 3, 0], peeling_depths(&input_set, &queries));
+    let layers = convex_layers(&input_set);
+    for (depth, layer) in layers.iter().enumerate() {
+        assert_eq!(vec![depth + 1; layer.len()], peeling_depths(&input_set, layer));
+    }
+    assert_eq!(0, peeling_depth::<Point2D>(&[], &Point2D::new(0.0, 0.0)));
+}
+
+#[test]
+fn test_in_convex_position() {
+    use rustalgo::samples::*;
+    assert!(in_convex_position(&SQUARE_HULL));
+    assert!(!in_convex_position(&SQUARE_WITH_CENTER));
+    // copies of a vertex, but not points on an edge
+    let mut repeated = SQUARE_HULL.to_vec();
+    repeated.extend_from_slice(&SQUARE_HULL[..2]);
+    assert!(in_convex_position(&repeated));
+    repeated.push(Point2D::new(1.0, 0.0));
+    assert!(!in_convex_position(&repeated));
+    assert!(!in_convex_position(&COLLINEAR));
+    assert!(in_convex_position(&COLLINEAR[..2]));
+    assert!(in_convex_position::<Point2D>(&[]));
+}
+
+#[test]
+fn test_upper_and_lower_hull() {
+    let input_set = [
+        Point2D::new(0.0, 0.0),
+        Point2D::new(0.0, 1.0),
+        Point2D::new(1.0, -1.0),
+        Point2D::new(1.0, 0.5),
+        Point2D::new(2.0, 2.0),
+        Point2D::new(3.0, 0.0),
+        Point2D::new(3.0, 1.0),
+    ];
+    assert_eq!(
+        vec![Point2D::new(0.0, 1.0), Point2D::new(2.0, 2.0), Point2D::new(3.0, 1.0)],
+        upper_hull(&input_set)
+    );
+    assert_eq!(
+        vec![Point2D::new(0.0, 0.0), Point2D::new(1.0, -1.0), Point2D::new(3.0, 0.0)],
+        lower_hull(&input_set)
+    );
+    assert!(upper_hull::<f64>(&[]).is_empty());
+    assert!(lower_hull::<f64>(&[]).is_empty());
+}
+
+#[test]
+fn test_generic_coordinates() {
+    // a hair off the diagonal, which f64 rounds onto it
+    let hair = Point2D::new(1i64 << 57, (1i64 << 57) + 1);
+    let hull = vec![Point2D::new(0, 0), Point2D::new(1i64 << 58, 0), Point2D::new(1i64 << 58, 1i64 << 58), hair];
+    let mut points = hull.clone();
+    points.extend(vec![Point2D::new(1i64 << 56, 1i64 << 55), Point2D::new(1i64 << 57, 0), Point2D::new(1i64 << 57, 1i64 << 56)]);
+    assert_eq!(3, convex_hull_of(&points).len());
+    let algorithms = vec![
+        Algorithm::Graham,
+        Algorithm::GrahamAngleFree,
+        Algorithm::Jarvis,
+        Algorithm::Chan,
+        Algorithm::ChanWith(GuessSchedule::Doubling),
+        Algorithm::QuickHull,
+        Algorithm::KirkpatrickSeidel,
+        Algorithm::Incremental,
+        Algorithm::Approximate(8),
+        Algorithm::DivideAndConquer(1),
+        Algorithm::DivideAndConquer(3),
+        Algorithm::Auto,
+        #[cfg(feature = "parallel")]
+        Algorithm::ParallelQuickHull,
+    ];
+    for &algorithm in &algorithms {
+        let mut prepared = prepare(algorithm, &mut points.clone());
+        assert_eq!(hull, execute(algorithm, &mut prepared), "{:?}", algorithm);
+    }
+    //the same points small enough for i32
+    let small: Vec<Point2D<i32>> =
+        vec![Point2D::new(0, 0), Point2D::new(4, 0), Point2D::new(4, 4), Point2D::new(2, 3), Point2D::new(2, 1), Point2D::new(2, 0)];
+    for &algorithm in &algorithms {
+        let mut prepared = prepare(algorithm, &mut small.clone());
+        assert_eq!(small[..4].to_vec(), execute(algorithm, &mut prepared), "{:?}", algorithm);
+    }
+}
+
+#[test]
+fn test_f32_points() {
+    let points: Vec<Point2D<f32>> =
+        vec![Point2D::new(0.0, 0.0), Point2D::new(0.3, 0.1), Point2D::new(0.1, 0.1), Point2D::new(0.1, 0.3)];
+    //the same f32 values, none rounded through f64
+    let hull = vec![points[0], points[1], points[3]];
+    for &algorithm in &[Algorithm::Graham, Algorithm::Jarvis, Algorithm::Chan, Algorithm::QuickHull, Algorithm::Incremental, Algorithm::Delaunay] {
+        let mut prepared = prepare(algorithm, &mut points.clone());
+        assert_eq!(hull, execute(algorithm, &mut prepared), "{:?}", algorithm);
+    }
+    assert_eq!(hull, convex_hull_exact(&points));
+    assert_eq!(hull, convex_hull_of(&points));
+}
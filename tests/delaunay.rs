@@ -0,0 +1,64 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+extern crate rustalgo;
+mod common;
+use common::scattered;
+use rustalgo::convexhull::convex_hull_of;
+use rustalgo::delaunay::*;
+use rustalgo::points::*;
+use rustalgo::samples::*;
+
+// the triangles are counter clockwise, with no point inside the circle
+// of any of them
+fn assert_delaunay(points: &[Point2D], triangles: &[Triangle]) {
+    for triangle in triangles {
+        let (a, b, c) = (points[triangle[0]], points[triangle[1]], points[triangle[2]]);
+        assert!(orietation(&a, &b, &c) > 0.0);
+        let d = 2.0 * ((b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x));
+        let (b2, c2) = ((b.x - a.x).powi(2) + (b.y - a.y).powi(2), (c.x - a.x).powi(2) + (c.y - a.y).powi(2));
+        let center = Point2D::new(a.x + ((c.y - a.y) * b2 - (b.y - a.y) * c2) / d, a.y + ((b.x - a.x) * c2 - (c.x - a.x) * b2) / d);
+        let radius = ((center.x - a.x).powi(2) + (center.y - a.y).powi(2)).sqrt();
+        for point in points {
+            assert!(((center.x - point.x).powi(2) + (center.y - point.y).powi(2)).sqrt() >= radius * (1.0 - 1e-9));
+        }
+    }
+}
+
+#[test]
+fn test_delaunay_triangulation() {
+    let points = scattered(300, 0.0);
+    let triangles = delaunay_triangulation(&points);
+    assert_delaunay(&points, &triangles);
+    // 2n - 2 - h triangles for n points, h of them on the hull
+    assert_eq!(2 * 300 - 2 - convex_hull_of(&points).len(), triangles.len());
+    let square = delaunay_triangulation(&SQUARE_WITH_CENTER);
+    assert_eq!(4, square.len());
+    assert!(square.iter().all(|triangle| triangle.contains(&2)));
+    assert!(delaunay_triangulation(&COLLINEAR).is_empty());
+    assert!(delaunay_triangulation(&[Point2D::new(1.0, 1.0); 5]).is_empty());
+}
+
+#[test]
+fn test_delaunay_triangulation_of_a_lattice() {
+    // every square of the lattice has its corners on one circle
+    let mut points: Vec<Point2D> = (0..100).map(|i| Point2D::new((i % 10) as f64, (i / 10) as f64)).collect();
+    points.extend(points.clone());
+    let triangles = delaunay_triangulation(&points);
+    assert_eq!(2 * 81, triangles.len());
+    assert!(triangles.iter().all(|triangle| triangle.iter().all(|&corner| corner < 100)));
+    assert_delaunay(&points, &triangles);
+}
+
+#[test]
+fn test_delaunay_hull() {
+    let points = scattered(2000, 0.0);
+    assert_eq!(convex_hull_of(&points), delaunay_hull(&points));
+    assert_eq!(SQUARE_HULL.to_vec(), delaunay_hull(&SQUARE_WITH_CENTER));
+    assert_eq!(TRIANGLE_HULL.to_vec(), delaunay_hull(&TRIANGLE_WITH_EDGE_POINTS));
+    assert_eq!(convex_hull_of(&COLLINEAR), delaunay_hull(&COLLINEAR));
+    let lattice: Vec<Point2D> = (0..400).map(|i| Point2D::new((i % 20) as f64, (i / 20) as f64)).collect();
+    assert_eq!(convex_hull_of(&lattice), delaunay_hull(&lattice));
+}
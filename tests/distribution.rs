@@ -0,0 +1,42 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+#![cfg(feature = "generators")]
+
+extern crate rustalgo;
+use rustalgo::distribution::*;
+
+#[test]
+fn test_translated_disk() {
+    let input_set = Dist::disk(1.0).translate(5.0, -5.0).take(1_000);
+    assert_eq!(1_000, input_set.len());
+    for point in input_set {
+        assert!(((point.x - 5.0).powi(2) + (point.y + 5.0).powi(2)).sqrt() <= 1.0);
+    }
+}
+
+#[test]
+fn test_mix_weights() {
+    let only_first = Dist::disk(1.0).mix(Dist::disk(1.0).translate(10.0, 0.0), 0.0);
+    assert!(only_first.take(100).iter().all(|point| point.x <= 1.0));
+    let only_second = Dist::disk(1.0).mix(Dist::disk(1.0).translate(10.0, 0.0), 1.0);
+    assert!(only_second.take(100).iter().all(|point| point.x >= 9.0));
+}
+
+#[test]
+#[should_panic]
+fn test_mix_with_invalid_weight() {
+    Dist::disk(1.0).mix(Dist::gaussian(1.0), 1.5);
+}
+
+#[test]
+fn test_mixture_skips_zero_weights() {
+    let mixture = Dist::mixture(vec![
+        (Dist::disk(1.0), 0.0),
+        (Dist::disk(1.0).translate(10.0, 0.0), 2.0),
+        (Dist::disk(1.0).translate(-10.0, 0.0), 0.0),
+    ]);
+    assert!(mixture.take(100).iter().all(|point| point.x >= 9.0));
+}
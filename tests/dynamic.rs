@@ -0,0 +1,107 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+extern crate rustalgo;
+use rustalgo::convexhull::*;
+use rustalgo::dynamic::*;
+use rustalgo::points::*;
+
+// a lattice with many collinear and duplicate points
+fn lattice() -> Vec<Point2D> {
+    (0..3000)
+        .map(|i| Point2D::new((i * 37 % 41) as f64, (i * 59 % 43) as f64))
+        .collect()
+}
+
+#[test]
+fn test_insert() {
+    let input_set = lattice();
+    let mut hull = DynamicHull::new();
+    assert!(hull.is_empty());
+    for (count, point) in input_set.iter().enumerate() {
+        hull.insert(*point);
+        if count % 100 == 0 {
+            assert_eq!(quick_hull(&input_set[..count + 1]), hull.hull());
+        }
+    }
+    assert_eq!(quick_hull(&input_set), hull.hull());
+    assert!(hull.contains(&Point2D::new(20.0, 20.0)));
+    assert!(!hull.contains(&Point2D::new(-1.0, 20.0)));
+    assert!(!hull.insert(Point2D::new(20.0, 20.0)));
+}
+
+#[test]
+fn test_from_iterator() {
+    let input_set = lattice();
+    let hull: DynamicHull = input_set.iter().cloned().collect();
+    assert_eq!(quick_hull(&input_set), hull.hull());
+    let single: DynamicHull = vec![Point2D::new(1.0, 2.0)].into_iter().collect();
+    assert_eq!(vec![Point2D::new(1.0, 2.0)], single.hull());
+    let mut hull: FullyDynamicHull = input_set.iter().cloned().collect();
+    assert_eq!(input_set.len(), hull.len());
+    assert_eq!(quick_hull(&input_set), hull.hull());
+    hull.extend(vec![Point2D::new(-1.0, -1.0), Point2D::new(-1.0, -1.0)]);
+    assert_eq!(input_set.len() + 2, hull.len());
+    assert_eq!(Point2D::new(-1.0, -1.0), hull.hull()[0]);
+}
+
+#[test]
+fn test_fully_dynamic_hull() {
+    let input_set = lattice();
+    let mut hull = FullyDynamicHull::new();
+    for (count, point) in input_set.iter().enumerate() {
+        hull.insert(*point);
+        if count % 100 == 0 {
+            assert_eq!(quick_hull(&input_set[..count + 1]), hull.hull());
+        }
+    }
+    assert_eq!(input_set.len(), hull.len());
+    // a sliding window of 500 points
+    for (count, point) in input_set.iter().enumerate().take(2500) {
+        assert!(hull.remove(point));
+        if count % 100 == 0 {
+            assert_eq!(quick_hull(&input_set[count + 1..]), hull.hull());
+        }
+    }
+    assert!(!hull.remove(&Point2D::new(-1.0, -1.0)));
+    for point in &input_set[2500..] {
+        assert!(hull.remove(point));
+    }
+    assert!(hull.is_empty());
+    assert!(hull.hull().is_empty());
+}
+
+#[test]
+fn test_hull_sketch() {
+    // a disc of radius 1, with every point on the circle
+    let input_set: Vec<Point2D> = (0..20000)
+        .map(|i| {
+            let angle = i as f64 * 2.399_963;
+            Point2D::new(angle.cos(), angle.sin())
+        })
+        .collect();
+    let epsilon = 0.01;
+    let mut sketch = HullSketch::new(epsilon);
+    sketch.extend(input_set.iter().cloned());
+    let hull = sketch.hull();
+    assert!(hull.len() > 3 && hull.len() <= sketch.directions());
+    for vertex in &hull {
+        assert!(input_set.contains(vertex));
+    }
+    // every point is close to an edge of the sketch
+    let distance = |point: &Point2D, start: &Point2D, end: &Point2D| {
+        let (dx, dy) = (end.x - start.x, end.y - start.y);
+        let along = ((point.x - start.x) * dx + (point.y - start.y) * dy) / (dx * dx + dy * dy);
+        let along = along.clamp(0.0, 1.0);
+        ((start.x + along * dx - point.x).powi(2) + (start.y + along * dy - point.y).powi(2)).sqrt()
+    };
+    for point in &input_set {
+        let nearest = (0..hull.len())
+            .map(|i| distance(point, &hull[i], &hull[(i + 1) % hull.len()]))
+            .fold(f64::INFINITY, f64::min);
+        assert!(nearest <= epsilon * 2.0);
+    }
+    assert!(HullSketch::new(epsilon).hull().is_empty());
+}
@@ -0,0 +1,55 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+extern crate rustalgo;
+use rustalgo::envelope::*;
+
+#[test]
+fn test_convex_hull_trick() {
+    let fraction = |value: f64| value - value.floor();
+    // decreasing slopes, with repeated ones
+    let lines: Vec<(f64, f64)> = (0..300)
+        .map(|i| (10.0 - (i / 2) as f64 * 0.1, 50.0 * fraction(i as f64 * 0.618_034) - 25.0))
+        .collect();
+    let mut envelope = ConvexHullTrick::new();
+    assert_eq!(None, envelope.min_at(0.0));
+    for &(slope, intercept) in &lines {
+        envelope.add(slope, intercept);
+    }
+    assert!(envelope.len() < lines.len());
+    assert!(envelope.lines().windows(2).all(|pair| pair[0].0 > pair[1].0));
+    for step in 0..400 {
+        let x = -100.0 + step as f64 * 0.5;
+        let expected = lines
+            .iter()
+            .map(|&(slope, intercept)| slope * x + intercept)
+            .fold(f64::INFINITY, f64::min);
+        assert_eq!(Some(expected), envelope.min_at(x));
+        assert_eq!(Some(expected), envelope.min_at_increasing(x));
+    }
+}
+
+#[test]
+fn test_convex_hull_trick_interleaved() {
+    // lines added between increasing queries, y = -i x + i^2 is the
+    // minimum from x = 2i - 1 to 2i + 1
+    let mut envelope = ConvexHullTrick::new();
+    for i in 0..50 {
+        let i = i as f64;
+        envelope.add(-i, i * i);
+        let x = 2.0 * i - 0.5;
+        assert_eq!(Some(-i * x + i * i), envelope.min_at_increasing(x));
+        assert_eq!(envelope.min_at(x), envelope.min_at_increasing(x));
+    }
+    assert_eq!(50, envelope.len());
+}
+
+#[test]
+#[should_panic]
+fn test_convex_hull_trick_increasing_slope() {
+    let mut envelope = ConvexHullTrick::new();
+    envelope.add(1.0, 0.0);
+    envelope.add(2.0, 0.0);
+}
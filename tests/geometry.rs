@@ -0,0 +1,45 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+extern crate rustalgo;
+use rustalgo::geometry::*;
+use rustalgo::points::*;
+use rustalgo::samples::*;
+
+#[test]
+fn test_signed_area_and_orientation() {
+    assert_eq!(4.0, signed_area(&SQUARE_HULL));
+    assert_eq!(Orientation::CounterClockwise, orientation(&SQUARE_HULL));
+    let clockwise: Vec<Point2D> = TRIANGLE_HULL.iter().rev().cloned().collect();
+    assert_eq!(-4.0, signed_area(&clockwise));
+    assert_eq!(Orientation::Clockwise, orientation(&clockwise));
+    assert_eq!(0.0, signed_area(&COLLINEAR));
+    assert_eq!(Orientation::Collinear, orientation(&COLLINEAR));
+    assert_eq!(Orientation::Collinear, orientation(&[]));
+}
+
+#[test]
+fn test_turn() {
+    let (a, b) = (Point2D::new(0.0, 0.0), Point2D::new(1.0, 0.0));
+    assert_eq!(Orientation::CounterClockwise, turn(&a, &b, &Point2D::new(2.0, 1.0)));
+    assert_eq!(Orientation::Clockwise, turn(&a, &b, &Point2D::new(2.0, -1.0)));
+    assert_eq!(Orientation::Collinear, turn(&a, &b, &Point2D::new(-3.0, 0.0)));
+}
+
+#[test]
+fn test_canonicalize() {
+    let mut hull = vec![Point2D::new(2.0, 2.0), Point2D::new(2.0, 0.0), Point2D::new(0.0, 0.0), Point2D::new(0.0, 2.0)];
+    canonicalize(&mut hull);
+    assert_eq!(SQUARE_HULL.to_vec(), hull);
+    canonicalize(&mut hull);
+    assert_eq!(SQUARE_HULL.to_vec(), hull);
+    // the lower of two leftmost vertices comes first
+    let mut hull = vec![Point2D::new(0.0, 2.0), Point2D::new(0.0, 0.0), Point2D::new(2.0, 0.0), Point2D::new(2.0, 2.0)];
+    canonicalize(&mut hull);
+    assert_eq!(SQUARE_HULL.to_vec(), hull);
+    let mut empty: Vec<Point2D> = Vec::new();
+    canonicalize(&mut empty);
+    assert!(empty.is_empty());
+}
@@ -0,0 +1,59 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+extern crate rustalgo;
+use rustalgo::points::*;
+use rustalgo::hull::*;
+use rustalgo::halfplane::*;
+use rustalgo::samples::SQUARE_HULL;
+
+/// The half-planes inside the edges of a counter clockwise hull
+fn edges(hull: &[Point2D]) -> Vec<Line> {
+    (0..hull.len()).map(|i| Line::new(hull[i], hull[(i + 1) % hull.len()])).collect()
+}
+
+#[test]
+fn test_half_plane_intersection() {
+    assert_eq!(SQUARE_HULL.to_vec(), half_plane_intersection(&edges(&SQUARE_HULL)));
+    // with redundant half-planes, some parallel to an edge
+    let mut planes = edges(&SQUARE_HULL);
+    planes.push(Line::new(Point2D::new(5.0, -1.0), Point2D::new(5.0, 0.0)));
+    planes.push(Line::new(Point2D::new(0.0, -1.0), Point2D::new(1.0, -1.0)));
+    planes.push(Line::new(Point2D::new(-1.0, 0.0), Point2D::new(0.0, -1.0)));
+    planes.reverse();
+    assert_eq!(SQUARE_HULL.to_vec(), half_plane_intersection(&planes));
+    // a corner cut off
+    planes.push(Line::new(Point2D::new(2.0, 1.0), Point2D::new(1.0, 2.0)));
+    let cut = vec![Point2D::new(0.0, 0.0), Point2D::new(2.0, 0.0), Point2D::new(2.0, 1.0), Point2D::new(1.0, 2.0), Point2D::new(0.0, 2.0)];
+    assert_eq!(cut, half_plane_intersection(&planes));
+    // nothing in common and unbounded
+    planes.push(Line::new(Point2D::new(-2.0, 5.0), Point2D::new(-1.0, 5.0)));
+    assert!(half_plane_intersection(&planes).is_empty());
+    assert!(half_plane_intersection(&edges(&SQUARE_HULL)[..3]).is_empty());
+    let strip = vec![Line::new(Point2D::new(0.0, 0.0), Point2D::new(1.0, 0.0)), Line::new(Point2D::new(1.0, 1.0), Point2D::new(0.0, 1.0)), Line::new(Point2D::new(0.0, 0.0), Point2D::new(0.0, -1.0))];
+    assert!(half_plane_intersection(&strip).is_empty());
+}
+
+#[test]
+fn test_half_plane_intersection_against_clipping() {
+    // a large square clipped by every half-plane in turn
+    let large = ConvexHull::new(vec![Point2D::new(-100.0, -100.0), Point2D::new(100.0, -100.0), Point2D::new(100.0, 100.0), Point2D::new(-100.0, 100.0)]);
+    for k in 0..50 {
+        let mut planes = edges(large.vertices());
+        for i in 0..(3 + k % 17) {
+            let angle = (i * 7 + k * 3) as f64 * 0.61;
+            let offset = 1.0 + ((i + k) as f64 * 0.37).sin().abs() * 3.0;
+            let (dx, dy) = (angle.cos(), angle.sin());
+            // directions along the circle, each line offset from the origin
+            planes.push(Line::new(Point2D::new(dy * offset, -dx * offset), Point2D::new(dy * offset + dx, -dx * offset + dy)));
+        }
+        let expected = planes.iter().try_fold(large.clone(), |hull, plane| hull.split(plane).0);
+        let region = half_plane_intersection(&planes);
+        match expected {
+            Some(expected) => assert!(expected.diff(&ConvexHull::new(region), 1e-9).vertices_match(), "{}", k),
+            None => assert!(region.is_empty(), "{}", k),
+        }
+    }
+}
@@ -0,0 +1,537 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+extern crate rustalgo;
+use rustalgo::points::*;
+use rustalgo::hull::*;
+use rustalgo::samples::{SQUARE_HULL, TRIANGLE_HULL};
+
+fn square() -> ConvexHull {
+    ConvexHull::new(SQUARE_HULL.to_vec())
+}
+
+#[test]
+fn test_new_reverses_clockwise_hull() {
+    let clockwise = vec![
+        Point2D::new(0.0, 0.0),
+        Point2D::new(0.0, 2.0),
+        Point2D::new(2.0, 2.0),
+        Point2D::new(2.0, 0.0),
+    ];
+    let hull = ConvexHull::new(clockwise);
+    assert_eq!(square().vertices()[1..], hull.vertices()[..3]);
+}
+
+#[test]
+fn test_split() {
+    let line = Line::new(Point2D::new(1.0, -1.0), Point2D::new(1.0, 3.0));
+    let (left, right) = square().split(&line);
+    let left_should_be = vec![
+        Point2D::new(0.0, 0.0),
+        Point2D::new(1.0, 0.0),
+        Point2D::new(1.0, 2.0),
+        Point2D::new(0.0, 2.0),
+    ];
+    let right_should_be = vec![
+        Point2D::new(1.0, 0.0),
+        Point2D::new(2.0, 0.0),
+        Point2D::new(2.0, 2.0),
+        Point2D::new(1.0, 2.0),
+    ];
+    assert_eq!(left_should_be, left.unwrap().into_vertices());
+    assert_eq!(right_should_be, right.unwrap().into_vertices());
+}
+
+#[test]
+fn test_split_outside_hull() {
+    let line = Line::new(Point2D::new(3.0, 0.0), Point2D::new(3.0, 1.0));
+    let (left, right) = square().split(&line);
+    assert_eq!(Some(square()), left);
+    assert_eq!(None, right);
+}
+
+#[test]
+fn test_diff() {
+    // the square rotated to start elsewhere, clockwise and with one vertex moved
+    let other = ConvexHull::new(vec![
+        Point2D::new(2.0, 2.0),
+        Point2D::new(2.0, 0.0),
+        Point2D::new(0.0, 0.0),
+        Point2D::new(0.0, 2.0 + 1e-12),
+    ]);
+    let diff = square().diff(&other, 1e-9);
+    assert!(diff.vertices_match());
+    assert!(diff.symmetric_difference_area < 1e-9);
+
+    let triangle = ConvexHull::new(vec![Point2D::new(0.0, 0.0), Point2D::new(2.0, 0.0), Point2D::new(0.0, 2.0)]);
+    let diff = square().diff(&triangle, 1e-9);
+    assert_eq!(vec![Point2D::new(2.0, 2.0)], diff.only_in_first);
+    assert!(diff.only_in_second.is_empty());
+    assert!((diff.symmetric_difference_area - 2.0).abs() < 1e-9);
+    assert_eq!(4.0, square().area());
+}
+
+#[test]
+fn test_hull_area_and_perimeter() {
+    use rustalgo::convexhull::*;
+    use rustalgo::samples::*;
+    let hull = jarvis_march(&mut sort_input(&mut TRIANGLE_WITH_EDGE_POINTS.to_vec()));
+    assert_eq!(4.0, hull_area(&hull));
+    assert_eq!(4.0, hull_area(&quick_hull(&TRIANGLE_WITH_EDGE_POINTS)));
+    assert_eq!(4.0 + 4.0 * 2.0_f64.sqrt(), hull_perimeter(&hull));
+    assert_eq!(8.0, square().perimeter());
+    assert_eq!(0.0, hull_area(&COLLINEAR[..2]));
+    // there and back
+    assert_eq!(2.0 * 2.0_f64.sqrt(), hull_perimeter(&COLLINEAR[..2]));
+    assert_eq!(0.0, hull_perimeter(&[]));
+}
+
+#[test]
+fn test_centroid() {
+    assert_eq!(Some(Point2D::new(1.0, 1.0)), square().centroid());
+    // many vertices near one corner don't pull the centroid there
+    let mut vertices = vec![Point2D::new(0.0, 0.0)];
+    vertices.extend((0..10).map(|i| Point2D::new(3.0, 0.01 * i as f64)));
+    vertices.push(Point2D::new(3.0, 3.0));
+    vertices.push(Point2D::new(0.0, 3.0));
+    let centroid = hull_centroid(&vertices).unwrap();
+    assert!((centroid.x - 1.5).abs() < 1e-12 && (centroid.y - 1.5).abs() < 1e-12);
+    // far from the origin, clockwise
+    let far = vec![Point2D::new(1e9, 1e9), Point2D::new(1e9, 1e9 + 3.0), Point2D::new(1e9 + 3.0, 1e9)];
+    assert_eq!(Some(Point2D::new(1e9 + 1.0, 1e9 + 1.0)), hull_centroid(&far));
+    assert_eq!(Some(Point2D::new(1.0, 2.0)), hull_centroid(&[Point2D::new(0.0, 1.0), Point2D::new(2.0, 3.0)]));
+    assert_eq!(None, hull_centroid(&[]));
+}
+
+#[test]
+fn test_simplify_hull() {
+    // a square with its sides bulging out by less than 1e-3
+    let mut noisy = Vec::new();
+    for side in 0..4 {
+        let (from, to) = (SQUARE_HULL[side], SQUARE_HULL[(side + 1) % 4]);
+        for i in 0..100 {
+            let t = i as f64 / 100.0;
+            let bulge = 4e-3 * t * (1.0 - t);
+            let (dx, dy) = (to.x - from.x, to.y - from.y);
+            noisy.push(Point2D::new(from.x + t * dx + bulge * dy / 2.0, from.y + t * dy - bulge * dx / 2.0));
+        }
+    }
+    assert_eq!(SQUARE_HULL.to_vec(), simplify_hull(&noisy, 1e-3));
+    // every vertex left out is near the boundary of the simplified hull
+    let simplified = simplify_hull(&noisy, 1e-4);
+    assert!(simplified.len() > 4 && simplified.len() < noisy.len());
+    assert!(simplified.iter().all(|vertex| noisy.contains(vertex)));
+    for vertex in &noisy {
+        assert!(closest_point_on_hull(&simplified, vertex).1 <= 1e-4);
+    }
+    assert_eq!(noisy, simplify_hull(&noisy, 0.0));
+    // clockwise, as from other libraries
+    let mut clockwise = SQUARE_HULL.to_vec();
+    clockwise.reverse();
+    assert_eq!(clockwise, simplify_hull(&clockwise, 1e-3));
+    assert_eq!(vec![Point2D::new(0.0, 0.0), Point2D::new(4.0, 0.0)], simplify_hull(&TRIANGLE_HULL, 2.0));
+    assert_eq!(TRIANGLE_HULL[..2].to_vec(), simplify_hull(&TRIANGLE_HULL[..2], 1.0));
+}
+
+#[test]
+fn test_point_locator() {
+    // an octagon, clockwise
+    let octagon: Vec<Point2D> = (0..8)
+        .rev()
+        .map(|i| {
+            let angle = i as f64 * std::f64::consts::PI / 4.0;
+            Point2D::new((4.0 * angle.cos()).round(), (4.0 * angle.sin()).round())
+        })
+        .collect();
+    let locator = PointLocator::new(&octagon);
+    let on_edges = |point: &Point2D| {
+        let vertices = locator.vertices();
+        (0..vertices.len()).all(|i| Line::new(vertices[i], vertices[(i + 1) % vertices.len()]).side(point) >= 0.0)
+    };
+    let queries: Vec<Point2D> = (-12..=12)
+        .flat_map(|x| (-12..=12).map(move |y| Point2D::new(x as f64 / 2.0, y as f64 / 2.0)))
+        .collect();
+    let expected: Vec<bool> = queries.iter().map(on_edges).collect();
+    assert_eq!(expected, locator.contains_all(&queries));
+    // the vertices and edges count, points on the lines of edges beyond them don't
+    assert!(locator.contains(&Point2D::new(4.0, 0.0)));
+    assert!(locator.contains(&Point2D::new(3.5, 1.5)));
+    assert!(!locator.contains(&Point2D::new(4.0, 4.0)));
+    assert!(!locator.contains(&Point2D::new(4.0, -4.0)));
+    assert!(!locator.contains(&Point2D::new(5.0, 0.0)));
+    // repeated vertices and hulls without area
+    let repeated = PointLocator::new(&[Point2D::new(0.0, 0.0), Point2D::new(2.0, 0.0), Point2D::new(2.0, 0.0), Point2D::new(0.0, 2.0), Point2D::new(0.0, 0.0)]);
+    assert!(!repeated.contains(&Point2D::new(3.0, 0.0)));
+    assert!(repeated.contains(&Point2D::new(1.0, 1.0)));
+    let segment = PointLocator::new(&[Point2D::new(0.0, 0.0), Point2D::new(2.0, 2.0), Point2D::new(1.0, 1.0)]);
+    assert_eq!(vec![true, false, false], segment.contains_all(&[Point2D::new(0.5, 0.5), Point2D::new(3.0, 3.0), Point2D::new(1.0, 0.0)]));
+    assert!(PointLocator::new(&[Point2D::new(1.0, 1.0)]).contains(&Point2D::new(1.0, 1.0)));
+    assert!(!PointLocator::new(&[]).contains(&Point2D::new(1.0, 1.0)));
+}
+
+#[test]
+fn test_extreme_point() {
+    // a 40-gon with edges parallel to the axes, and an ellipse, both ways round
+    let polygon: Vec<Point2D> = (0..40)
+        .map(|i| {
+            let angle = i as f64 * std::f64::consts::PI / 20.0;
+            Point2D::new(angle.cos(), angle.sin())
+        })
+        .collect();
+    let ellipse: Vec<Point2D> = (0..500)
+        .map(|i| {
+            let angle = i as f64 * std::f64::consts::PI / 250.0 + 0.3;
+            Point2D::new(5.0 * angle.cos() + 1.0, angle.sin() - 7.0)
+        })
+        .collect();
+    let height = |direction: &Point2D, point: &Point2D| direction.x * point.x + direction.y * point.y;
+    for hull in [polygon.clone(), polygon.iter().rev().cloned().collect(), ellipse.clone(), ellipse.iter().rev().cloned().collect()] {
+        for k in 0..(hull.len() * 3) {
+            for start in 0..3 {
+                let mut rotated = hull.clone();
+                rotated.rotate_left(start * 7 % hull.len());
+                let angle = k as f64 * std::f64::consts::PI * 2.0 / (hull.len() * 3) as f64;
+                let direction = Point2D::new(angle.cos(), angle.sin());
+                let extreme = extreme_point(&rotated, &direction);
+                assert!(rotated.contains(&extreme));
+                let best = rotated.iter().map(|vertex| height(&direction, vertex)).fold(f64::MIN, f64::max);
+                assert_eq!(best, height(&direction, &extreme), "{:?} {:?}", direction, extreme);
+            }
+        }
+    }
+    let square = SQUARE_HULL.to_vec();
+    assert_eq!(Point2D::new(2.0, 2.0), extreme_point(&square, &Point2D::new(1.0, 1.0)));
+    assert_eq!(Point2D::new(0.0, 0.0), extreme_point(&square, &Point2D::new(-1.0, -3.0)));
+}
+
+#[test]
+fn test_width_in_direction() {
+    let square = SQUARE_HULL.to_vec();
+    assert_eq!(2.0, width_in_direction(&square, &Point2D::new(0.0, 3.0)));
+    assert!((width_in_direction(&square, &Point2D::new(1.0, 1.0)) - 8f64.sqrt()).abs() < 1e-12);
+    assert!((width_in_direction(&square, &Point2D::new(-1.0, 1.0)) - 8f64.sqrt()).abs() < 1e-12);
+    assert_eq!(0.0, width_in_direction(&square, &Point2D::new(0.0, 0.0)));
+    assert_eq!(0.0, width_in_direction(&[Point2D::new(1.0, 2.0)], &Point2D::new(1.0, 0.0)));
+    // a circle is as wide in every direction
+    let circle: Vec<Point2D> = (0..400)
+        .map(|i| {
+            let angle = i as f64 * std::f64::consts::PI / 200.0;
+            Point2D::new(angle.cos() + 3.0, angle.sin() - 1.0)
+        })
+        .collect();
+    for k in 0..100 {
+        let angle = k as f64 * 0.0628;
+        let width = width_in_direction(&circle, &Point2D::new(5.0 * angle.cos(), 5.0 * angle.sin()));
+        assert!(width <= 2.0 + 1e-12 && width > 2.0 - 1e-4);
+    }
+}
+
+#[test]
+fn test_hull_intersection() {
+    // clipped by every edge of the other hull in turn, in O(n m)
+    let clipped = |a: &ConvexHull, b: &ConvexHull| -> Option<ConvexHull> {
+        let vertices = b.vertices();
+        let mut clipped = a.clone();
+        for i in 0..vertices.len() {
+            clipped = clipped.split(&Line::new(vertices[i], vertices[(i + 1) % vertices.len()])).0?;
+        }
+        Some(clipped)
+    };
+    let ellipse = |cx: f64, cy: f64, turn: f64, count: usize| {
+        ConvexHull::new(
+            (0..count)
+                .map(|i| {
+                    let angle = i as f64 * 2.0 * std::f64::consts::PI / count as f64;
+                    let (x, y) = (3.0 * angle.cos(), angle.sin());
+                    Point2D::new(cx + x * turn.cos() - y * turn.sin(), cy + x * turn.sin() + y * turn.cos())
+                })
+                .collect(),
+        )
+    };
+    for k in 0..40 {
+        let a = ellipse(0.0, 0.0, 0.0, 7 + k);
+        let b = ellipse((k as f64 * 0.37).sin() * 3.0, (k as f64 * 0.23).cos(), k as f64 * 0.4, 50 - k);
+        let expected = clipped(&a, &b);
+        let intersection = a.intersection(&b);
+        assert_eq!(expected.is_some(), intersection.is_some(), "{}", k);
+        if let (Some(expected), Some(intersection)) = (expected, intersection) {
+            assert!(expected.diff(&intersection, 1e-9).vertices_match(), "{} {:?} {:?}", k, expected, intersection);
+            let clockwise: Vec<Point2D> = b.vertices().iter().rev().cloned().collect();
+            assert_eq!(intersection.vertices(), &hull_intersection(a.vertices(), &clockwise)[..]);
+        }
+    }
+    // a triangle over the corner of the square, one inside it and squares that only touch
+    let triangle = vec![Point2D::new(1.0, 1.0), Point2D::new(3.0, 1.0), Point2D::new(1.0, 3.0)];
+    let corner = vec![Point2D::new(1.0, 1.0), Point2D::new(2.0, 1.0), Point2D::new(2.0, 2.0), Point2D::new(1.0, 2.0)];
+    assert_eq!(corner, hull_intersection(&SQUARE_HULL, &triangle));
+    assert_eq!(corner, hull_intersection(&SQUARE_HULL, &corner));
+    let touching = vec![Point2D::new(2.0, 0.0), Point2D::new(4.0, 0.0), Point2D::new(4.0, 2.0), Point2D::new(2.0, 2.0)];
+    assert!(hull_intersection(&SQUARE_HULL, &touching).is_empty());
+    assert!(hull_intersection(&SQUARE_HULL, &[Point2D::new(1.0, 1.0)]).is_empty());
+    assert_eq!(None, square().intersection(&ConvexHull::new(SQUARE_HULL.iter().map(|p| Point2D::new(p.x + 3.0, p.y)).collect())));
+}
+
+#[test]
+fn test_hull_intersection_meeting_at_vertex() {
+    //the lower chains meet at (5, 1), a vertex of only one of them
+    let a = vec![Point2D::new(1.0, 3.0), Point2D::new(3.0, 1.0), Point2D::new(6.0, 1.0), Point2D::new(6.0, 6.0), Point2D::new(1.0, 5.0)];
+    let b = vec![
+        Point2D::new(3.0, 6.0),
+        Point2D::new(5.0, 1.0),
+        Point2D::new(7.0, 1.0),
+        Point2D::new(8.0, 5.0),
+        Point2D::new(6.0, 7.0),
+        Point2D::new(5.0, 7.0),
+    ];
+    let intersection = hull_intersection(&a, &b);
+    assert!(intersection.contains(&Point2D::new(5.0, 1.0)), "{:?}", intersection);
+    assert!((hull_overlap_area(&a, &b) - 55.0 / 6.0).abs() < 1e-9);
+    // Sutherland–Hodgman clipping of a by every edge of b
+    let clipped_area = |a: &[Point2D], b: &[Point2D]| {
+        let mut clipped = a.to_vec();
+        for i in 0..b.len() {
+            let line = Line::new(b[i], b[(i + 1) % b.len()]);
+            let input = clipped;
+            clipped = Vec::new();
+            for j in 0..input.len() {
+                let (p, q) = (input[j], input[(j + 1) % input.len()]);
+                let (side_p, side_q) = (line.side(&p), line.side(&q));
+                if side_p >= 0.0 {
+                    clipped.push(p);
+                }
+                if (side_p > 0.0 && side_q < 0.0) || (side_p < 0.0 && side_q > 0.0) {
+                    let t = side_p / (side_p - side_q);
+                    clipped.push(Point2D::new(p.x + t * (q.x - p.x), p.y + t * (q.y - p.y)));
+                }
+            }
+        }
+        if clipped.len() < 3 { 0.0 } else { twice_signed_area(&clipped) / 2.0 }
+    };
+    //pairs of quadrilaterals with corners on a small grid, where many
+    //edges and vertices line up
+    use rustalgo::convexhull::convex_hull_of;
+    let grid = |k: usize| Point2D::new((k % 7) as f64, (k / 7 % 7) as f64);
+    let quad = |k: usize| convex_hull_of(&[grid(k * 7 + 1), grid(k * 11 + 3), grid(k * 13 + 5), grid(k * 17 + 9)]);
+    for k in 0..600 {
+        let (first, second) = (quad(k), quad(k * 3 + 1));
+        if first.len() < 3 || second.len() < 3 {
+            continue;
+        }
+        let expected = clipped_area(&first, &second);
+        assert!((hull_overlap_area(&first, &second) - expected).abs() < 1e-9, "{:?} {:?} {}", first, second, expected);
+    }
+}
+
+#[test]
+fn test_hull_overlap_area_and_iou() {
+    // the square and the same square shifted right by one
+    let shifted: Vec<Point2D> = SQUARE_HULL.iter().map(|point| Point2D::new(point.x + 1.0, point.y)).collect();
+    assert_eq!(2.0, hull_overlap_area(&SQUARE_HULL, &shifted));
+    assert_eq!(2.0 / 6.0, hull_iou(&SQUARE_HULL, &shifted));
+    let clockwise: Vec<Point2D> = SQUARE_HULL.iter().rev().cloned().collect();
+    assert_eq!(1.0, hull_iou(&SQUARE_HULL, &clockwise));
+    let apart: Vec<Point2D> = SQUARE_HULL.iter().map(|point| Point2D::new(point.x + 5.0, point.y)).collect();
+    assert_eq!(0.0, hull_overlap_area(&SQUARE_HULL, &apart));
+    assert_eq!(0.0, hull_iou(&SQUARE_HULL, &apart));
+    assert_eq!(0.0, hull_iou(&[Point2D::new(0.0, 0.0)], &[Point2D::new(0.0, 0.0)]));
+}
+
+#[test]
+fn test_hull_symmetric_difference_area() {
+    let shifted: Vec<Point2D> = SQUARE_HULL.iter().map(|point| Point2D::new(point.x + 1.0, point.y)).collect();
+    assert_eq!(4.0, hull_symmetric_difference_area(&SQUARE_HULL, &shifted));
+    let clockwise: Vec<Point2D> = SQUARE_HULL.iter().rev().cloned().collect();
+    assert_eq!(0.0, hull_symmetric_difference_area(&SQUARE_HULL, &clockwise));
+    let apart: Vec<Point2D> = SQUARE_HULL.iter().map(|point| Point2D::new(point.x + 5.0, point.y)).collect();
+    assert_eq!(8.0, hull_symmetric_difference_area(&SQUARE_HULL, &apart));
+    // a corner moved by rounding
+    let mut jittered = SQUARE_HULL.to_vec();
+    jittered[2] = Point2D::new(2.0 + 1e-9, 2.0);
+    assert!(hull_symmetric_difference_area(&SQUARE_HULL, &jittered) < 1e-8);
+    assert_eq!(hull_symmetric_difference_area(&SQUARE_HULL, &TRIANGLE_HULL), square().diff(&ConvexHull::new(TRIANGLE_HULL.to_vec()), 0.0).symmetric_difference_area);
+}
+
+#[test]
+fn test_minkowski_sum() {
+    // every pairwise sum, hulled
+    let sums = |a: &[Point2D], b: &[Point2D]| -> ConvexHull {
+        let all: Vec<Point2D> = a.iter().flat_map(|p| b.iter().map(move |q| Point2D::new(p.x + q.x, p.y + q.y))).collect();
+        ConvexHull::new(rustalgo::convexhull::convex_hull_of(&all))
+    };
+    let polygon = |count: usize, scale: f64, turn: f64| -> Vec<Point2D> {
+        (0..count)
+            .map(|i| {
+                let angle = i as f64 * 2.0 * std::f64::consts::PI / count as f64 + turn;
+                Point2D::new(scale * angle.cos() + 1.0, angle.sin() - 2.0)
+            })
+            .collect()
+    };
+    for k in 3..20 {
+        let (a, b) = (polygon(k, 2.0, 0.1 * k as f64), polygon(23 - k, 0.5, 0.0));
+        let sum = ConvexHull::new(minkowski_sum(&a, &b));
+        assert!(sums(&a, &b).diff(&sum, 1e-9).vertices_match(), "{}", k);
+        let clockwise: Vec<Point2D> = b.iter().rev().cloned().collect();
+        assert_eq!(sum.vertices(), &minkowski_sum(&clockwise, &a)[..]);
+    }
+    let doubled = vec![Point2D::new(0.0, 0.0), Point2D::new(4.0, 0.0), Point2D::new(4.0, 4.0), Point2D::new(0.0, 4.0)];
+    assert_eq!(doubled, minkowski_sum(&SQUARE_HULL, &SQUARE_HULL));
+    let moved = vec![Point2D::new(1.0, -1.0), Point2D::new(3.0, -1.0), Point2D::new(3.0, 1.0), Point2D::new(1.0, 1.0)];
+    assert_eq!(moved, minkowski_sum(&SQUARE_HULL, &[Point2D::new(1.0, -1.0)]));
+    let segment = [Point2D::new(0.0, 0.0), Point2D::new(1.0, 1.0)];
+    assert_eq!(segment.to_vec(), minkowski_sum(&segment, &[Point2D::new(0.0, 0.0)]));
+    let wide = vec![Point2D::new(0.0, 0.0), Point2D::new(3.0, 0.0), Point2D::new(3.0, 2.0), Point2D::new(0.0, 2.0)];
+    assert_eq!(wide, minkowski_sum(&SQUARE_HULL, &[Point2D::new(1.0, 0.0), Point2D::new(0.0, 0.0)]));
+    assert!(minkowski_sum(&SQUARE_HULL, &[]).is_empty());
+}
+
+#[test]
+fn test_hull_distance() {
+    // the least distance between the edges
+    let distance = |a: &Point2D, b: &Point2D| ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt();
+    let to_segment = |point: &Point2D, from: &Point2D, to: &Point2D| {
+        let (dx, dy) = (to.x - from.x, to.y - from.y);
+        let t = (((point.x - from.x) * dx + (point.y - from.y) * dy) / (dx * dx + dy * dy)).clamp(0.0, 1.0);
+        distance(point, &Point2D::new(from.x + t * dx, from.y + t * dy))
+    };
+    let brute_force = |a: &[Point2D], b: &[Point2D]| {
+        let mut least = f64::MAX;
+        for (p, q) in [(a, b), (b, a)] {
+            for point in p {
+                for i in 0..q.len() {
+                    least = least.min(to_segment(point, &q[i], &q[(i + 1) % q.len()]));
+                }
+            }
+        }
+        least
+    };
+    let polygon = |count: usize, cx: f64, cy: f64| -> Vec<Point2D> {
+        (0..count)
+            .map(|i| {
+                let angle = i as f64 * 2.0 * std::f64::consts::PI / count as f64;
+                Point2D::new(cx + 2.0 * angle.cos(), cy + angle.sin())
+            })
+            .collect()
+    };
+    for k in 0..30 {
+        let a = polygon(3 + k, 0.0, 0.0);
+        let b = polygon(33 - k, 6.0 * (k as f64 * 0.5).cos(), 4.0 * (k as f64 * 0.5).sin());
+        let (on_a, on_b, gap) = hull_distance(&a, &b);
+        assert!((brute_force(&a, &b) - gap).abs() < 1e-9, "{}", k);
+        assert!((distance(&on_a, &on_b) - gap).abs() < 1e-9);
+        assert!(PointLocator::new(&a).contains(&on_a) || (0..a.len()).any(|i| to_segment(&on_a, &a[i], &a[(i + 1) % a.len()]) < 1e-9));
+        assert!((0..b.len()).any(|i| to_segment(&on_b, &b[i], &b[(i + 1) % b.len()]) < 1e-9));
+    }
+    // apart, overlapping, touching and a point inside
+    let apart: Vec<Point2D> = SQUARE_HULL.iter().map(|point| Point2D::new(point.x + 5.0, point.y + 1.0)).collect();
+    assert_eq!(3.0, hull_distance(&SQUARE_HULL, &apart).2);
+    let (on_square, on_triangle, gap) = hull_distance(&SQUARE_HULL, &TRIANGLE_HULL);
+    assert_eq!((on_square, 0.0), (on_triangle, gap));
+    assert!(PointLocator::new(&SQUARE_HULL).contains(&on_square) && PointLocator::new(&TRIANGLE_HULL).contains(&on_square));
+    let touching: Vec<Point2D> = SQUARE_HULL.iter().map(|point| Point2D::new(point.x + 2.0, point.y + 1.0)).collect();
+    assert_eq!(0.0, hull_distance(&SQUARE_HULL, &touching).2);
+    let inside = Point2D::new(0.5, 1.5);
+    assert_eq!((inside, inside, 0.0), hull_distance(&[inside], &SQUARE_HULL));
+}
+
+#[test]
+fn test_hausdorff_distance() {
+    assert_eq!(0.0, hausdorff_distance(&SQUARE_HULL, &SQUARE_HULL));
+    // the corner (0, 2) of the square is √2 away from the triangle, its
+    // corner (4, 0) 2 away from the square
+    assert_eq!(2.0, hausdorff_distance(&SQUARE_HULL, &TRIANGLE_HULL));
+    let cut = [Point2D::new(0.0, 0.0), Point2D::new(2.0, 0.0), Point2D::new(2.0, 2.0)];
+    assert!((hausdorff_distance(&SQUARE_HULL, &cut) - 2.0_f64.sqrt()).abs() < 1e-12);
+    // the square is inside the triangle, whose corners reach 2 beyond it
+    let triangle = [Point2D::new(0.0, 0.0), Point2D::new(4.0, 0.0), Point2D::new(0.0, 4.0)];
+    assert_eq!(2.0, hausdorff_distance(&SQUARE_HULL, &triangle));
+    // the same either way round and in either orientation
+    let mut clockwise = triangle.to_vec();
+    clockwise.reverse();
+    assert_eq!(hausdorff_distance(&SQUARE_HULL, &triangle), hausdorff_distance(&clockwise, &SQUARE_HULL));
+    let far = [Point2D::new(10.0, 0.0)];
+    assert!((hausdorff_distance(&SQUARE_HULL, &far) - 104f64.sqrt()).abs() < 1e-12);
+}
+
+#[test]
+fn test_closest_point_on_hull() {
+    let nearest = |hull: &[Point2D], point: &Point2D| {
+        (0..hull.len())
+            .map(|i| {
+                let (from, to) = (hull[i], hull[(i + 1) % hull.len()]);
+                let (dx, dy) = (to.x - from.x, to.y - from.y);
+                let t = (((point.x - from.x) * dx + (point.y - from.y) * dy) / (dx * dx + dy * dy)).clamp(0.0, 1.0);
+                ((point.x - from.x - t * dx).powi(2) + (point.y - from.y - t * dy).powi(2)).sqrt()
+            })
+            .fold(f64::MAX, f64::min)
+    };
+    // a thin ellipse both ways round, and a square with points on its edges
+    let ellipse: Vec<Point2D> = (0..200)
+        .map(|i| {
+            let angle = i as f64 * std::f64::consts::PI / 100.0;
+            Point2D::new(10.0 * angle.cos(), angle.sin())
+        })
+        .collect();
+    let edges = vec![
+        Point2D::new(0.0, 0.0),
+        Point2D::new(1.0, 0.0),
+        Point2D::new(2.0, 0.0),
+        Point2D::new(2.0, 1.0),
+        Point2D::new(2.0, 2.0),
+        Point2D::new(0.0, 2.0),
+    ];
+    for hull in [ellipse.clone(), ellipse.iter().rev().cloned().collect(), edges] {
+        let locator = PointLocator::new(&hull);
+        for k in 0..2000 {
+            let point = Point2D::new(12.0 * (k as f64 * 0.37).sin(), 3.0 * (k as f64 * 0.71).cos());
+            let (on_hull, distance) = locator.closest_point(&point);
+            assert!((nearest(&hull, &point) - distance).abs() < 1e-9, "{:?}", point);
+            assert!((nearest(&hull, &on_hull)).abs() < 1e-9);
+            assert!(((point.x - on_hull.x).powi(2) + (point.y - on_hull.y).powi(2)).sqrt() - distance < 1e-12);
+        }
+    }
+    // nearer to the far side of a long rectangle than to its ends
+    let rectangle = [Point2D::new(0.0, 0.0), Point2D::new(10.0, 0.0), Point2D::new(10.0, 1.0), Point2D::new(0.0, 1.0)];
+    assert_eq!((Point2D::new(5.0, 1.0), 1.0), closest_point_on_hull(&rectangle, &Point2D::new(5.0, 2.0)));
+    assert_eq!((Point2D::new(4.0, 0.0), 0.25), closest_point_on_hull(&rectangle, &Point2D::new(4.0, 0.25)));
+    assert_eq!((Point2D::new(10.0, 1.0), 5.0), closest_point_on_hull(&rectangle, &Point2D::new(13.0, 5.0)));
+    assert_eq!((Point2D::new(1.0, 1.0), 0.0), closest_point_on_hull(&[Point2D::new(0.0, 0.0), Point2D::new(2.0, 2.0)], &Point2D::new(1.0, 1.0)));
+    assert_eq!((Point2D::new(1.0, 1.0), 5.0), closest_point_on_hull(&[Point2D::new(1.0, 1.0)], &Point2D::new(4.0, 5.0)));
+}
+
+#[test]
+fn test_ray_intersect() {
+    // from the left through the square, starting inside, missing, touching and rounding
+    let hit = ray_intersect(&SQUARE_HULL, &Point2D::new(-2.0, 1.0), &Point2D::new(1.0, 0.0)).unwrap();
+    assert_eq!((2.0, 4.0), (hit.entry, hit.exit));
+    assert_eq!((Point2D::new(0.0, 1.0), Point2D::new(2.0, 1.0)), (hit.entry_point, hit.exit_point));
+    let clockwise: Vec<Point2D> = SQUARE_HULL.iter().rev().cloned().collect();
+    let hit = ray_intersect(&clockwise, &Point2D::new(1.0, 1.0), &Point2D::new(0.0, -2.0)).unwrap();
+    assert_eq!((0.0, 0.5, Point2D::new(1.0, 1.0), Point2D::new(1.0, 0.0)), (hit.entry, hit.exit, hit.entry_point, hit.exit_point));
+    assert_eq!(None, ray_intersect(&SQUARE_HULL, &Point2D::new(-2.0, 1.0), &Point2D::new(-1.0, 0.0)));
+    assert_eq!(None, ray_intersect(&SQUARE_HULL, &Point2D::new(-2.0, 3.0), &Point2D::new(1.0, 0.0)));
+    let corner = ray_intersect(&SQUARE_HULL, &Point2D::new(3.0, 1.0), &Point2D::new(-1.0, 1.0)).unwrap();
+    assert_eq!((1.0, 1.0, Point2D::new(2.0, 2.0)), (corner.entry, corner.exit, corner.entry_point));
+    let edge = ray_intersect(&SQUARE_HULL, &Point2D::new(-1.0, 2.0), &Point2D::new(1.0, 0.0)).unwrap();
+    assert_eq!((1.0, 3.0), (edge.entry, edge.exit));
+    let diagonal = ray_intersect(&TRIANGLE_HULL, &Point2D::new(2.0, -1.0), &Point2D::new(0.0, 1.0)).unwrap();
+    assert_eq!((1.0, 3.0), (diagonal.entry, diagonal.exit));
+    assert_eq!(None, ray_intersect(&[Point2D::new(0.0, 0.0), Point2D::new(2.0, 0.0)], &Point2D::new(1.0, -1.0), &Point2D::new(0.0, 1.0)));
+}
+
+#[test]
+fn test_clip_segment() {
+    let (from, to) = (Point2D::new(-1.0, 0.5), Point2D::new(3.0, 1.5));
+    assert_eq!(Some((Point2D::new(0.0, 0.75), Point2D::new(2.0, 1.25))), clip_segment(&SQUARE_HULL, &from, &to));
+    assert_eq!(Some((Point2D::new(2.0, 1.25), Point2D::new(0.0, 0.75))), clip_segment(&SQUARE_HULL, &to, &from));
+    // one end inside, both inside, outside, touching and a single point
+    let inside = Point2D::new(0.5, 0.5);
+    assert_eq!(Some((inside, Point2D::new(2.0, 0.5))), clip_segment(&SQUARE_HULL, &inside, &Point2D::new(4.0, 0.5)));
+    assert_eq!(Some((inside, Point2D::new(1.5, 1.0))), clip_segment(&SQUARE_HULL, &inside, &Point2D::new(1.5, 1.0)));
+    assert_eq!(None, clip_segment(&SQUARE_HULL, &Point2D::new(3.0, 0.0), &Point2D::new(3.0, 2.0)));
+    assert_eq!(None, clip_segment(&SQUARE_HULL, &Point2D::new(-1.0, 0.5), &Point2D::new(-0.5, 3.0)));
+    let touching = clip_segment(&TRIANGLE_HULL, &Point2D::new(1.0, 2.0), &Point2D::new(3.0, 2.0));
+    assert_eq!(Some((Point2D::new(2.0, 2.0), Point2D::new(2.0, 2.0))), touching);
+    assert_eq!(Some((inside, inside)), clip_segment(&SQUARE_HULL, &inside, &inside));
+}
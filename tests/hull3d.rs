@@ -0,0 +1,58 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+extern crate rustalgo;
+use rustalgo::hull3d::*;
+use rustalgo::points::*;
+
+// no input point is in front of a face
+fn assert_convex(points: &[Point3D], faces: &[Face]) {
+    for face in faces {
+        let (a, b, c) = (points[face[0]], points[face[1]], points[face[2]]);
+        let (u, v) = ((b.x - a.x, b.y - a.y, b.z - a.z), (c.x - a.x, c.y - a.y, c.z - a.z));
+        let normal = (u.1 * v.2 - u.2 * v.1, u.2 * v.0 - u.0 * v.2, u.0 * v.1 - u.1 * v.0);
+        for point in points {
+            let offset = (point.x - a.x, point.y - a.y, point.z - a.z);
+            assert!(normal.0 * offset.0 + normal.1 * offset.1 + normal.2 * offset.2 <= 1e-9);
+        }
+    }
+}
+
+#[test]
+fn test_cube() {
+    let mut points: Vec<Point3D> = (0..8)
+        .map(|i| Point3D::new((i & 1) as f64, (i >> 1 & 1) as f64, (i >> 2 & 1) as f64))
+        .collect();
+    points.push(Point3D::new(0.5, 0.5, 0.5));
+    points.push(Point3D::new(0.2, 0.7, 0.1));
+    let faces = convex_hull_3d(&points);
+    // every square side is split into two triangles
+    assert_eq!(12, faces.len());
+    assert!(faces.iter().all(|face| face.iter().all(|&vertex| vertex < 8)));
+    assert_convex(&points, &faces);
+}
+
+#[test]
+fn test_sphere() {
+    // points on a spiral around the unit sphere are all vertices
+    let n = 200;
+    let points: Vec<Point3D> = (0..n)
+        .map(|i| {
+            let z = 1.0 - (2 * i + 1) as f64 / n as f64;
+            let (radius, angle) = ((1.0 - z * z).sqrt(), i as f64 * 2.399963);
+            Point3D::new(radius * angle.cos(), radius * angle.sin(), z)
+        })
+        .collect();
+    let faces = convex_hull_3d(&points);
+    assert_eq!(2 * n - 4, faces.len());
+    assert_convex(&points, &faces);
+}
+
+#[test]
+fn test_flat() {
+    let points: Vec<Point3D> = (0..10).map(|i| Point3D::new(i as f64, (i * i) as f64, 1.0)).collect();
+    assert!(convex_hull_3d(&points).is_empty());
+    assert!(convex_hull_3d(&[]).is_empty());
+}
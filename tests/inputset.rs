@@ -10,7 +10,7 @@ use rustalgo::inputset::*;
 #[test]
 #[should_panic]
 fn test_set_pivot_with_emptyinput() {
-    set_pivot(&mut Vec::new());
+    set_pivot::<f64>(&mut Vec::new());
 }
 
 #[test]
@@ -34,7 +34,107 @@ fn test_sort_polar_angle_ccw() {
     let point_a1 = Point2D::new(1.0, 0.0);
     let point_b1 = Point2D::new(2.0, 2.0);
     let point_c1 = Point2D::new(3.0, 1.0);
-    let mut input_set = vec![point_a, point_b, point_c];
+    let input_set = vec![point_a, point_b, point_c];
     let out_set = vec![point_a1, point_c1, point_b1];
-    assert_eq!(out_set, sort_polar_angle_ccw(&mut input_set));
+    assert_eq!(out_set, sort_polar_angle_ccw(&input_set));
+}
+
+#[test]
+fn test_sort_polar_angle_cross() {
+    // (2, 0) is at angle zero, (1, 1) behind (2, 2) and (0, 0) a copy of the pivot
+    let input_set = vec![
+        Point2D::new(0.0, 0.0),
+        Point2D::new(1.0, 1.0),
+        Point2D::new(0.0, 2.0),
+        Point2D::new(2.0, 2.0),
+        Point2D::new(0.0, 0.0),
+        Point2D::new(2.0, 0.0),
+    ];
+    let out_set = vec![
+        Point2D::new(0.0, 0.0),
+        Point2D::new(2.0, 0.0),
+        Point2D::new(2.0, 2.0),
+        Point2D::new(0.0, 2.0),
+    ];
+    assert_eq!(out_set, sort_polar_angle_cross(&input_set));
+    assert!(sort_polar_angle_cross::<f64>(&Vec::new()).is_empty());
+}
+
+#[test]
+fn test_time_steps() {
+    let moving_point = MovingPoint::new(Point2D::new(1.0, 0.0), Point2D::new(0.5, -1.0));
+    let steps = time_steps(&[moving_point], 3, 2.0);
+    let steps_should_be = vec![
+        vec![Point2D::new(1.0, 0.0)],
+        vec![Point2D::new(2.0, -2.0)],
+        vec![Point2D::new(3.0, -4.0)],
+    ];
+    assert_eq!(steps_should_be, steps);
+}
+
+#[cfg(feature = "generators")]
+#[test]
+fn test_generate_moving() {
+    let moving_points = generate_moving(Shape::Triangle, Number::Hundred, 0.5);
+    assert_eq!(100, moving_points.len());
+    for point in moving_points {
+        assert!((point.velocity.x.powi(2) + point.velocity.y.powi(2)).sqrt() <= 0.5);
+    }
+}
+
+#[cfg(feature = "generators")]
+#[test]
+fn test_random_walk() {
+    let walk = random_walk(Number::Thousand, 0.5);
+    assert_eq!(1_000, walk.len());
+    assert_eq!(Point2D::new(0.0, 0.0), walk[0]);
+    for pair in walk.windows(2) {
+        let step = ((pair[1].x - pair[0].x).powi(2) + (pair[1].y - pair[0].y).powi(2)).sqrt();
+        assert!((step - 0.5).abs() < 1e-9);
+    }
+}
+
+#[cfg(feature = "generators")]
+#[test]
+fn test_poisson_disk() {
+    let input_set = poisson_disk(4.0, 2.0, 0.2);
+    assert!(input_set.len() > 50);
+    for (i, point_a) in input_set.iter().enumerate() {
+        assert!(point_a.x.abs() <= 2.0 && point_a.y.abs() <= 1.0);
+        for point_b in &input_set[i + 1..] {
+            assert!(((point_a.x - point_b.x).powi(2) + (point_a.y - point_b.y).powi(2)).sqrt() >= 0.2);
+        }
+    }
+}
+
+#[test]
+fn test_validate() {
+    let mut input_set = vec![Point2D::new(1.0, 2.0), Point2D::new(1.0, 3.0)];
+    assert_eq!(Ok(()), validate(&input_set));
+    input_set.push(Point2D::new(f64::NAN, 0.0));
+    input_set.push(Point2D::new(0.0, f64::INFINITY));
+    assert_eq!(2, validate(&input_set).unwrap_err().index);
+}
+
+// tests/data/mask.png is 4 x 3 grayscale pixels:
+//   0   255 0   0
+//   128 200 127 0
+//   0   0   0   255
+#[cfg(feature = "image")]
+#[test]
+fn test_from_png() {
+    let mask = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/mask.png");
+    let points = |coordinates: &[(f64, f64)]| -> Vec<Point2D> {
+        coordinates.iter().map(|&(x, y)| Point2D::new(x, y)).collect()
+    };
+    assert_eq!(points(&[(1.0, 0.0), (0.0, 1.0), (1.0, 1.0), (3.0, 2.0)]), from_png(mask, 128).unwrap());
+    assert_eq!(points(&[(1.0, 0.0), (1.0, 1.0), (3.0, 2.0)]), from_png(mask, 200).unwrap());
+    assert_eq!(points(&[(1.0, 0.0), (3.0, 2.0)]), from_png(mask, 201).unwrap());
+    assert_eq!(12, from_png(mask, 0).unwrap().len());
+    // the brightness of a colour pixel is the mean of its channels,
+    // (30, 60, 90) and (255, 255, 0) here
+    let rgb_mask = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/mask_rgb.png");
+    assert_eq!(points(&[(0.0, 0.0), (1.0, 0.0)]), from_png(rgb_mask, 60).unwrap());
+    assert_eq!(points(&[(1.0, 0.0)]), from_png(rgb_mask, 61).unwrap());
+    assert!(from_png(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/missing.png"), 128).is_err());
 }
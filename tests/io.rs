@@ -0,0 +1,82 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+extern crate rustalgo;
+use rustalgo::points::*;
+use rustalgo::io::*;
+use std::path::Path;
+
+#[test]
+fn test_read_csv() {
+    let input = "x,y\n1.0,2.0\n\n# comment\n-3.5, 4\n";
+    let input_set = read_csv(input.as_bytes()).unwrap();
+    assert_eq!(vec![Point2D::new(1.0, 2.0), Point2D::new(-3.5, 4.0)], input_set);
+}
+
+#[test]
+fn test_read_csv_with_invalid_line() {
+    let input = "1.0,2.0\n1.0;2.0\n";
+    assert!(read_csv(input.as_bytes()).is_err());
+}
+
+#[test]
+fn test_read_binary() {
+    let mut input: Vec<u8> = Vec::new();
+    for value in &[1.0f64, 2.0, -3.5, 4.0] {
+        input.extend_from_slice(&value.to_le_bytes());
+    }
+    let input_set = read_binary(&input[..]).unwrap();
+    assert_eq!(vec![Point2D::new(1.0, 2.0), Point2D::new(-3.5, 4.0)], input_set);
+    assert!(read_binary(&input[..20]).is_err());
+}
+
+#[test]
+fn test_format_of() {
+    assert_eq!(Some(Format::Csv), Format::of(Path::new("points.csv")));
+    assert_eq!(Some(Format::Csv), Format::of(Path::new("points.csv.gz")));
+    assert_eq!(Some(Format::Binary), Format::of(Path::new("dir/points.bin.gz")));
+    assert_eq!(Some(Format::GeoJson), Format::of(Path::new("hull.geojson")));
+    assert_eq!(None, Format::of(Path::new("points.gz")));
+}
+
+#[cfg(feature = "geojson")]
+#[test]
+fn test_read_geojson() {
+    let polygon = r#"{"type": "Feature", "properties": {}, "geometry":
+        {"type": "Polygon", "coordinates": [[[0, 0], [2, 0], [2, 2], [0, 0]]]}}"#;
+    let input_set = read_geojson(polygon.as_bytes()).unwrap();
+    assert_eq!(vec![Point2D::new(0.0, 0.0), Point2D::new(2.0, 0.0), Point2D::new(2.0, 2.0)], input_set);
+    let points = r#"{"type": "MultiPoint", "coordinates": [[1.5, -1], [3, 4]]}"#;
+    assert_eq!(vec![Point2D::new(1.5, -1.0), Point2D::new(3.0, 4.0)], read_geojson(points.as_bytes()).unwrap());
+    assert!(read_geojson(r#"{"type": "Point", "coordinates": [1, 2]}"#.as_bytes()).is_err());
+}
+
+#[test]
+fn test_hull_of_file() {
+    use rustalgo::convexhull::convex_hull_of;
+    use std::fs;
+    let input_set: Vec<Point2D> = (0..5000)
+        .map(|i| Point2D::new((i * 37 % 41) as f64, (i * 59 % 43) as f64))
+        .collect();
+    let mut binary: Vec<u8> = Vec::new();
+    let mut csv = String::from("x,y\n");
+    for point in &input_set {
+        binary.extend_from_slice(&point.x.to_le_bytes());
+        binary.extend_from_slice(&point.y.to_le_bytes());
+        csv.push_str(&format!("{},{}\n", point.x, point.y));
+    }
+    let directory = std::env::temp_dir();
+    let binary_path = directory.join(format!("hull_of_file_{}.bin", std::process::id()));
+    let csv_path = directory.join(format!("hull_of_file_{}.csv", std::process::id()));
+    fs::write(&binary_path, &binary).unwrap();
+    fs::write(&csv_path, &csv).unwrap();
+    for &chunk_size in &[1, 7, 1000, 100_000] {
+        assert_eq!(convex_hull_of(&input_set), hull_of_file(&binary_path, chunk_size).unwrap());
+        assert_eq!(convex_hull_of(&input_set), hull_of_file(&csv_path, chunk_size).unwrap());
+    }
+    fs::remove_file(&binary_path).unwrap();
+    fs::remove_file(&csv_path).unwrap();
+    assert!(hull_of_file(&binary_path, 1000).is_err());
+}
@@ -0,0 +1,101 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+extern crate rustalgo;
+use rustalgo::kdtree::*;
+use rustalgo::points::*;
+
+// scattered points on a 17x13 lattice
+fn scattered(n: usize) -> Vec<Point2D> {
+    (0..n)
+        .map(|i| Point2D::new((i * 7 % 17) as f64, (i * 5 % 13) as f64 + 0.1 * i as f64))
+        .collect()
+}
+
+fn distance_squared(a: &Point2D, b: &Point2D) -> f64 {
+    (a.x - b.x).powi(2) + (a.y - b.y).powi(2)
+}
+
+#[test]
+fn test_nearest() {
+    let points = scattered(100);
+    let built = KdTree::build(&points);
+    let mut inserted = KdTree::new();
+    for point in &points {
+        inserted.insert(*point);
+    }
+    assert_eq!(100, built.len());
+    for target in &[Point2D::new(3.3, 4.7), Point2D::new(-5.0, 20.0), Point2D::new(16.0, 0.0)] {
+        let closest = points
+            .iter()
+            .map(|point| distance_squared(point, target))
+            .fold(f64::INFINITY, f64::min);
+        for tree in &[&built, &inserted] {
+            let (index, point) = tree.nearest(target).unwrap();
+            assert_eq!(points[index], point);
+            assert_eq!(closest, distance_squared(&point, target));
+        }
+    }
+    assert_eq!(None, KdTree::new().nearest(&Point2D::new(0.0, 0.0)));
+}
+
+#[test]
+fn test_range_queries() {
+    let points = scattered(100);
+    let tree = KdTree::build(&points);
+    let min = Point2D::new(2.0, 3.0);
+    let max = Point2D::new(9.0, 8.0);
+    let mut in_range = tree.range(&min, &max);
+    in_range.sort();
+    let expected: Vec<usize> = (0..points.len())
+        .filter(|&i| points[i].x >= min.x && points[i].x <= max.x && points[i].y >= min.y && points[i].y <= max.y)
+        .collect();
+    assert_eq!(expected, in_range);
+
+    let center = Point2D::new(8.0, 6.0);
+    let mut in_radius = tree.within_radius(&center, 3.5);
+    in_radius.sort();
+    let expected: Vec<usize> = (0..points.len())
+        .filter(|&i| distance_squared(&points[i], &center) < 3.5_f64.powi(2))
+        .collect();
+    assert_eq!(expected, in_radius);
+}
+
+#[test]
+fn test_k_nearest() {
+    let points = scattered(100);
+    let tree = KdTree::build(&points);
+    let target = Point2D::new(8.2, 6.9);
+    let mut expected: Vec<usize> = (0..points.len()).collect();
+    expected.sort_by(|&a, &b| {
+        distance_squared(&points[a], &target)
+            .partial_cmp(&distance_squared(&points[b], &target))
+            .unwrap()
+    });
+    assert_eq!(expected[..7].to_vec(), tree.k_nearest(&target, 7));
+    assert_eq!(expected, tree.k_nearest(&target, 500));
+    let even: Vec<usize> = expected.iter().cloned().filter(|i| i % 2 == 0).take(5).collect();
+    assert_eq!(even, tree.k_nearest_where(&target, 5, |i| i % 2 == 0));
+    assert!(tree.k_nearest(&target, 0).is_empty());
+}
+
+#[test]
+fn test_inside_hull() {
+    let points = scattered(200);
+    let tree = KdTree::build(&points);
+    let hull = vec![Point2D::new(3.0, 1.0), Point2D::new(12.0, 4.0), Point2D::new(8.0, 15.0), Point2D::new(2.0, 9.0)];
+    let locator = rustalgo::hull::PointLocator::new(&hull);
+    let expected: Vec<usize> = (0..points.len()).filter(|&i| locator.contains(&points[i])).collect();
+    let mut inside = tree.inside_hull(&hull);
+    inside.sort();
+    assert!(!expected.is_empty());
+    assert_eq!(expected, inside);
+    let mut clockwise = hull.clone();
+    clockwise.reverse();
+    inside = tree.inside_hull(&clockwise);
+    inside.sort();
+    assert_eq!(expected, inside);
+    assert!(tree.inside_hull(&[]).is_empty());
+}
@@ -0,0 +1,63 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+extern crate rustalgo;
+use rustalgo::convexhull::*;
+use rustalgo::kinetic::*;
+use rustalgo::points::*;
+
+#[test]
+fn test_point_leaves_and_joins() {
+    // a square with a point moving out through its right side
+    let points = vec![
+        MovingPoint::new(Point2D::new(0.0, 0.0), Point2D::new(0.0, 0.0)),
+        MovingPoint::new(Point2D::new(2.0, 0.0), Point2D::new(0.0, 0.0)),
+        MovingPoint::new(Point2D::new(2.0, 2.0), Point2D::new(0.0, 0.0)),
+        MovingPoint::new(Point2D::new(0.0, 2.0), Point2D::new(0.0, 0.0)),
+        MovingPoint::new(Point2D::new(1.0, 1.0), Point2D::new(1.0, 0.25)),
+    ];
+    let mut hull = KineticHull::new(points, 0.0);
+    assert_eq!(4, hull.hull().len());
+    hull.advance(0.5);
+    assert_eq!(0, hull.events());
+    // it crosses at time 1
+    hull.advance(3.0);
+    assert_eq!(1, hull.events());
+    assert_eq!(5, hull.hull().len());
+    assert!(hull.hull().contains(&Point2D::new(4.0, 1.75)));
+    // from time 4 on the corner (2, 2) is below the edge from (0, 2) to it
+    hull.advance(10.0);
+    assert_eq!(2, hull.events());
+    assert_eq!(4, hull.hull().len());
+    assert!(!hull.hull().contains(&Point2D::new(2.0, 2.0)));
+    assert_eq!(10.0, hull.time());
+}
+
+#[test]
+fn test_matches_recomputation() {
+    // points in general position moving at different speeds
+    let fraction = |value: f64| value - value.floor();
+    let points: Vec<MovingPoint> = (0..200)
+        .map(|i| {
+            let i = i as f64;
+            MovingPoint::new(
+                Point2D::new(40.0 * fraction(i * 0.618_034), 40.0 * fraction(i * 0.414_214)),
+                Point2D::new(fraction(i * 0.302_776) - 0.5, fraction(i * 0.732_051) - 0.5),
+            )
+        })
+        .collect();
+    let mut hull = KineticHull::new(points.clone(), 0.0);
+    for step in 1..50 {
+        let time = step as f64 * 0.37;
+        hull.advance(time);
+        let positions: Vec<Point2D> = points.iter().map(|point| point.at(time)).collect();
+        let mut kinetic = hull.hull();
+        let mut recomputed = convex_hull_of(&positions);
+        kinetic.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap()));
+        recomputed.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap()));
+        assert_eq!(recomputed, kinetic);
+    }
+    assert!(hull.events() > 0);
+}
@@ -21,9 +21,98 @@ fn test_ccw() {
     let point_a = Point2D::new(1.0, 1.0);
     let point_b = Point2D::new(2.0, 2.0);
     let point_c = Point2D::new(3.0, 2.5);
-    assert_eq!(false, point_a.ccw(&point_b, &point_c));
+    assert!(!point_a.ccw(&point_b, &point_c));
     let point_a = Point2D::new(0.0, 0.0);
     let point_b = Point2D::new(1.0, 1.0);
     let point_c = Point2D::new(2.0, 0.0);
-    assert_eq!(true, point_a.ccw(&point_c, &point_b));
+    assert!(point_a.ccw(&point_c, &point_b));
+}
+
+#[test]
+fn test_bounding_box() {
+    use rustalgo::samples::*;
+    assert_eq!((Point2D::new(0.0, 0.0), Point2D::new(2.0, 2.0)), bounding_box(&SQUARE_WITH_CENTER));
+    assert_eq!((Point2D::new(0.0, 0.0), Point2D::new(4.0, 2.0)), bounding_box(&TRIANGLE_HULL));
+    let point = Point2D::new(-1.0, 3.0);
+    assert_eq!((point, point), bounding_box(&[point]));
+    // points with a payload
+    assert_eq!((Point2D::new(-1.0, 0.0), Point2D::new(0.0, 3.0)), bounding_box(&[(point, 'a'), (Point2D::new(0.0, 0.0), 'b')]));
+}
+
+#[test]
+fn test_dedup_points() {
+    let mut points = vec![
+        Point2D::new(0.0, 0.0),
+        Point2D::new(1.0, 1.0),
+        Point2D::new(-0.0, 0.0),
+        Point2D::new(1.0, 1.0),
+        Point2D::new(1.0, 1.0 + 1e-12),
+    ];
+    dedup_points(&mut points, 0.0);
+    assert_eq!(vec![Point2D::new(0.0, 0.0), Point2D::new(1.0, 1.0), Point2D::new(1.0, 1.0 + 1e-12)], points);
+    dedup_points(&mut points, 1e-9);
+    assert_eq!(vec![Point2D::new(0.0, 0.0), Point2D::new(1.0, 1.0)], points);
+    // close across a cell border, but not to the ones a tolerance away
+    let mut row: Vec<Point2D> = (0..10).map(|i| Point2D::new(i as f64 * 0.3 - 1.0, 0.0)).collect();
+    row.push(Point2D::new(-0.05, 0.0));
+    row.push(Point2D::new(0.0, 0.55));
+    dedup_points(&mut row, 0.5);
+    let kept: Vec<f64> = row.iter().map(|point| point.x).collect();
+    assert_eq!(vec![-1.0, -1.0 + 0.3 * 2.0, -1.0 + 0.3 * 4.0, -1.0 + 0.3 * 6.0, -1.0 + 0.3 * 8.0, 0.0], kept);
+}
+
+#[test]
+fn test_other_coordinate_types() {
+    use rustalgo::convexhull::*;
+    let pixels: Vec<Point2D<i32>> = vec![Point2D::new(0, 0), Point2D::new(4, 0), Point2D::new(1, 1), Point2D::new(2, 3), Point2D::new(0, 3)];
+    let hull = convex_hull_of(&pixels);
+    assert_eq!(vec![Point2D::new(0, 0), Point2D::new(4, 0), Point2D::new(2, 3), Point2D::new(0, 3)], hull);
+    assert_eq!(vec![0, 1, 3, 4], hull_indices(&pixels));
+    assert!(!in_convex_position(&pixels));
+    assert_eq!((Point2D::new(0.0, 0.0), Point2D::new(4.0, 3.0)), bounding_box(&pixels));
+    let wide: Vec<Point2D<i64>> = pixels.iter().map(|pixel| Point2D::new(i64::from(pixel.x), i64::from(pixel.y))).collect();
+    assert_eq!(4, convex_hull_of(&wide).len());
+    let single: Vec<Point2D<f32>> = pixels.iter().map(|pixel| Point2D::new(pixel.x as f32 * 0.5, pixel.y as f32)).collect();
+    assert_eq!(Point2D::new(2.0f32, 0.0), convex_hull_of(&single)[1]);
+}
+
+#[test]
+fn test_exact_orientation() {
+    use rustalgo::geometry::Orientation;
+    let (low, high) = (Point2D::new(i64::MIN, i64::MIN), Point2D::new(i64::MAX, i64::MAX));
+    assert_eq!(Orientation::Clockwise, exact_orientation(&low, &high, &Point2D::new(i64::MAX, i64::MAX - 1)));
+    assert_eq!(Orientation::CounterClockwise, exact_orientation(&low, &high, &Point2D::new(i64::MAX - 1, i64::MAX)));
+    assert_eq!(Orientation::Collinear, exact_orientation(&low, &high, &Point2D::new(0, 0)));
+    assert_eq!(Orientation::Collinear, exact_orientation(&low, &low, &high));
+    let pixel = |x: i32, y: i32| Point2D::new(x, y);
+    assert_eq!(Orientation::Clockwise, i32::orientation(&pixel(i32::MIN, i32::MIN), &pixel(i32::MAX, i32::MAX), &pixel(i32::MAX, i32::MAX - 1)));
+    assert_eq!(Orientation::CounterClockwise, f64::orientation(&Point2D::new(0.0, 0.0), &Point2D::new(1.0, 0.0), &Point2D::new(1.0, 1.0)));
+}
+
+#[test]
+fn test_fatpoint_extreme_coordinates() {
+    use std::cmp::Ordering;
+    //differences of these coordinates overflow i32
+    let vertex = Point2D::new(i32::MIN, i32::MIN);
+    let far = Fatpoint2D::new(&Point2D::new(i32::MAX, i32::MAX), &vertex);
+    let near = Fatpoint2D::new(&Point2D::new(0, 0), &vertex);
+    let level = Fatpoint2D::new(&Point2D::new(i32::MAX, i32::MIN), &vertex);
+    assert!(far.eq_polar_angle(&near));
+    assert_eq!(Some(Ordering::Greater), far.partial_cmp_distance(&near));
+    assert_eq!(Some(Ordering::Less), level.partial_cmp(&far));
+}
+
+#[test]
+fn test_convex_hull_exact() {
+    use rustalgo::convexhull::*;
+    // a hair off the diagonal, which f64 rounds onto it
+    let corner = Point2D::new(1i64 << 59, (1i64 << 59) + 1);
+    let points = vec![Point2D::new(0, 0), Point2D::new(1i64 << 60, 1i64 << 60), corner];
+    assert_eq!(2, convex_hull_of(&points).len());
+    assert_eq!(vec![points[0], points[1], corner], convex_hull_exact(&points));
+    let pixels: Vec<Point2D<i32>> = (0..400).map(|i| Point2D::new(i % 20 * 3, i / 20 * 7)).collect();
+    assert_eq!(convex_hull_of(&pixels), convex_hull_exact(&pixels));
+    let floats: Vec<Point2D> = pixels.iter().map(|pixel| pixel.to_f64()).collect();
+    assert_eq!(convex_hull_of(&floats), convex_hull_exact(&floats));
+    assert_eq!(vec![Point2D::new(1, 1)], convex_hull_exact(&[Point2D::new(1, 1); 3]));
 }
@@ -0,0 +1,137 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+extern crate rustalgo;
+#[cfg(feature = "geo")]
+extern crate geo_types;
+use rustalgo::convexhull::*;
+use rustalgo::polygon::*;
+use rustalgo::points::*;
+
+// a simple polygon with two notches and a collinear vertex
+fn notched() -> Vec<Point2D> {
+    vec![
+        Point2D::new(0.0, 0.0),
+        Point2D::new(2.0, 0.0),
+        Point2D::new(4.0, 0.0),
+        Point2D::new(4.0, 4.0),
+        Point2D::new(3.0, 1.0),
+        Point2D::new(2.0, 4.0),
+        Point2D::new(1.0, 3.0),
+        Point2D::new(0.0, 4.0),
+        Point2D::new(1.0, 2.0),
+    ]
+}
+
+#[test]
+fn test_melkman() {
+    let expected = convex_hull_of(&notched());
+    assert_eq!(4, expected.len());
+    // every starting vertex and both directions give the same hull
+    for start in 0..notched().len() {
+        let mut polygon = notched();
+        polygon.rotate_left(start);
+        assert_eq!(expected, melkman(&polygon));
+        polygon.reverse();
+        assert_eq!(expected, melkman(&polygon));
+    }
+    let collinear = vec![Point2D::new(0.0, 0.0), Point2D::new(1.0, 1.0), Point2D::new(2.0, 2.0)];
+    assert_eq!(2, melkman(&collinear).len());
+}
+
+#[test]
+fn test_is_convex() {
+    assert!(!is_convex(&notched()));
+    let mut hull = convex_hull_of(&notched());
+    assert!(is_convex(&hull));
+    hull.reverse();
+    assert!(is_convex(&hull));
+    // collinear runs, repeated vertices and the closing vertex
+    let square = vec![
+        Point2D::new(0.0, 0.0),
+        Point2D::new(1.0, 0.0),
+        Point2D::new(2.0, 0.0),
+        Point2D::new(2.0, 2.0),
+        Point2D::new(2.0, 2.0),
+        Point2D::new(0.0, 2.0),
+        Point2D::new(0.0, 1.0),
+        Point2D::new(0.0, 0.0),
+    ];
+    assert!(is_convex(&square));
+    assert!(Ring::new(square.clone()).is_convex());
+    // a spike back along the bottom side
+    let mut spike = square.clone();
+    spike.insert(3, Point2D::new(1.5, 0.0));
+    assert!(!is_convex(&spike));
+    // a pentagram turns the same way at every vertex, but twice around
+    let star: Vec<Point2D> = (0..5)
+        .map(|i| {
+            let angle = (i * 2) as f64 * 2.0 * std::f64::consts::PI / 5.0;
+            Point2D::new(angle.cos(), angle.sin())
+        })
+        .collect();
+    assert!(!is_convex(&star));
+    let collinear = vec![Point2D::new(0.0, 0.0), Point2D::new(1.0, 1.0), Point2D::new(2.0, 2.0)];
+    assert!(!is_convex(&collinear));
+    assert!(!is_convex(&collinear[..2]));
+}
+
+#[test]
+fn test_ring_from_wkt() {
+    let ring = Ring::from_wkt("POLYGON ((0 0, 2 0, 4 0, 4 4, 3 1, 2 4, 1 3, 0 4, 1 2, 0 0), (1 1, 2 1, 2 2, 1 1))").unwrap();
+    assert_eq!(&notched()[..], ring.vertices());
+    assert_eq!(convex_hull_of(&notched()), ring.hull());
+    assert_eq!(ring, Ring::from_wkt("polygon Z((0 0 1, 2 0 1, 4 0 1, 4 4 1, 3 1 1, 2 4 1, 1 3 1, 0 4 1, 1 2 1))").unwrap());
+    assert!(Ring::from_wkt("POINT (1 2)").is_err());
+    assert!(Ring::from_wkt("POLYGON ((0 0, 1 x, 2 2))").is_err());
+    assert!(Ring::from_wkt("POLYGON ((0 0, 1 1").is_err());
+}
+
+#[cfg(feature = "geo")]
+#[test]
+fn test_ring_from_geo() {
+    use geo_types::{Coordinate, LineString, Polygon};
+    let exterior = notched()
+        .iter()
+        .chain(notched().first())
+        .map(|point| Coordinate { x: point.x, y: point.y })
+        .collect();
+    let polygon = Polygon::new(LineString(exterior), vec![]);
+    assert_eq!(&notched()[..], Ring::from(&polygon).vertices());
+}
+
+#[test]
+fn test_hull_of_segments() {
+    let segments = vec![
+        (Point2D::new(0.0, 0.0), Point2D::new(4.0, 0.0)),
+        (Point2D::new(1.0, 1.0), Point2D::new(2.0, 5.0)),
+        // shares its start with the end of the first segment
+        (Point2D::new(4.0, 0.0), Point2D::new(3.0, 1.0)),
+    ];
+    let hull = hull_of_segments(&segments);
+    let sources: Vec<(usize, usize)> = hull.iter().map(|&(_, source)| (source.primitive, source.vertex)).collect();
+    assert_eq!(vec![(0, 0), (0, 1), (1, 1)], sources);
+    assert_eq!(Point2D::new(2.0, 5.0), hull[2].0);
+    assert!(hull_of_segments(&[]).is_empty());
+}
+
+#[test]
+fn test_hull_of_polygons() {
+    let polygons = vec![
+        Ring::new(notched()),
+        Ring::from_wkt("POLYGON ((5 0, 7 2, 5 4, 5 0))").unwrap(),
+    ];
+    let hull = hull_of_polygons(&polygons);
+    let expected = vec![
+        (Point2D::new(0.0, 0.0), Source { primitive: 0, vertex: 0 }),
+        (Point2D::new(5.0, 0.0), Source { primitive: 1, vertex: 0 }),
+        (Point2D::new(7.0, 2.0), Source { primitive: 1, vertex: 1 }),
+        (Point2D::new(5.0, 4.0), Source { primitive: 1, vertex: 2 }),
+        (Point2D::new(0.0, 4.0), Source { primitive: 0, vertex: 7 }),
+    ];
+    assert_eq!(expected, hull);
+    let lists = vec![vec![Point2D::new(0.0, 0.0)], vec![Point2D::new(1.0, 0.0), Point2D::new(0.0, 1.0)]];
+    assert_eq!(3, hull_of_polygons(&lists).len());
+}
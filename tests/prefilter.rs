@@ -0,0 +1,62 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+extern crate rustalgo;
+use rustalgo::convexhull::*;
+use rustalgo::prefilter::*;
+use rustalgo::points::*;
+
+// a 20x20 lattice, slightly perturbed so that no three points are collinear
+fn lattice() -> Vec<Point2D> {
+    (0..400)
+        .map(|i| {
+            let (x, y) = ((i % 20) as f64, (i / 20) as f64);
+            Point2D::new(x + 0.001 * y * y, y + 0.001 * x * x)
+        })
+        .collect()
+}
+
+#[test]
+fn test_grid_filter() {
+    let input_set = lattice();
+    let filtered = Prefilter::Grid.apply(&input_set);
+    assert!(filtered.len() < input_set.len() / 2);
+    assert!(filtered.iter().all(|point| input_set.contains(point)));
+    let mut hull = convex_hull_of(&input_set);
+    let mut filtered_hull = convex_hull_of(&filtered);
+    hull.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap());
+    filtered_hull.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap());
+    assert_eq!(hull, filtered_hull);
+    assert!(grid_filter(&[]).is_empty());
+}
+
+#[test]
+fn test_prune_akl_toussaint() {
+    // the extremes of the lattice are on a line, so use a disc
+    let input_set: Vec<Point2D> = (0..400)
+        .map(|i| {
+            let (angle, radius) = (i as f64 * 2.4, (i as f64 / 400.0).sqrt());
+            Point2D::new(radius * angle.cos(), radius * angle.sin())
+        })
+        .collect();
+    let pruned = Prefilter::AklToussaint.apply(&input_set);
+    assert!(pruned.len() < input_set.len() * 2 / 3);
+    assert!(pruned.iter().all(|point| input_set.contains(point)));
+    let mut hull = convex_hull_of(&input_set);
+    let mut pruned_hull = convex_hull_of(&pruned);
+    hull.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap());
+    pruned_hull.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap());
+    assert_eq!(hull, pruned_hull);
+    // a diamond around its center
+    let diamond = [
+        Point2D::new(0.0, 1.0),
+        Point2D::new(1.0, 0.0),
+        Point2D::new(2.0, 1.0),
+        Point2D::new(1.0, 2.0),
+        Point2D::new(1.0, 1.0),
+    ];
+    assert_eq!(diamond[..4].to_vec(), prune_akl_toussaint(&diamond));
+    assert!(prune_akl_toussaint::<f64>(&[]).is_empty());
+}
@@ -0,0 +1,63 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+#![cfg(feature = "rational")]
+
+extern crate rustalgo;
+use rustalgo::convexhull::*;
+use rustalgo::geometry::Orientation;
+use rustalgo::points::*;
+use rustalgo::rational::*;
+use rustalgo::samples::{COLLINEAR, SQUARE_HULL, SQUARE_WITH_CENTER};
+
+fn exact(x: f64, y: f64) -> RationalPoint {
+    RationalPoint::from_point(&Point2D::new(x, y)).unwrap()
+}
+
+#[test]
+fn test_rational_orientation() {
+    let ulp = 1.0 / 9_007_199_254_740_992.0;
+    let (q, r) = (exact(12.0, 12.0), exact(24.0, 24.0));
+    assert_eq!(Orientation::Collinear, rational_orientation(&exact(0.5, 0.5), &q, &r));
+    //a hair left of the line, which the f64 cross product rounds onto it
+    assert_eq!(Orientation::CounterClockwise, rational_orientation(&q, &r, &exact(0.5, 0.5 + ulp)));
+    assert_eq!(Orientation::Clockwise, rational_orientation(&q, &r, &exact(0.5 + ulp, 0.5)));
+    assert_eq!(None, RationalPoint::from_point(&Point2D::new(1.0, f64::NAN)));
+}
+
+#[test]
+fn test_exact_hull() {
+    assert_eq!(SQUARE_HULL.to_vec(), exact_hull(&SQUARE_WITH_CENTER));
+    assert_eq!(vec![COLLINEAR[0], COLLINEAR[COLLINEAR.len() - 1]], exact_hull(&COLLINEAR));
+    let points: Vec<Point2D> = (0..200).map(|i| Point2D::new((i as f64 * 0.37).sin(), (i as f64 * 0.91).cos())).collect();
+    assert_eq!(convex_hull_of(&points), exact_hull(&points));
+}
+
+#[test]
+fn test_exact_hull_near_line() {
+    let ulp = 1.0 / 9_007_199_254_740_992.0;
+    let mut points = vec![Point2D::new(12.0, 12.0), Point2D::new(24.0, 24.0)];
+    for i in 0..8 {
+        for j in 0..8 {
+            points.push(Point2D::new(0.5 + i as f64 * ulp, 0.5 + j as f64 * ulp));
+        }
+    }
+    let hull = exact_hull(&points);
+    //only the corners of the tiny grid far enough off the line are vertices
+    let corners = vec![
+        Point2D::new(0.5, 0.5),
+        Point2D::new(0.5 + 7.0 * ulp, 0.5),
+        Point2D::new(24.0, 24.0),
+        Point2D::new(0.5, 0.5 + 7.0 * ulp),
+    ];
+    assert_eq!(corners, hull);
+    let n = hull.len();
+    for i in 0..n {
+        let (a, b) = (RationalPoint::from_point(&hull[i]).unwrap(), RationalPoint::from_point(&hull[(i + 1) % n]).unwrap());
+        for point in &points {
+            assert_ne!(Orientation::Clockwise, rational_orientation(&a, &b, &RationalPoint::from_point(point).unwrap()));
+        }
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+extern crate rustalgo;
+use rustalgo::geometry::Orientation;
+use rustalgo::points::*;
+use rustalgo::robust::orient2d;
+
+/// 2^-53, the distance between the f64 values near 0.5
+const ULP: f64 = 1.0 / 9_007_199_254_740_992.0;
+
+/// Points a few ulps around (0.5, 0.5), with (12, 12) and (24, 24)
+/// nearly on a line through them
+fn near_line() -> Vec<Point2D> {
+    let mut points: Vec<Point2D> = Vec::new();
+    for i in 0..32 {
+        for j in 0..32 {
+            points.push(Point2D::new(0.5 + i as f64 * ULP, 0.5 + j as f64 * ULP));
+        }
+    }
+    points
+}
+
+/// The point as an integer multiple of ULP, which is exact for the
+/// points of near_line
+fn scaled(point: &Point2D) -> Point2D<i64> {
+    Point2D::new((point.x / ULP) as i64, (point.y / ULP) as i64)
+}
+
+fn exact(a: &Point2D, b: &Point2D, c: &Point2D) -> Orientation {
+    exact_orientation(&scaled(a), &scaled(b), &scaled(c))
+}
+
+fn sign(turn: f64) -> Orientation {
+    if turn > 0.0 {
+        Orientation::CounterClockwise
+    } else if turn < 0.0 {
+        Orientation::Clockwise
+    } else {
+        Orientation::Collinear
+    }
+}
+
+#[test]
+fn test_orient2d_matches_exact_turns() {
+    let (q, r) = (Point2D::new(12.0, 12.0), Point2D::new(24.0, 24.0));
+    let mut plain_wrong = 0;
+    for p in near_line() {
+        assert_eq!(exact(&p, &q, &r), sign(orient2d(&p, &q, &r)), "{:?}", p);
+        assert_eq!(exact(&q, &r, &p), sign(orient2d(&q, &r, &p)), "{:?}", p);
+        let plain = (q.x - p.x) * (r.y - p.y) - (q.y - p.y) * (r.x - p.x);
+        if sign(plain) != exact(&p, &q, &r) {
+            plain_wrong += 1;
+        }
+    }
+    //the f64 cross product gets many of them wrong
+    assert!(plain_wrong > 0);
+}
+
+#[test]
+fn test_orient2d_ordinary_points() {
+    let (a, b) = (Point2D::new(0.0, 0.0), Point2D::new(4.0, 0.0));
+    assert_eq!(8.0, orient2d(&a, &b, &Point2D::new(2.0, 2.0)));
+    assert_eq!(-8.0, orient2d(&a, &b, &Point2D::new(2.0, -2.0)));
+    assert_eq!(0.0, orient2d(&a, &b, &Point2D::new(7.0, 0.0)));
+    assert_eq!(0.0, orient2d(&a, &a, &b));
+}
+
+/// Whether the hull is strictly convex and has no input point outside,
+/// with exact turns
+#[cfg(feature = "robust")]
+fn assert_convex(hull: &[Point2D], points: &[Point2D]) {
+    let n = hull.len();
+    for i in 0..n {
+        let (a, b) = (&hull[i], &hull[(i + 1) % n]);
+        assert_eq!(Orientation::CounterClockwise, exact(a, b, &hull[(i + 2) % n]), "{:?}", hull);
+        assert!(points.iter().all(|point| exact(a, b, point) != Orientation::Clockwise), "{:?}", hull);
+    }
+}
+
+#[cfg(feature = "robust")]
+#[test]
+fn test_robust_graham_and_jarvis() {
+    use rustalgo::convexhull::*;
+    let mut points = near_line();
+    points.push(Point2D::new(12.0, 12.0));
+    points.push(Point2D::new(24.0, 24.0));
+    let mut sorted = sort_input(&mut points.clone());
+    assert_convex(&graham_scan(&mut sorted), &points);
+    assert_convex(&jarvis_march(&mut sorted), &points);
+}
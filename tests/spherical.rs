@@ -0,0 +1,68 @@
+// Copyright © 2018 Yokesh Thirumoorthi
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+extern crate rustalgo;
+use rustalgo::points::*;
+use rustalgo::spherical::*;
+
+// the same vertices in the same order, from any of them
+fn assert_ring(expected: &[Point2D], hull: &[Point2D]) {
+    assert_eq!(expected.len(), hull.len());
+    let first = expected.iter().position(|vertex| *vertex == hull[0]).unwrap();
+    for (i, vertex) in hull.iter().enumerate() {
+        assert_eq!(expected[(first + i) % expected.len()], *vertex);
+    }
+}
+
+#[test]
+fn test_across_the_antimeridian() {
+    let corners = vec![
+        Point2D::new(170.0, -10.0),
+        Point2D::new(-170.0, -10.0),
+        Point2D::new(-170.0, 10.0),
+        Point2D::new(170.0, 10.0),
+    ];
+    let mut points = corners.clone();
+    points.push(Point2D::new(180.0, 0.0));
+    points.push(Point2D::new(175.0, 5.0));
+    points.push(Point2D::new(-175.0, -5.0));
+    assert_ring(&corners, &spherical_hull(&points).unwrap());
+}
+
+#[test]
+fn test_around_the_pole() {
+    let mut points: Vec<Point2D> = (0..8).map(|i| Point2D::new(i as f64 * 45.0 - 180.0, 80.0)).collect();
+    points.push(Point2D::new(0.0, 90.0));
+    points.push(Point2D::new(30.0, 85.0));
+    // counter clockwise seen from above the pole
+    assert_ring(&points[..8], &spherical_hull(&points).unwrap());
+}
+
+#[test]
+fn test_far_from_the_mean() {
+    // the cluster pulls the mean direction more than a quarter turn
+    // away from the last point
+    let mut points: Vec<Point2D> = (0..40).map(|i| Point2D::new((i % 5) as f64, (i / 5) as f64 - 4.0)).collect();
+    points.push(Point2D::new(120.0, 0.0));
+    let hull = spherical_hull(&points).unwrap();
+    assert!(hull.contains(&Point2D::new(120.0, 0.0)));
+    assert!(hull.contains(&Point2D::new(0.0, -4.0)));
+    assert!(hull.contains(&Point2D::new(0.0, 3.0)));
+    assert!(!hull.contains(&Point2D::new(2.0, 0.0)));
+}
+
+#[test]
+fn test_no_hemisphere() {
+    let octahedron = vec![
+        Point2D::new(0.0, 0.0),
+        Point2D::new(90.0, 0.0),
+        Point2D::new(180.0, 0.0),
+        Point2D::new(-90.0, 0.0),
+        Point2D::new(0.0, 90.0),
+        Point2D::new(0.0, -90.0),
+    ];
+    assert_eq!(None, spherical_hull(&octahedron));
+    assert_eq!(Some(Vec::new()), spherical_hull(&[]));
+}